@@ -56,6 +56,21 @@ pub extern "C" fn obs_hotkey_register_source(
     panic!()
 }
 
+#[no_mangle]
+pub extern "C" fn obs_hotkey_unregister(_id: obs_hotkey_id) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_key_from_virtual_key(_code: c_int) -> obs_key_t {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_key_from_name(_name: *const c_char) -> obs_key_t {
+    panic!()
+}
+
 #[no_mangle]
 pub extern "C" fn obs_properties_create() -> *mut obs_properties_t {
     panic!()
@@ -99,6 +114,18 @@ pub extern "C" fn obs_properties_add_int(
     panic!()
 }
 
+#[no_mangle]
+pub extern "C" fn obs_properties_add_int_slider(
+    _props: *mut obs_properties_t,
+    _name: *const c_char,
+    _description: *const c_char,
+    _min: c_int,
+    _max: c_int,
+    _step: c_int,
+) -> *mut obs_property_t {
+    panic!()
+}
+
 #[no_mangle]
 pub extern "C" fn obs_data_get_int(_data: *mut obs_data_t, _name: *const c_char) -> c_longlong {
     panic!()
@@ -135,6 +162,21 @@ pub extern "C" fn gs_effect_set_texture(_param: *mut gs_eparam_t, _val: *mut gs_
     panic!()
 }
 
+#[no_mangle]
+pub extern "C" fn gs_effect_set_texture_srgb(_param: *mut gs_eparam_t, _val: *mut gs_texture_t) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn gs_framebuffer_srgb_enabled() -> bool {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn gs_enable_framebuffer_srgb(_enable: bool) {
+    panic!()
+}
+
 #[no_mangle]
 pub extern "C" fn gs_technique_begin(_technique: *mut gs_technique_t) -> size_t {
     panic!()
@@ -160,6 +202,11 @@ pub extern "C" fn obs_get_base_effect(_effect: obs_base_effect) -> *mut gs_effec
     panic!()
 }
 
+#[no_mangle]
+pub extern "C" fn obs_get_version() -> u32 {
+    panic!()
+}
+
 #[no_mangle]
 pub extern "C" fn obs_data_set_default_int(
     _data: *mut obs_data_t,
@@ -178,3 +225,447 @@ pub extern "C" fn obs_properties_add_button(
 ) -> *mut obs_property_t {
     panic!()
 }
+
+#[no_mangle]
+pub extern "C" fn obs_properties_add_bool(
+    _props: *mut obs_properties_t,
+    _name: *const c_char,
+    _description: *const c_char,
+) -> *mut obs_property_t {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_data_get_bool(_data: *mut obs_data_t, _name: *const c_char) -> bool {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_data_set_default_bool(
+    _data: *mut obs_data_t,
+    _name: *const c_char,
+    _val: bool,
+) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_data_get_json(_data: *mut obs_data_t) -> *const c_char {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_data_create_from_json(_json_string: *const c_char) -> *mut obs_data_t {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_data_apply(_target: *mut obs_data_t, _apply_data: *mut obs_data_t) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_data_release(_data: *mut obs_data_t) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_get_proc_handler() -> *mut proc_handler_t {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn proc_handler_call(
+    _handler: *mut proc_handler_t,
+    _name: *const c_char,
+    _params: *mut calldata_t,
+) -> bool {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn calldata_set_data(
+    _data: *mut calldata_t,
+    _name: *const c_char,
+    _in: *const c_void,
+    _new_size: size_t,
+) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn calldata_get_data(
+    _data: *const calldata_t,
+    _name: *const c_char,
+    _out: *mut c_void,
+    _size: size_t,
+) -> bool {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn bfree(_ptr: *mut c_void) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_add_tick_callback(_tick: obs_tick_callback_t, _param: *mut c_void) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_remove_tick_callback(_tick: obs_tick_callback_t, _param: *mut c_void) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_properties_add_text(
+    _props: *mut obs_properties_t,
+    _name: *const c_char,
+    _description: *const c_char,
+    _type_: obs_text_type,
+) -> *mut obs_property_t {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_data_set_default_string(
+    _data: *mut obs_data_t,
+    _name: *const c_char,
+    _val: *const c_char,
+) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_source_get_settings(_source: *const obs_source_t) -> *mut obs_data_t {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_data_set_string(
+    _data: *mut obs_data_t,
+    _name: *const c_char,
+    _val: *const c_char,
+) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_data_set_int(_data: *mut obs_data_t, _name: *const c_char, _val: c_longlong) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_data_set_bool(_data: *mut obs_data_t, _name: *const c_char, _val: bool) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_properties_add_group(
+    _props: *mut obs_properties_t,
+    _name: *const c_char,
+    _description: *const c_char,
+    _type_: obs_group_type,
+    _group: *mut obs_properties_t,
+) -> *mut obs_property_t {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_property_set_modified_callback2(
+    _p: *mut obs_property_t,
+    _modified: obs_property_modified2_t,
+    _priv: *mut c_void,
+) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_properties_get(
+    _props: *mut obs_properties_t,
+    _property: *const c_char,
+) -> *mut obs_property_t {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_property_int_set_limits(
+    _p: *mut obs_property_t,
+    _min: c_int,
+    _max: c_int,
+    _step: c_int,
+) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_properties_add_editable_list(
+    _props: *mut obs_properties_t,
+    _name: *const c_char,
+    _description: *const c_char,
+    _type_: obs_editable_list_type,
+    _filter: *const c_char,
+    _default_path: *const c_char,
+) -> *mut obs_property_t {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_properties_add_color_alpha(
+    _props: *mut obs_properties_t,
+    _name: *const c_char,
+    _description: *const c_char,
+) -> *mut obs_property_t {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_properties_add_list(
+    _props: *mut obs_properties_t,
+    _name: *const c_char,
+    _description: *const c_char,
+    _type_: obs_combo_type,
+    _format: obs_combo_format,
+) -> *mut obs_property_t {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_property_list_add_int(
+    _p: *mut obs_property_t,
+    _name: *const c_char,
+    _val: c_longlong,
+) -> size_t {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_property_list_add_string(
+    _p: *mut obs_property_t,
+    _name: *const c_char,
+    _val: *const c_char,
+) -> size_t {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_data_get_array(
+    _data: *mut obs_data_t,
+    _name: *const c_char,
+) -> *mut obs_data_array_t {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_data_array_count(_array: *mut obs_data_array_t) -> size_t {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_data_array_item(
+    _array: *mut obs_data_array_t,
+    _idx: size_t,
+) -> *mut obs_data_t {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_data_array_release(_array: *mut obs_data_array_t) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_data_has_user_value(_data: *mut obs_data_t, _name: *const c_char) -> bool {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_property_set_long_description(
+    _p: *mut obs_property_t,
+    _long_description: *const c_char,
+) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_module_get_config_path(
+    _module: *mut obs_module_t,
+    _file: *const c_char,
+) -> *mut c_char {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_source_update(_source: *mut obs_source_t, _settings: *mut obs_data_t) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_source_update_properties(_source: *mut obs_source_t) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_source_set_audio_active(_source: *mut obs_source_t, _show: bool) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_enum_scenes(_enum_proc: obs_enum_proc_t, _param: *mut c_void) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_source_get_name(_source: *const obs_source_t) -> *const c_char {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_get_source_by_name(_name: *const c_char) -> *mut obs_source_t {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_source_release(_source: *mut obs_source_t) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_source_output_audio(
+    _source: *mut obs_source_t,
+    _audio: *const obs_source_audio,
+) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn os_gettime_ns() -> u64 {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_enum_sources(_enum_proc: obs_enum_proc_t, _param: *mut c_void) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_source_get_unversioned_id(_source: *const obs_source_t) -> *const c_char {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_source_create_private(
+    _id: *const c_char,
+    _name: *const c_char,
+    _settings: *mut obs_data_t,
+) -> *mut obs_source_t {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_queue_task(
+    _type_: obs_task_type,
+    _task: obs_task_t,
+    _param: *mut c_void,
+    _wait: bool,
+) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_data_create() -> *mut obs_data_t {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn calldata_get_string(
+    _data: *const calldata_t,
+    _name: *const c_char,
+    _str: *mut *const c_char,
+) -> bool {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_source_get_proc_handler(_source: *const obs_source_t) -> *mut proc_handler_t {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn proc_handler_add(
+    _handler: *mut proc_handler_t,
+    _decl_string: *const c_char,
+    _proc: proc_handler_proc_t,
+    _data: *mut c_void,
+) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_source_get_signal_handler(
+    _source: *const obs_source_t,
+) -> *mut signal_handler_t {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn signal_handler_add(
+    _handler: *mut signal_handler_t,
+    _signal_decl: *const c_char,
+) -> bool {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn signal_handler_signal(
+    _handler: *mut signal_handler_t,
+    _signal: *const c_char,
+    _params: *mut calldata_t,
+) {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_missing_files_create() -> *mut obs_missing_files_t {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_missing_file_create(
+    _path: *const c_char,
+    _callback: obs_missing_file_cb,
+    _src_type: obs_missing_file_src,
+    _src: *mut c_void,
+    _data: *mut c_void,
+) -> *mut obs_missing_file_t {
+    panic!()
+}
+
+#[no_mangle]
+pub extern "C" fn obs_missing_files_add_file(
+    _files: *mut obs_missing_files_t,
+    _file: *mut obs_missing_file_t,
+) {
+    panic!()
+}
+
+#[cfg(windows)]
+#[no_mangle]
+pub extern "C" fn os_utf8_to_wcs_ptr(
+    _str: *const c_char,
+    _len: size_t,
+    _pstr: *mut *mut wchar_t,
+) -> size_t {
+    panic!()
+}
+
+#[cfg(windows)]
+#[no_mangle]
+pub extern "C" fn os_wcs_to_utf8_ptr(
+    _str: *const wchar_t,
+    _len: size_t,
+    _pstr: *mut *mut c_char,
+) -> size_t {
+    panic!()
+}
@@ -0,0 +1,158 @@
+use std::{
+    ffi::{c_void, CString},
+    os::raw::c_char,
+};
+
+use livesplit_core::{
+    timing::formatter::{Regular, TimeFormatter},
+    Attempt, Run, SharedTimer, TimeSpan, TimingMethod,
+};
+
+use crate::{
+    ffi::{
+        obs_data_get_int, obs_data_release, obs_properties_add_button, obs_properties_add_group,
+        obs_properties_add_list, obs_properties_create, obs_properties_t,
+        obs_property_list_add_int, obs_property_set_long_description, obs_property_t,
+        obs_source_get_settings, OBS_COMBO_FORMAT_INT, OBS_COMBO_TYPE_LIST, OBS_GROUP_NORMAL,
+    },
+    run_maintenance, State,
+};
+
+const SETTINGS_ATTEMPT_HISTORY: *const c_char = cstr!("attempt_history");
+const SETTINGS_ATTEMPT_HISTORY_ATTEMPT: *const c_char = cstr!("attempt_history_attempt");
+const SETTINGS_ATTEMPT_HISTORY_DELETE: *const c_char = cstr!("attempt_history_delete");
+
+/// How many of the most recent attempts are listed.
+const LISTED_ATTEMPTS: usize = 100;
+
+pub unsafe fn add_properties(props: *mut obs_properties_t, timer: Option<&SharedTimer>) {
+    let group = obs_properties_create();
+    let attempts = obs_properties_add_list(
+        group,
+        SETTINGS_ATTEMPT_HISTORY_ATTEMPT,
+        cstr!("Attempt"),
+        OBS_COMBO_TYPE_LIST,
+        OBS_COMBO_FORMAT_INT,
+    );
+    if let Some(timer) = timer {
+        let timer = timer.read().unwrap();
+        let run = timer.run();
+        let method = timer.current_timing_method();
+        for attempt in run.attempt_history().iter().rev().take(LISTED_ATTEMPTS) {
+            if let Ok(name) = CString::new(describe(run, attempt, method)) {
+                obs_property_list_add_int(attempts, name.as_ptr(), attempt.index() as _);
+            }
+        }
+    }
+    let delete = obs_properties_add_button(
+        group,
+        SETTINGS_ATTEMPT_HISTORY_DELETE,
+        cstr!("Delete Attempt"),
+        Some(delete),
+    );
+    obs_property_set_long_description(
+        delete,
+        cstr!("Removes the attempt and its segment times, so it no longer counts towards the statistics. Needs to be confirmed in the Maintenance group."),
+    );
+
+    obs_properties_add_group(
+        props,
+        SETTINGS_ATTEMPT_HISTORY,
+        cstr!("Attempt History"),
+        OBS_GROUP_NORMAL,
+        group,
+    );
+}
+
+/// Describes the attempt with its date, its duration and how far it got.
+fn describe(run: &Run, attempt: &Attempt, method: TimingMethod) -> String {
+    let index = attempt.index();
+    let date = attempt.started().map_or_else(
+        || String::from("Unknown Date"),
+        |started| {
+            let time = started.time;
+            format!("{} {:02}:{:02}", time.date(), time.hour(), time.minute())
+        },
+    );
+    let duration = attempt.time()[method].or_else(|| {
+        let started = attempt.started()?.time.unix_timestamp();
+        let ended = attempt.ended()?.time.unix_timestamp();
+        let pause_time = attempt.pause_time().unwrap_or_default();
+        Some(TimeSpan::from_seconds((ended - started) as f64) - pause_time)
+    });
+    let outcome = if attempt.time()[method].is_some() {
+        String::from("Completed")
+    } else {
+        // The attempt was reset in the segment after the last one it has a
+        // time for.
+        let reached = run
+            .segments()
+            .iter()
+            .rposition(|segment| segment.segment_history().get(index).is_some())
+            .map_or(0, |last| last + 1);
+        match run.segments().get(reached) {
+            Some(segment) => format!("Reset at {}", segment.name()),
+            None => String::from("Reset"),
+        }
+    };
+    format!(
+        "#{index}: {date}, {}, {outcome}",
+        Regular::new().format(duration)
+    )
+}
+
+/// Removes the attempt from the history, along with its segment times. Best
+/// segments that came from the attempt fall back to the best remaining time.
+fn delete_attempt(run: &mut Run, index: i32) {
+    let attempts = run.attempt_history().to_vec();
+    let histories: Vec<_> = run
+        .segments()
+        .iter()
+        .map(|segment| segment.segment_history().clone())
+        .collect();
+    // There's no way to remove a single attempt, so the history is rebuilt
+    // without it.
+    run.clear_history();
+    for attempt in attempts.iter().filter(|attempt| attempt.index() != index) {
+        run.add_attempt_with_index(
+            attempt.time(),
+            attempt.index(),
+            attempt.started(),
+            attempt.ended(),
+            attempt.pause_time(),
+        );
+    }
+    for (segment, mut history) in run.segments_mut().iter_mut().zip(histories) {
+        let removed = history.get(index);
+        history.remove(index);
+        if let Some(removed) = removed {
+            for method in [TimingMethod::RealTime, TimingMethod::GameTime] {
+                if removed[method].is_some()
+                    && removed[method] == segment.best_segment_time()[method]
+                {
+                    segment.best_segment_time_mut()[method] =
+                        history.iter().filter_map(|&(_, time)| time[method]).min();
+                }
+            }
+        }
+        *segment.segment_history_mut() = history;
+    }
+    if attempts.len() > run.attempt_history().len() {
+        run.set_attempt_count(run.attempt_count().saturating_sub(1));
+    }
+}
+
+unsafe extern "C" fn delete(
+    _: *mut obs_properties_t,
+    _: *mut obs_property_t,
+    data: *mut c_void,
+) -> bool {
+    let state: &State = &*data.cast();
+    let settings = obs_source_get_settings(state.source);
+    let index = obs_data_get_int(settings, SETTINGS_ATTEMPT_HISTORY_ATTEMPT) as i32;
+    obs_data_release(settings);
+
+    run_maintenance::maintain(data, "Deleted the attempt.", |run, _| {
+        delete_attempt(run, index)
+    })
+}
@@ -0,0 +1,207 @@
+use std::{os::raw::c_char, path::Path, ptr};
+
+use livesplit_core::{analysis::state_helper, Timer};
+
+use crate::{
+    events::{self, EventTracker, TimerEvent},
+    ffi::{
+        obs_data_get_int, obs_data_get_string, obs_data_set_default_int, obs_data_t,
+        obs_properties_add_group, obs_properties_add_int, obs_properties_add_path,
+        obs_properties_create, obs_properties_t, obs_source_audio, obs_source_output_audio,
+        obs_source_t, os_gettime_ns, AUDIO_FORMAT_FLOAT, MAX_AV_PLANES, OBS_GROUP_NORMAL,
+        OBS_PATH_FILE,
+    },
+    path,
+};
+
+const SETTINGS_AUDIO_CUES: *const c_char = cstr!("audio_cues");
+const SETTINGS_SPLIT_SOUND: *const c_char = cstr!("audio_cues_split");
+const SETTINGS_GOLD_SOUND: *const c_char = cstr!("audio_cues_gold");
+const SETTINGS_FINISH_SOUND: *const c_char = cstr!("audio_cues_finish");
+const SETTINGS_BEHIND_SOUND: *const c_char = cstr!("audio_cues_behind");
+const SETTINGS_VOLUME: *const c_char = cstr!("audio_cues_volume");
+
+const SOUNDS: [(*const c_char, *const c_char); 4] = [
+    (SETTINGS_SPLIT_SOUND, cstr!("Split")),
+    (SETTINGS_GOLD_SOUND, cstr!("Gold Split")),
+    (SETTINGS_FINISH_SOUND, cstr!("Final Split")),
+    (SETTINGS_BEHIND_SOUND, cstr!("Falling Behind")),
+];
+
+/// A decoded sound, with its samples interleaved.
+#[derive(PartialEq)]
+struct Sound {
+    samples: Vec<f32>,
+    channels: u16,
+    sample_rate: u32,
+}
+
+impl Sound {
+    fn load(path: &Path) -> Option<Self> {
+        if path.as_os_str().is_empty() {
+            return None;
+        }
+        let reader = hound::WavReader::open(path)
+            .map_err(|e| log::warn!("Failed to load {}: {e}", path.display()))
+            .ok()?;
+        let spec = reader.spec();
+        // OBS doesn't have a speaker layout for 7 channels.
+        if !matches!(spec.channels, 1..=6 | 8) {
+            log::warn!("{} has an unsupported number of channels.", path.display());
+            return None;
+        }
+        let samples: Result<Vec<f32>, _> = match spec.sample_format {
+            hound::SampleFormat::Float => reader.into_samples::<f32>().collect(),
+            hound::SampleFormat::Int => {
+                let scale = (1i64 << (spec.bits_per_sample - 1)) as f32;
+                reader
+                    .into_samples::<i32>()
+                    .map(|sample| sample.map(|s| s as f32 / scale))
+                    .collect()
+            }
+        };
+        let samples = samples
+            .map_err(|e| log::warn!("Failed to decode {}: {e}", path.display()))
+            .ok()?;
+        Some(Self {
+            samples,
+            channels: spec.channels,
+            sample_rate: spec.sample_rate,
+        })
+    }
+}
+
+#[derive(PartialEq)]
+pub struct Config {
+    split: Option<Sound>,
+    gold: Option<Sound>,
+    finish: Option<Sound>,
+    behind: Option<Sound>,
+    volume: f32,
+}
+
+/// Plays sounds through the source when splitting, getting a gold, finishing
+/// the run and falling behind the comparison.
+pub struct AudioCues {
+    config: Config,
+    tracker: EventTracker,
+    behind: bool,
+}
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let group = obs_properties_create();
+    for (name, description) in SOUNDS {
+        obs_properties_add_path(
+            group,
+            name,
+            description,
+            OBS_PATH_FILE,
+            cstr!("Wave Files (*.wav)"),
+            ptr::null(),
+        );
+    }
+    obs_properties_add_int(group, SETTINGS_VOLUME, cstr!("Volume"), 0, 100, 1);
+
+    obs_properties_add_group(
+        props,
+        SETTINGS_AUDIO_CUES,
+        cstr!("Audio Cues"),
+        OBS_GROUP_NORMAL,
+        group,
+    );
+}
+
+pub unsafe fn set_defaults(settings: *mut obs_data_t) {
+    obs_data_set_default_int(settings, SETTINGS_VOLUME, 100);
+}
+
+pub unsafe fn parse_settings(settings: *mut obs_data_t) -> Option<Config> {
+    let load = |name| Sound::load(&path::from_obs(obs_data_get_string(settings, name)));
+    let config = Config {
+        split: load(SETTINGS_SPLIT_SOUND),
+        gold: load(SETTINGS_GOLD_SOUND),
+        finish: load(SETTINGS_FINISH_SOUND),
+        behind: load(SETTINGS_BEHIND_SOUND),
+        volume: obs_data_get_int(settings, SETTINGS_VOLUME) as f32 / 100.0,
+    };
+    if config.split.is_none()
+        && config.gold.is_none()
+        && config.finish.is_none()
+        && config.behind.is_none()
+    {
+        None
+    } else {
+        Some(config)
+    }
+}
+
+/// Returns whether the run is behind the comparison at the split.
+fn is_behind(timer: &Timer, index: usize) -> bool {
+    state_helper::last_delta(
+        timer.run(),
+        index,
+        timer.current_comparison(),
+        timer.current_timing_method(),
+    )
+    .map_or(false, |delta| delta.total_milliseconds() > 0.0)
+}
+
+impl AudioCues {
+    pub fn new(config: Config, timer: &Timer) -> Self {
+        Self {
+            config,
+            tracker: EventTracker::new(timer),
+            behind: false,
+        }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub unsafe fn poll(&mut self, source: *mut obs_source_t, timer: &Timer) {
+        let mut sound = None;
+        for event in self.tracker.poll(timer) {
+            match event {
+                TimerEvent::Started | TimerEvent::Reset => self.behind = false,
+                TimerEvent::Splitted { index } => {
+                    let behind = is_behind(timer, index);
+                    let fell_behind = behind && !self.behind;
+                    self.behind = behind;
+                    // Only the most important sound of a split gets played.
+                    sound = if events::is_gold(timer, index) && self.config.gold.is_some() {
+                        self.config.gold.as_ref()
+                    } else if fell_behind && self.config.behind.is_some() {
+                        self.config.behind.as_ref()
+                    } else {
+                        self.config.split.as_ref()
+                    };
+                }
+                TimerEvent::Finished if self.config.finish.is_some() => {
+                    sound = self.config.finish.as_ref();
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(sound) = sound {
+            play(source, sound, self.config.volume);
+        }
+    }
+}
+
+unsafe fn play(source: *mut obs_source_t, sound: &Sound, volume: f32) {
+    let samples: Vec<f32> = sound.samples.iter().map(|s| s * volume).collect();
+    let mut data = [ptr::null(); MAX_AV_PLANES];
+    data[0] = samples.as_ptr().cast();
+    let audio = obs_source_audio {
+        data,
+        frames: (samples.len() / sound.channels as usize) as u32,
+        // The speaker layouts are numbered by their amount of channels.
+        speakers: sound.channels as _,
+        format: AUDIO_FORMAT_FLOAT,
+        samples_per_sec: sound.sample_rate,
+        timestamp: os_gettime_ns(),
+    };
+    obs_source_output_audio(source, &audio);
+}
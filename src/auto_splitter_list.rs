@@ -0,0 +1,246 @@
+use std::{
+    ffi::{c_void, CStr, CString},
+    fs,
+    io::Read,
+    os::raw::c_char,
+    path::PathBuf,
+    ptr,
+    sync::{mpsc, Mutex},
+    thread,
+};
+
+use serde::Deserialize;
+
+use crate::{
+    ffi::{
+        obs_data_get_string, obs_data_release, obs_data_set_string, obs_properties_add_button,
+        obs_properties_add_group, obs_properties_add_list, obs_properties_create, obs_properties_t,
+        obs_property_list_add_string, obs_property_t, obs_source_get_settings, obs_source_t,
+        obs_source_update, obs_source_update_properties, OBS_COMBO_FORMAT_STRING,
+        OBS_COMBO_TYPE_LIST, OBS_GROUP_NORMAL,
+    },
+    http, module_config_path, path, State, SETTINGS_AUTO_SPLITTER_PATH,
+};
+
+const LIST_URL: &str =
+    "https://raw.githubusercontent.com/LiveSplit/LiveSplit.AutoSplitters/master/LiveSplit.AutoSplitters.xml";
+
+const SETTINGS_AUTO_SPLITTER_LIST: *const c_char = cstr!("auto_splitter_list");
+const SETTINGS_GAME: *const c_char = cstr!("auto_splitter_list_game");
+const SETTINGS_REFRESH: *const c_char = cstr!("auto_splitter_list_refresh");
+const SETTINGS_DOWNLOAD: *const c_char = cstr!("auto_splitter_list_download");
+
+#[derive(Deserialize)]
+struct AutoSplitters {
+    #[serde(rename = "AutoSplitter", default)]
+    auto_splitters: Vec<AutoSplitter>,
+}
+
+#[derive(Deserialize)]
+struct AutoSplitter {
+    #[serde(rename = "Games")]
+    games: Games,
+    #[serde(rename = "URLs")]
+    urls: Urls,
+}
+
+#[derive(Deserialize)]
+struct Games {
+    #[serde(rename = "Game", default)]
+    games: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct Urls {
+    #[serde(rename = "URL", default)]
+    urls: Vec<String>,
+}
+
+/// The games that have a LiveSplit One auto splitter, along with the URL of
+/// its `.wasm` file. This is only downloaded when the user asks for it.
+static GAMES: Mutex<Vec<(String, String)>> = Mutex::new(Vec::new());
+
+/// The downloads that were started from the properties of a source. They run
+/// on threads of their own, as GitHub may take a while to answer, and the
+/// source takes them over once they are done.
+#[derive(Default)]
+pub struct Downloads {
+    list: Option<mpsc::Receiver<Result<Vec<(String, String)>, String>>>,
+    auto_splitter: Option<mpsc::Receiver<Result<PathBuf, String>>>,
+}
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let group = obs_properties_create();
+    let game = obs_properties_add_list(
+        group,
+        SETTINGS_GAME,
+        cstr!("Game"),
+        OBS_COMBO_TYPE_LIST,
+        OBS_COMBO_FORMAT_STRING,
+    );
+    for (name, url) in GAMES.lock().unwrap().iter() {
+        if let (Ok(name), Ok(url)) = (CString::new(name.as_str()), CString::new(url.as_str())) {
+            obs_property_list_add_string(game, name.as_ptr(), url.as_ptr());
+        }
+    }
+    obs_properties_add_button(
+        group,
+        SETTINGS_REFRESH,
+        cstr!("Refresh List"),
+        Some(refresh),
+    );
+    obs_properties_add_button(
+        group,
+        SETTINGS_DOWNLOAD,
+        cstr!("Download and Use"),
+        Some(download),
+    );
+
+    obs_properties_add_group(
+        props,
+        SETTINGS_AUTO_SPLITTER_LIST,
+        cstr!("Auto Splitter Downloader"),
+        OBS_GROUP_NORMAL,
+        group,
+    );
+}
+
+fn fetch_list() -> Result<Vec<(String, String)>, Box<dyn std::error::Error>> {
+    let xml = http::agent().get(LIST_URL).call()?.into_string()?;
+    let list: AutoSplitters = quick_xml::de::from_str(&xml)?;
+
+    let mut games: Vec<(String, String)> = list
+        .auto_splitters
+        .into_iter()
+        .filter_map(|auto_splitter| {
+            let url = auto_splitter
+                .urls
+                .urls
+                .into_iter()
+                .find(|url| url.trim().ends_with(".wasm"))?;
+            Some((auto_splitter.games.games, url.trim().to_owned()))
+        })
+        .flat_map(|(games, url)| games.into_iter().map(move |game| (game, url.clone())))
+        .collect();
+    games.sort_by_key(|(game, _)| game.to_lowercase());
+
+    Ok(games)
+}
+
+/// Downloads the auto splitter to the path, which is in OBS' config directory
+/// for this plugin, so it survives restarts without being downloaded again.
+fn download_auto_splitter(url: &str, path: PathBuf) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let mut wasm = Vec::new();
+    http::agent()
+        .get(url)
+        .call()?
+        .into_reader()
+        .read_to_end(&mut wasm)?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, wasm)?;
+
+    Ok(path)
+}
+
+/// Runs the download on a thread of its own. The result can be received once
+/// it's done.
+fn spawn<T: Send + 'static>(
+    download: impl FnOnce() -> Result<T, Box<dyn std::error::Error>> + Send + 'static,
+) -> Option<mpsc::Receiver<Result<T, String>>> {
+    let (sender, receiver) = mpsc::channel();
+    thread::Builder::new()
+        .name("LiveSplit One Auto Splitter Download".into())
+        .spawn(move || {
+            let _ = sender.send(download().map_err(|e| e.to_string()));
+        })
+        .map_err(|e| log::warn!("Failed to start the download: {e}"))
+        .ok()?;
+    Some(receiver)
+}
+
+/// Takes the result of the download if it's done.
+fn finished<T>(
+    download: &mut Option<mpsc::Receiver<Result<T, String>>>,
+) -> Option<Result<T, String>> {
+    let result = match download.as_ref()?.try_recv() {
+        Ok(result) => result,
+        Err(mpsc::TryRecvError::Empty) => return None,
+        Err(mpsc::TryRecvError::Disconnected) => Err("The download was cancelled.".into()),
+    };
+    *download = None;
+    Some(result)
+}
+
+impl Downloads {
+    /// Takes over the downloads that are done. The list shows up in the
+    /// properties, and a downloaded auto splitter gets used by the source.
+    pub unsafe fn poll(&mut self, source: *mut obs_source_t) {
+        match finished(&mut self.list) {
+            Some(Ok(games)) => {
+                log::info!("Found {} games with auto splitters.", games.len());
+                *GAMES.lock().unwrap() = games;
+                obs_source_update_properties(source);
+            }
+            Some(Err(e)) => log::warn!("Failed to download the list of auto splitters: {e}"),
+            None => {}
+        }
+
+        match finished(&mut self.auto_splitter) {
+            Some(Ok(path)) => {
+                log::info!("Downloaded auto splitter to {}.", path.display());
+                if let Some(path) = path::to_obs(&path) {
+                    let settings = obs_source_get_settings(source);
+                    obs_data_set_string(settings, SETTINGS_AUTO_SPLITTER_PATH, path.as_ptr());
+                    obs_data_release(settings);
+                    // The settings were modified in place, this only makes
+                    // OBS call the update callback, which loads the auto
+                    // splitter.
+                    obs_source_update(source, ptr::null_mut());
+                    obs_source_update_properties(source);
+                }
+            }
+            Some(Err(e)) => log::warn!("Failed to download the auto splitter: {e}"),
+            None => {}
+        }
+    }
+}
+
+unsafe extern "C" fn refresh(
+    _: *mut obs_properties_t,
+    _: *mut obs_property_t,
+    data: *mut c_void,
+) -> bool {
+    let state: &mut State = &mut *data.cast();
+    if state.auto_splitter_downloads.list.is_none() {
+        state.auto_splitter_downloads.list = spawn(fetch_list);
+    }
+    false
+}
+
+unsafe extern "C" fn download(
+    _: *mut obs_properties_t,
+    _: *mut obs_property_t,
+    data: *mut c_void,
+) -> bool {
+    let state: &mut State = &mut *data.cast();
+
+    let settings = obs_source_get_settings(state.source);
+    let url = CStr::from_ptr(obs_data_get_string(settings, SETTINGS_GAME))
+        .to_string_lossy()
+        .into_owned();
+    obs_data_release(settings);
+    if url.is_empty() {
+        return false;
+    }
+
+    let file_name = url.rsplit('/').next().unwrap_or("auto-splitter.wasm");
+    let Some(path) = module_config_path(&format!("auto-splitters/{file_name}")) else {
+        log::warn!("The config path of the module is unknown.");
+        return false;
+    };
+    state.auto_splitter_downloads.auto_splitter = spawn(move || download_auto_splitter(&url, path));
+    false
+}
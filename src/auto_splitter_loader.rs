@@ -0,0 +1,68 @@
+use std::{
+    path::PathBuf,
+    sync::{mpsc, Arc},
+    thread,
+};
+
+use livesplit_core::auto_splitting::Runtime;
+
+/// Loads auto splitters on a separate thread, as compiling large ones takes
+/// long enough to freeze OBS. The requests are handled one after another, so
+/// the last one always wins.
+pub struct Loader {
+    sender: mpsc::Sender<PathBuf>,
+    receiver: mpsc::Receiver<Result<bool, String>>,
+    pending: usize,
+}
+
+impl Loader {
+    pub fn new(runtime: Arc<Runtime>) -> Self {
+        let (sender, requests) = mpsc::channel::<PathBuf>();
+        let (results, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for path in requests {
+                let _ = runtime.unload_script_blocking();
+                let result = if path.as_os_str().is_empty() {
+                    Ok(false)
+                } else {
+                    runtime
+                        .load_script_blocking(path)
+                        .map(|()| true)
+                        .map_err(|e| e.to_string())
+                };
+                if results.send(result).is_err() {
+                    break;
+                }
+            }
+        });
+        Self {
+            sender,
+            receiver,
+            pending: 0,
+        }
+    }
+
+    /// Replaces the running auto splitter with the one at the path. An empty
+    /// path only unloads it.
+    pub fn load(&mut self, path: PathBuf) {
+        if self.sender.send(path).is_ok() {
+            self.pending += 1;
+        }
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.pending > 0
+    }
+
+    /// Returns whether the last request finished since the last call, and
+    /// whether an auto splitter was loaded. Earlier requests were replaced,
+    /// so their results don't matter.
+    pub fn poll(&mut self) -> Option<Result<bool, String>> {
+        let mut finished = None;
+        for result in self.receiver.try_iter() {
+            self.pending -= 1;
+            finished = Some(result);
+        }
+        finished.filter(|_| self.pending == 0)
+    }
+}
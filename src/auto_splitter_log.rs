@@ -0,0 +1,90 @@
+use std::{ffi::CStr, os::raw::c_char, sync::Mutex};
+
+use log::{Level, LevelFilter};
+
+use crate::ffi::{
+    obs_data_get_string, obs_data_set_default_string, obs_data_t, obs_properties_add_list,
+    obs_properties_t, obs_property_list_add_string, obs_property_set_long_description,
+    obs_source_t, OBS_COMBO_FORMAT_STRING, OBS_COMBO_TYPE_LIST,
+};
+
+const SETTINGS_AUTO_SPLITTER_LOG_LEVEL: *const c_char = cstr!("auto_splitter_log_level");
+
+/// The levels that can be chosen, with their names in the properties and in
+/// the settings.
+const LEVELS: [(*const c_char, *const c_char, LevelFilter); 5] = [
+    (cstr!("Nothing"), cstr!("off"), LevelFilter::Off),
+    (cstr!("Errors"), cstr!("error"), LevelFilter::Error),
+    (cstr!("Warnings"), cstr!("warn"), LevelFilter::Warn),
+    (cstr!("Messages"), cstr!("info"), LevelFilter::Info),
+    (cstr!("Everything"), cstr!("debug"), LevelFilter::Trace),
+];
+
+/// The level that each source logs its auto splitter at. The messages don't
+/// tell which source's auto splitter they came from, so the most verbose
+/// level of all sources applies.
+static SOURCES: Mutex<Vec<(usize, LevelFilter)>> = Mutex::new(Vec::new());
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let list = obs_properties_add_list(
+        props,
+        SETTINGS_AUTO_SPLITTER_LOG_LEVEL,
+        cstr!("Auto Splitter Log"),
+        OBS_COMBO_TYPE_LIST,
+        OBS_COMBO_FORMAT_STRING,
+    );
+    for (name, value, _) in LEVELS {
+        obs_property_list_add_string(list, name, value);
+    }
+    obs_property_set_long_description(
+        list,
+        cstr!("What the auto splitter prints ends up in the OBS log. Choose Everything while developing an auto splitter to see its debug output as well."),
+    );
+}
+
+pub unsafe fn set_defaults(settings: *mut obs_data_t) {
+    obs_data_set_default_string(settings, SETTINGS_AUTO_SPLITTER_LOG_LEVEL, cstr!("info"));
+}
+
+pub unsafe fn parse_settings(settings: *mut obs_data_t) -> LevelFilter {
+    let value = CStr::from_ptr(obs_data_get_string(
+        settings,
+        SETTINGS_AUTO_SPLITTER_LOG_LEVEL,
+    ));
+    LEVELS
+        .iter()
+        .find(|&&(_, name, _)| CStr::from_ptr(name) == value)
+        .map_or(LevelFilter::Info, |&(_, _, level)| level)
+}
+
+pub fn set_level(source: *mut obs_source_t, level: LevelFilter) {
+    let mut sources = SOURCES.lock().unwrap();
+    match sources.iter_mut().find(|(key, _)| *key == source as usize) {
+        Some((_, existing)) => *existing = level,
+        None => sources.push((source as usize, level)),
+    }
+}
+
+pub fn remove(source: *mut obs_source_t) {
+    SOURCES
+        .lock()
+        .unwrap()
+        .retain(|&(key, _)| key != source as usize);
+}
+
+/// Returns the level that a message of an auto splitter is logged at in OBS,
+/// if at all. OBS drops debug messages outside of its own debug builds, so
+/// they are logged as info for whoever asked for them.
+pub fn obs_level(level: Level) -> Option<Level> {
+    let max = SOURCES
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|&(_, level)| level)
+        .max()
+        .unwrap_or(LevelFilter::Info);
+    if level > max {
+        return None;
+    }
+    Some(level.min(Level::Info))
+}
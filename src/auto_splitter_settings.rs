@@ -0,0 +1,139 @@
+use std::{
+    ffi::{CStr, CString},
+    os::raw::c_char,
+};
+
+use livesplit_core::auto_splitting::{
+    self,
+    settings::{Value, WidgetKind},
+};
+
+use crate::{
+    ffi::{
+        obs_data_get_bool, obs_data_get_string, obs_data_has_user_value, obs_data_release,
+        obs_data_set_default_bool, obs_data_set_default_string, obs_data_t,
+        obs_properties_add_bool, obs_properties_add_group, obs_properties_add_list,
+        obs_properties_add_text, obs_properties_create, obs_properties_t,
+        obs_property_list_add_string, obs_property_set_long_description, obs_source_get_settings,
+        OBS_COMBO_FORMAT_STRING, OBS_COMBO_TYPE_LIST, OBS_GROUP_NORMAL, OBS_TEXT_INFO,
+    },
+    State,
+};
+
+const SETTINGS_AUTO_SPLITTER_SETTINGS: *const c_char = cstr!("auto_splitter_settings");
+
+/// The settings of the auto splitter are stored in the source's settings, with
+/// their keys prefixed so they can't clash with our own settings.
+fn property_name(key: &str) -> Option<CString> {
+    CString::new(format!("auto_splitter_setting_{key}")).ok()
+}
+
+/// Adds a property for each of the settings that the currently loaded auto
+/// splitter declares.
+pub unsafe fn add_properties(props: *mut obs_properties_t, state: &State) {
+    let Some(widgets) = state.auto_splitter.settings_widgets() else {
+        return;
+    };
+    if widgets.is_empty() {
+        return;
+    }
+
+    // The defaults are only known once the auto splitter is loaded, so they
+    // can't be set in get_defaults.
+    let settings = obs_source_get_settings(state.source);
+    let group = obs_properties_create();
+
+    for widget in widgets.iter() {
+        let (Some(name), Ok(description)) = (
+            property_name(&widget.key),
+            CString::new(&*widget.description),
+        ) else {
+            continue;
+        };
+
+        let property = match &widget.kind {
+            WidgetKind::Title { .. } => {
+                obs_properties_add_text(group, name.as_ptr(), description.as_ptr(), OBS_TEXT_INFO)
+            }
+            WidgetKind::Bool { default_value } => {
+                obs_data_set_default_bool(settings, name.as_ptr(), *default_value);
+                obs_properties_add_bool(group, name.as_ptr(), description.as_ptr())
+            }
+            WidgetKind::Choice {
+                default_option_key,
+                options,
+            } => {
+                if let Ok(default) = CString::new(&**default_option_key) {
+                    obs_data_set_default_string(settings, name.as_ptr(), default.as_ptr());
+                }
+                let list = obs_properties_add_list(
+                    group,
+                    name.as_ptr(),
+                    description.as_ptr(),
+                    OBS_COMBO_TYPE_LIST,
+                    OBS_COMBO_FORMAT_STRING,
+                );
+                for option in options.iter() {
+                    if let (Ok(description), Ok(key)) = (
+                        CString::new(&*option.description),
+                        CString::new(&*option.key),
+                    ) {
+                        obs_property_list_add_string(list, description.as_ptr(), key.as_ptr());
+                    }
+                }
+                list
+            }
+            _ => continue,
+        };
+
+        if let Some(tooltip) = widget
+            .tooltip
+            .as_deref()
+            .and_then(|tooltip| CString::new(tooltip).ok())
+        {
+            obs_property_set_long_description(property, tooltip.as_ptr());
+        }
+    }
+
+    obs_data_release(settings);
+
+    obs_properties_add_group(
+        props,
+        SETTINGS_AUTO_SPLITTER_SETTINGS,
+        cstr!("Auto Splitter Settings"),
+        OBS_GROUP_NORMAL,
+        group,
+    );
+}
+
+/// Pushes the settings the user changed to the auto splitter. Returns `false`
+/// if the auto splitter hasn't declared its settings yet.
+pub unsafe fn apply(runtime: &auto_splitting::Runtime, settings: *mut obs_data_t) -> bool {
+    let (Some(widgets), Some(mut map)) = (runtime.settings_widgets(), runtime.settings_map())
+    else {
+        return false;
+    };
+
+    for widget in widgets.iter() {
+        let Some(name) = property_name(&widget.key) else {
+            continue;
+        };
+        // Settings the user never touched keep the auto splitter's default.
+        if !obs_data_has_user_value(settings, name.as_ptr()) {
+            continue;
+        }
+        let value = match &widget.kind {
+            WidgetKind::Bool { .. } => Value::Bool(obs_data_get_bool(settings, name.as_ptr())),
+            WidgetKind::Choice { .. } => Value::String(
+                CStr::from_ptr(obs_data_get_string(settings, name.as_ptr()))
+                    .to_string_lossy()
+                    .into(),
+            ),
+            _ => continue,
+        };
+        map.insert(widget.key.clone(), value);
+    }
+
+    runtime.set_settings_map(map);
+    true
+}
@@ -0,0 +1,158 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    ffi::{c_void, CString},
+    fs,
+    hash::{Hash, Hasher},
+    os::raw::c_char,
+    sync::Mutex,
+};
+
+use log::{Level, Record};
+
+use crate::{
+    ffi::{
+        obs_properties_add_button, obs_properties_add_group, obs_properties_add_text,
+        obs_properties_create, obs_properties_t, obs_property_t, OBS_GROUP_NORMAL, OBS_TEXT_INFO,
+    },
+    State,
+};
+
+const SETTINGS_AUTO_SPLITTER_STATUS: *const c_char = cstr!("auto_splitter_status");
+const SETTINGS_AUTO_SPLITTER_STATUS_STATE: *const c_char = cstr!("auto_splitter_status_state");
+const SETTINGS_AUTO_SPLITTER_STATUS_VERSION: *const c_char = cstr!("auto_splitter_status_version");
+const SETTINGS_AUTO_SPLITTER_STATUS_MESSAGE: *const c_char = cstr!("auto_splitter_status_message");
+const SETTINGS_AUTO_SPLITTER_STATUS_ERROR: *const c_char = cstr!("auto_splitter_status_error");
+const SETTINGS_AUTO_SPLITTER_STATUS_CHECK: *const c_char = cstr!("auto_splitter_status_check");
+
+/// What the auto splitters logged last. The runtime doesn't report whether the
+/// game is hooked, but scripts usually print a message when they attach to it.
+struct Messages {
+    last: Option<String>,
+    error: Option<String>,
+}
+
+static MESSAGES: Mutex<Messages> = Mutex::new(Messages {
+    last: None,
+    error: None,
+});
+
+/// Checks whether the message comes from the auto splitting runtime or one of
+/// its scripts.
+pub fn is_auto_splitter(target: &str) -> bool {
+    target
+        .to_ascii_lowercase()
+        .replace(' ', "_")
+        .contains("auto_split")
+}
+
+/// Remembers the messages of the auto splitters, as they pass through the
+/// logger.
+pub fn record(record: &Record<'_>) {
+    if record.level() > Level::Info {
+        return;
+    }
+    let message = record.args().to_string();
+    let mut messages = MESSAGES.lock().unwrap();
+    if record.level() <= Level::Warn {
+        messages.error = Some(message.clone());
+    }
+    messages.last = Some(message);
+}
+
+/// Identifies the script by a hash of its contents, as auto splitters don't
+/// declare a version of their own.
+fn version(state: &State) -> Option<String> {
+    let script = fs::read(&state.auto_splitter_path).ok()?;
+    let mut hasher = DefaultHasher::new();
+    script.hash(&mut hasher);
+    Some(format!(
+        "{:08x} ({} KiB)",
+        hasher.finish() as u32,
+        (script.len() + 1023) / 1024
+    ))
+}
+
+unsafe fn add_line(props: *mut obs_properties_t, name: *const c_char, text: String) {
+    if let Ok(text) = CString::new(text) {
+        obs_properties_add_text(props, name, text.as_ptr(), OBS_TEXT_INFO);
+    }
+}
+
+/// Shows whether the auto splitter is running and why it isn't, as a failing
+/// auto splitter would otherwise go unnoticed.
+pub unsafe fn add_properties(props: *mut obs_properties_t, state: &State) {
+    let group = obs_properties_create();
+
+    let running = state.auto_splitter.settings_widgets().is_some();
+    let status = if state.auto_splitter_path.as_os_str().is_empty() {
+        "No auto splitter is chosen."
+    } else if !state.auto_splitter_enabled {
+        "Unloaded."
+    } else if state.auto_splitter_loader.is_loading() {
+        "Loading..."
+    } else if running {
+        "Running."
+    } else {
+        "Not running."
+    };
+    add_line(
+        group,
+        SETTINGS_AUTO_SPLITTER_STATUS_STATE,
+        format!("Status: {status}"),
+    );
+    if running {
+        if let Some(version) = version(state) {
+            add_line(
+                group,
+                SETTINGS_AUTO_SPLITTER_STATUS_VERSION,
+                format!("Version: {version}"),
+            );
+        }
+    }
+
+    let messages = MESSAGES.lock().unwrap();
+    if let Some(message) = &messages.last {
+        add_line(
+            group,
+            SETTINGS_AUTO_SPLITTER_STATUS_MESSAGE,
+            format!("Last Message: {message}"),
+        );
+    }
+    if let Some(error) = state
+        .auto_splitter_error
+        .as_ref()
+        .or(messages.error.as_ref())
+    {
+        add_line(
+            group,
+            SETTINGS_AUTO_SPLITTER_STATUS_ERROR,
+            format!("Last Error: {error}"),
+        );
+    }
+    drop(messages);
+
+    obs_properties_add_button(
+        group,
+        SETTINGS_AUTO_SPLITTER_STATUS_CHECK,
+        cstr!("Check Status"),
+        Some(check),
+    );
+
+    obs_properties_add_group(
+        props,
+        SETTINGS_AUTO_SPLITTER_STATUS,
+        cstr!("Auto Splitter Status"),
+        OBS_GROUP_NORMAL,
+        group,
+    );
+}
+
+/// The status is gathered whenever the properties are created, so they only
+/// need to be refreshed.
+unsafe extern "C" fn check(
+    _: *mut obs_properties_t,
+    _: *mut obs_property_t,
+    _: *mut c_void,
+) -> bool {
+    true
+}
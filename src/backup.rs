@@ -0,0 +1,92 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    fs,
+    hash::{Hash, Hasher},
+    os::raw::c_char,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    ffi::{
+        obs_data_get_int, obs_data_set_default_int, obs_data_t, obs_properties_add_int,
+        obs_properties_t, obs_property_set_long_description,
+    },
+    module_config_path,
+};
+
+const SETTINGS_BACKUP_COUNT: *const c_char = cstr!("backup_count");
+
+/// The amount of backups that are kept when it's not configured.
+pub const DEFAULT_COUNT: usize = 10;
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let count = obs_properties_add_int(
+        props,
+        SETTINGS_BACKUP_COUNT,
+        cstr!("Splits Backups to Keep"),
+        0,
+        1000,
+        1,
+    );
+    obs_property_set_long_description(
+        count,
+        cstr!("Before saving the splits, the previous file is backed up into the plugin's config folder. 0 turns this off."),
+    );
+}
+
+pub unsafe fn set_defaults(settings: *mut obs_data_t) {
+    obs_data_set_default_int(settings, SETTINGS_BACKUP_COUNT, DEFAULT_COUNT as _);
+}
+
+pub unsafe fn parse_settings(settings: *mut obs_data_t) -> usize {
+    obs_data_get_int(settings, SETTINGS_BACKUP_COUNT).max(0) as usize
+}
+
+/// Copies the splits into a folder of backups before they get overwritten.
+/// Only the most recent backups are kept.
+pub unsafe fn backup(splits_path: &Path, count: usize) {
+    if count == 0 || !splits_path.exists() {
+        return;
+    }
+
+    let name = splits_path
+        .file_stem()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    let mut hasher = DefaultHasher::new();
+    splits_path.hash(&mut hasher);
+    let Some(folder) =
+        module_config_path(&format!("backups/{name}-{:08x}", hasher.finish() as u32))
+    else {
+        log::warn!("The config path of the module is unknown.");
+        return;
+    };
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs());
+    let result = fs::create_dir_all(&folder)
+        .and_then(|()| fs::copy(splits_path, folder.join(format!("{timestamp}.lss"))));
+    if let Err(e) = result {
+        log::warn!("Failed to back up the splits: {e}");
+        return;
+    }
+
+    let Ok(entries) = fs::read_dir(&folder) else {
+        return;
+    };
+    // The names are timestamps, so sorting them sorts the backups by age.
+    let mut backups: Vec<_> = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .collect();
+    backups.sort_by_key(|path| {
+        path.file_stem()
+            .and_then(|stem| stem.to_str()?.parse::<u64>().ok())
+            .unwrap_or_default()
+    });
+    let excess = backups.len().saturating_sub(count);
+    for backup in &backups[..excess] {
+        let _ = fs::remove_file(backup);
+    }
+}
@@ -0,0 +1,91 @@
+// Rust versions of the inline helpers from libobs' callback/calldata.h, which
+// aren't exported from the library itself.
+
+use std::{
+    ffi::{c_void, CStr},
+    mem,
+    os::raw::c_char,
+    ptr,
+};
+
+use crate::ffi::{bfree, calldata_get_data, calldata_get_string, calldata_set_data, calldata_t};
+
+/// An owned `calldata_t` that frees its stack when dropped.
+pub struct CallData(calldata_t);
+
+impl Default for CallData {
+    fn default() -> Self {
+        Self(calldata_t {
+            stack: ptr::null_mut(),
+            size: 0,
+            capacity: 0,
+            fixed: false,
+        })
+    }
+}
+
+impl CallData {
+    pub fn as_mut_ptr(&mut self) -> *mut calldata_t {
+        &mut self.0
+    }
+}
+
+impl Drop for CallData {
+    fn drop(&mut self) {
+        if !self.0.fixed {
+            unsafe { bfree(self.0.stack.cast()) };
+        }
+    }
+}
+
+unsafe fn set<T>(data: *mut calldata_t, name: *const c_char, value: T) {
+    calldata_set_data(data, name, (&value as *const T).cast(), mem::size_of::<T>());
+}
+
+unsafe fn get<T: Default>(data: *const calldata_t, name: *const c_char) -> T {
+    let mut value = T::default();
+    calldata_get_data(
+        data,
+        name,
+        (&mut value as *mut T).cast(),
+        mem::size_of::<T>(),
+    );
+    value
+}
+
+pub unsafe fn set_ptr(data: *mut calldata_t, name: *const c_char, value: *mut c_void) {
+    set(data, name, value);
+}
+
+pub unsafe fn set_string(data: *mut calldata_t, name: *const c_char, value: &CStr) {
+    let value = value.to_bytes_with_nul();
+    calldata_set_data(data, name, value.as_ptr().cast(), value.len());
+}
+
+pub unsafe fn set_int(data: *mut calldata_t, name: *const c_char, value: i64) {
+    set(data, name, value);
+}
+
+pub unsafe fn get_ptr(data: *const calldata_t, name: *const c_char) -> *mut c_void {
+    let mut value: *mut c_void = ptr::null_mut();
+    calldata_get_data(
+        data,
+        name,
+        (&mut value as *mut *mut c_void).cast(),
+        mem::size_of::<*mut c_void>(),
+    );
+    value
+}
+
+pub unsafe fn get_bool(data: *const calldata_t, name: *const c_char) -> bool {
+    get(data, name)
+}
+
+pub unsafe fn get_string<'a>(data: *const calldata_t, name: *const c_char) -> Option<&'a CStr> {
+    let mut value = ptr::null();
+    if calldata_get_string(data, name, &mut value) && !value.is_null() {
+        Some(CStr::from_ptr(value))
+    } else {
+        None
+    }
+}
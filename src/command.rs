@@ -0,0 +1,475 @@
+use std::{
+    cmp::Ordering,
+    sync::{Arc, Mutex, RwLock, Weak},
+    time::{Duration, Instant},
+};
+
+use livesplit_core::{
+    timing::formatter::{Complete, TimeFormatter},
+    SharedTimer, TimeSpan, Timer, TimerPhase, TimingMethod,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::saved_attempt::SavedAttempt;
+
+/// How long the reset hotkey needs to be pressed again to confirm updating the
+/// splits when asking.
+const RESET_CONFIRMATION_TIME: Duration = Duration::from_secs(3);
+
+/// Whether resetting the timer updates the splits with the attempt, like the
+/// attempt history and the best segments.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum ResetPolicy {
+    Always,
+    Never,
+    /// Only if the reset is confirmed by resetting again.
+    Ask,
+}
+
+/// Decides what resetting and switching comparisons do to a timer. It belongs
+/// to the timer rather than to a source, so that the hotkeys, the controller
+/// sources and the remote controls all follow the same rules.
+pub struct Policy {
+    pub reset_policy: ResetPolicy,
+    pub hidden_comparisons: Vec<&'static str>,
+    /// Whether the attempt in progress is a practice attempt.
+    pub practicing: bool,
+    reset_requested_at: Option<Instant>,
+}
+
+static POLICIES: Mutex<Vec<(Weak<RwLock<Timer>>, Arc<Mutex<Policy>>)>> = Mutex::new(Vec::new());
+
+/// A command that a remote control surface can send to a timer. The shape
+/// matches the protocol that LiveSplit One's "Connect to Server" feature uses.
+#[derive(Deserialize)]
+#[serde(tag = "command", rename_all = "camelCase")]
+pub enum Command {
+    SplitOrStart,
+    Split,
+    UndoSplit,
+    SkipSplit,
+    TogglePauseOrStart,
+    Pause,
+    Resume,
+    UndoAllPauses,
+    Reset {
+        #[serde(default, rename = "saveAttempt")]
+        save_attempt: Option<bool>,
+    },
+    Start,
+    SwitchToPreviousComparison,
+    SwitchToNextComparison,
+    SetCurrentComparison {
+        comparison: String,
+    },
+    JumpToSplit {
+        index: usize,
+    },
+    ToggleTimingMethod,
+    SetCurrentTimingMethod {
+        #[serde(rename = "timingMethod")]
+        timing_method: TimingMethod,
+    },
+    InitializeGameTime,
+    SetGameTime {
+        time: String,
+    },
+    SetLoadingTimes {
+        time: String,
+    },
+    PauseGameTime,
+    ResumeGameTime,
+    SetCustomVariable {
+        key: String,
+        value: String,
+    },
+    GetCurrentTime,
+    GetCurrentSplitName,
+    GetCurrentState,
+    GetAttempt,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "code")]
+pub enum Error {
+    InvalidCommand { message: String },
+    InvalidTime,
+    ComparisonDoesntExist,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Response {
+    Success(Value),
+    Error(Error),
+}
+
+impl Command {
+    pub fn parse(message: &str) -> Result<Self, Error> {
+        serde_json::from_str(message).map_err(|e| Error::InvalidCommand {
+            message: e.to_string(),
+        })
+    }
+
+    pub fn from_value(value: Value) -> Result<Self, Error> {
+        serde_json::from_value(value).map_err(|e| Error::InvalidCommand {
+            message: e.to_string(),
+        })
+    }
+
+    pub fn execute(self, timer: &mut Timer) -> Result<Value, Error> {
+        match self {
+            Command::SplitOrStart => timer.split_or_start(),
+            Command::Split => timer.split(),
+            Command::UndoSplit => timer.undo_split(),
+            Command::SkipSplit => timer.skip_split(),
+            Command::TogglePauseOrStart => timer.toggle_pause_or_start(),
+            Command::Pause => timer.pause(),
+            Command::Resume => timer.resume(),
+            Command::UndoAllPauses => timer.undo_all_pauses(),
+            Command::Reset { save_attempt } => timer.reset(save_attempt.unwrap_or(true)),
+            Command::Start => timer.start(),
+            Command::SwitchToPreviousComparison => timer.switch_to_previous_comparison(),
+            Command::SwitchToNextComparison => timer.switch_to_next_comparison(),
+            Command::SetCurrentComparison { comparison } => timer
+                .set_current_comparison(comparison.as_str())
+                .map_err(|_| Error::ComparisonDoesntExist)?,
+            Command::JumpToSplit { index } => jump_to_split(timer, index),
+            Command::ToggleTimingMethod => timer.toggle_timing_method(),
+            Command::SetCurrentTimingMethod { timing_method } => {
+                timer.set_current_timing_method(timing_method)
+            }
+            Command::InitializeGameTime => timer.initialize_game_time(),
+            Command::SetGameTime { time } => timer.set_game_time(parse_time(&time)?),
+            Command::SetLoadingTimes { time } => timer.set_loading_times(parse_time(&time)?),
+            Command::PauseGameTime => timer.pause_game_time(),
+            Command::ResumeGameTime => timer.resume_game_time(),
+            Command::SetCustomVariable { key, value } => timer.set_custom_variable(key, value),
+            Command::GetCurrentTime => {
+                let time = timer.snapshot().current_time()[timer.current_timing_method()];
+                return Ok(json!(Complete.format(time).to_string()));
+            }
+            Command::GetCurrentSplitName => {
+                return Ok(json!(timer.current_split().map(|s| s.name())));
+            }
+            Command::GetCurrentState => {
+                return Ok(json!({
+                    "phase": phase_name(timer.current_phase()),
+                    "splitIndex": timer.current_split_index(),
+                    "comparison": timer.current_comparison(),
+                }));
+            }
+            Command::GetAttempt => {
+                return Ok(json!({
+                    "phase": phase_name(timer.current_phase()),
+                    "attempt": SavedAttempt::capture(timer),
+                }));
+            }
+        }
+        Ok(Value::Null)
+    }
+}
+
+fn parse_time(time: &str) -> Result<TimeSpan, Error> {
+    time.parse().map_err(|_| Error::InvalidTime)
+}
+
+/// Skips or undoes splits until the split at the index is the current one.
+/// The timer is started if it isn't running yet.
+fn jump_to_split(timer: &mut Timer, index: usize) {
+    // The last split can't be skipped, so that's as far as it goes.
+    let index = index.min(timer.run().len().saturating_sub(1));
+    if timer.current_phase() == TimerPhase::NotRunning {
+        timer.start();
+    }
+    while let Some(current) = timer.current_split_index() {
+        let previous = current;
+        match current.cmp(&index) {
+            Ordering::Less => timer.skip_split(),
+            Ordering::Equal => break,
+            Ordering::Greater => timer.undo_split(),
+        }
+        // Splits may not be skippable or undoable, e.g. while paused.
+        if timer.current_split_index() == Some(previous) {
+            break;
+        }
+    }
+}
+
+/// Returns the policy of the timer. Whenever both are locked, the policy needs
+/// to be locked before the timer.
+pub fn policy(timer: &SharedTimer) -> Arc<Mutex<Policy>> {
+    let mut policies = POLICIES.lock().unwrap();
+    policies.retain(|(timer, _)| timer.strong_count() > 0);
+    if let Some((_, policy)) = policies
+        .iter()
+        .find(|(other, _)| Weak::as_ptr(other) == Arc::as_ptr(timer))
+    {
+        return policy.clone();
+    }
+    let policy = Arc::new(Mutex::new(Policy {
+        reset_policy: ResetPolicy::Always,
+        hidden_comparisons: Vec::new(),
+        practicing: false,
+        reset_requested_at: None,
+    }));
+    policies.push((Arc::downgrade(timer), policy.clone()));
+    policy
+}
+
+/// Executes the command on the timer, following the policy of the timer.
+pub fn execute(command: Command, timer: &SharedTimer) -> Result<Value, Error> {
+    let policy = policy(timer);
+    let mut policy = policy.lock().unwrap();
+    policy.execute(command, &mut timer.write().unwrap())
+}
+
+impl Policy {
+    pub fn configure(&mut self, reset_policy: ResetPolicy, hidden_comparisons: Vec<&'static str>) {
+        self.reset_policy = reset_policy;
+        self.hidden_comparisons = hidden_comparisons;
+        self.reset_requested_at = None;
+    }
+
+    pub fn execute(&mut self, command: Command, timer: &mut Timer) -> Result<Value, Error> {
+        match command {
+            // Practice attempts aren't real runs, so they never update the
+            // splits.
+            Command::Reset { .. } if self.practicing => timer.reset(false),
+            Command::Reset { save_attempt: None } => self.reset(timer),
+            Command::SwitchToPreviousComparison => {
+                self.switch_comparison(timer, Timer::switch_to_previous_comparison)
+            }
+            Command::SwitchToNextComparison => {
+                self.switch_comparison(timer, Timer::switch_to_next_comparison)
+            }
+            command => return command.execute(timer),
+        }
+        Ok(Value::Null)
+    }
+
+    /// Switches comparisons until one is reached that wasn't turned off. If
+    /// all of them are, the timer ends up at the comparison it started at.
+    fn switch_comparison(&self, timer: &mut Timer, switch: fn(&mut Timer)) {
+        for _ in 0..timer.run().comparisons().count() {
+            switch(timer);
+            let current = timer.current_comparison();
+            if !self.hidden_comparisons.iter().any(|&name| name == current) {
+                break;
+            }
+        }
+    }
+
+    fn reset(&mut self, timer: &mut Timer) {
+        match self.reset_policy {
+            ResetPolicy::Always => timer.reset(true),
+            ResetPolicy::Never => timer.reset(false),
+            ResetPolicy::Ask => {
+                if self.reset_requested_at.take().is_some() {
+                    timer.reset(true);
+                } else if timer.current_phase() != TimerPhase::NotRunning {
+                    log::info!("Reset again to update the splits with this attempt.");
+                    self.reset_requested_at = Some(Instant::now());
+                }
+            }
+        }
+    }
+
+    /// Resets the timer and starts it again. Restarting doesn't wait for the
+    /// reset to be confirmed, so asking counts as an unconfirmed reset, unless
+    /// it was already requested.
+    pub fn restart(&mut self, timer: &mut Timer) {
+        let _ = self.execute(Command::Reset { save_attempt: None }, timer);
+        if self.reset_requested_at.take().is_some() {
+            timer.reset(false);
+        }
+        timer.start();
+    }
+
+    /// Resets the timer without updating the splits if a requested reset
+    /// wasn't confirmed in time.
+    pub fn finish_unconfirmed_reset(&mut self, timer: &mut Timer) {
+        if self
+            .reset_requested_at
+            .map_or(false, |time| time.elapsed() >= RESET_CONFIRMATION_TIME)
+        {
+            self.reset_requested_at = None;
+            timer.reset(false);
+        }
+    }
+}
+
+pub fn phase_name(phase: TimerPhase) -> &'static str {
+    match phase {
+        TimerPhase::NotRunning => "NotRunning",
+        TimerPhase::Running => "Running",
+        TimerPhase::Ended => "Ended",
+        TimerPhase::Paused => "Paused",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use livesplit_core::{comparison::best_segments, Run, Segment};
+
+    use super::*;
+
+    fn timer() -> SharedTimer {
+        let mut run = Run::new();
+        for name in ["One", "Two", "Three"] {
+            run.push_segment(Segment::new(name));
+        }
+        Timer::new(run).unwrap().into_shared()
+    }
+
+    fn run(command: Command, timer: &SharedTimer) {
+        assert!(execute(command, timer).is_ok());
+    }
+
+    #[test]
+    fn parses_commands() {
+        assert!(matches!(
+            Command::parse(r#"{ "command": "splitOrStart" }"#),
+            Ok(Command::SplitOrStart)
+        ));
+        assert!(matches!(
+            Command::parse(r#"{ "command": "reset" }"#),
+            Ok(Command::Reset { save_attempt: None })
+        ));
+        assert!(matches!(
+            Command::parse(r#"{ "command": "reset", "saveAttempt": false }"#),
+            Ok(Command::Reset {
+                save_attempt: Some(false)
+            })
+        ));
+        assert!(matches!(
+            Command::parse(r#"{ "command": "jumpToSplit", "index": 2 }"#),
+            Ok(Command::JumpToSplit { index: 2 })
+        ));
+        assert!(matches!(
+            Command::parse(
+                r#"{ "command": "setCurrentTimingMethod", "timingMethod": "GameTime" }"#
+            ),
+            Ok(Command::SetCurrentTimingMethod {
+                timing_method: TimingMethod::GameTime
+            })
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_commands() {
+        assert!(matches!(
+            Command::parse(r#"{ "command": "fly" }"#),
+            Err(Error::InvalidCommand { .. })
+        ));
+        assert!(matches!(
+            Command::parse(r#"{ "command": "jumpToSplit" }"#),
+            Err(Error::InvalidCommand { .. })
+        ));
+        assert!(matches!(
+            Command::parse("split"),
+            Err(Error::InvalidCommand { .. })
+        ));
+        assert!(matches!(
+            execute(
+                Command::SetGameTime {
+                    time: "soon".into()
+                },
+                &timer()
+            ),
+            Err(Error::InvalidTime)
+        ));
+    }
+
+    #[test]
+    fn resets_according_to_the_policy() {
+        let updates_splits = |reset_policy| {
+            let timer = timer();
+            policy(&timer)
+                .lock()
+                .unwrap()
+                .configure(reset_policy, Vec::new());
+            run(Command::Start, &timer);
+            run(Command::Split, &timer);
+            run(Command::Reset { save_attempt: None }, &timer);
+            let timer = timer.read().unwrap();
+            assert_eq!(timer.current_phase(), TimerPhase::NotRunning);
+            timer
+                .run()
+                .segment(0)
+                .best_segment_time()
+                .real_time
+                .is_some()
+        };
+        assert!(updates_splits(ResetPolicy::Always));
+        assert!(!updates_splits(ResetPolicy::Never));
+    }
+
+    #[test]
+    fn asks_before_resetting() {
+        let timer = timer();
+        policy(&timer)
+            .lock()
+            .unwrap()
+            .configure(ResetPolicy::Ask, Vec::new());
+        run(Command::Start, &timer);
+        run(Command::Reset { save_attempt: None }, &timer);
+        assert_eq!(timer.read().unwrap().current_phase(), TimerPhase::Running);
+        run(Command::Reset { save_attempt: None }, &timer);
+        assert_eq!(
+            timer.read().unwrap().current_phase(),
+            TimerPhase::NotRunning
+        );
+    }
+
+    #[test]
+    fn never_saves_practice_attempts() {
+        let timer = timer();
+        policy(&timer).lock().unwrap().practicing = true;
+        run(Command::Start, &timer);
+        run(Command::Split, &timer);
+        run(
+            Command::Reset {
+                save_attempt: Some(true),
+            },
+            &timer,
+        );
+        let timer = timer.read().unwrap();
+        assert_eq!(timer.current_phase(), TimerPhase::NotRunning);
+        assert!(timer
+            .run()
+            .segment(0)
+            .best_segment_time()
+            .real_time
+            .is_none());
+    }
+
+    #[test]
+    fn skips_hidden_comparisons() {
+        let timer = timer();
+        policy(&timer)
+            .lock()
+            .unwrap()
+            .configure(ResetPolicy::Always, vec![best_segments::NAME]);
+        let first = timer.read().unwrap().current_comparison().to_owned();
+        run(Command::SwitchToNextComparison, &timer);
+        let current = timer.read().unwrap().current_comparison().to_owned();
+        assert_ne!(current, first);
+        assert_ne!(current, best_segments::NAME);
+        run(Command::SwitchToPreviousComparison, &timer);
+        assert_eq!(timer.read().unwrap().current_comparison(), first);
+    }
+
+    #[test]
+    fn jumps_to_splits() {
+        let timer = timer();
+        run(Command::JumpToSplit { index: 2 }, &timer);
+        assert_eq!(timer.read().unwrap().current_split_index(), Some(2));
+        run(Command::JumpToSplit { index: 0 }, &timer);
+        assert_eq!(timer.read().unwrap().current_split_index(), Some(0));
+        // The last split can't be skipped.
+        run(Command::JumpToSplit { index: 10 }, &timer);
+        assert_eq!(timer.read().unwrap().current_split_index(), Some(2));
+    }
+}
@@ -0,0 +1,231 @@
+use std::{
+    ffi::{c_void, CStr},
+    mem,
+    os::raw::c_char,
+    ptr,
+};
+
+use livesplit_core::{layout::LayoutState, rendering::software::Renderer, Layout, SharedTimer};
+
+use crate::{
+    add_timer_group_property, draw_texture,
+    ffi::{
+        gs_effect_t, gs_texture_create, gs_texture_destroy, gs_texture_set_image, gs_texture_t,
+        obs_data_get_int, obs_data_get_string, obs_data_set_default_int,
+        obs_data_set_default_string, obs_data_t, obs_enter_graphics, obs_leave_graphics,
+        obs_properties_add_int, obs_properties_add_list, obs_properties_add_path,
+        obs_properties_create, obs_properties_t, obs_property_list_add_string, obs_source_info,
+        obs_source_t, GS_DYNAMIC, GS_RGBA, OBS_COMBO_FORMAT_STRING, OBS_COMBO_TYPE_LIST,
+        OBS_ICON_TYPE_GAME_CAPTURE, OBS_PATH_FILE, OBS_SOURCE_CUSTOM_DRAW, OBS_SOURCE_TYPE_INPUT,
+        OBS_SOURCE_VIDEO,
+    },
+    fonts, hash_layout_state, layout_builder, register_source, splits_timer, UnsafeMultiThread,
+};
+
+const SETTINGS_SPLITS_PATH: *const c_char = cstr!("splits_path");
+const SETTINGS_COMPONENT: *const c_char = cstr!("component");
+const SETTINGS_WIDTH: *const c_char = cstr!("width");
+const SETTINGS_HEIGHT: *const c_char = cstr!("height");
+
+/// A source that renders a single component of the timer of the splits, so
+/// that the components can be positioned independently.
+struct ComponentSource {
+    timer: SharedTimer,
+    layout: Layout,
+    state: LayoutState,
+    rendered_state: Option<u64>,
+    renderer: Renderer,
+    texture: *mut gs_texture_t,
+    width: u32,
+    height: u32,
+}
+
+unsafe fn parse_layout(settings: *mut obs_data_t) -> Layout {
+    let component = CStr::from_ptr(obs_data_get_string(settings, SETTINGS_COMPONENT));
+    layout_builder::build_with(settings, &[component.to_string_lossy().into_owned()])
+}
+
+unsafe fn parse_size(settings: *mut obs_data_t) -> (u32, u32) {
+    (
+        obs_data_get_int(settings, SETTINGS_WIDTH) as u32,
+        obs_data_get_int(settings, SETTINGS_HEIGHT) as u32,
+    )
+}
+
+unsafe extern "C" fn get_name(_: *mut c_void) -> *const c_char {
+    cstr!("LiveSplit One Component")
+}
+
+unsafe extern "C" fn create(settings: *mut obs_data_t, _: *mut obs_source_t) -> *mut c_void {
+    let (width, height) = parse_size(settings);
+
+    obs_enter_graphics();
+    let texture = gs_texture_create(width, height, GS_RGBA, 1, ptr::null_mut(), GS_DYNAMIC);
+    obs_leave_graphics();
+
+    Box::into_raw(Box::new(ComponentSource {
+        timer: splits_timer(settings),
+        layout: parse_layout(settings),
+        state: LayoutState::default(),
+        rendered_state: None,
+        renderer: fonts::renderer(),
+        texture,
+        width,
+        height,
+    }))
+    .cast()
+}
+
+unsafe extern "C" fn destroy(data: *mut c_void) {
+    let source: Box<ComponentSource> = Box::from_raw(data.cast());
+    obs_enter_graphics();
+    gs_texture_destroy(source.texture);
+    obs_leave_graphics();
+}
+
+unsafe extern "C" fn get_width(data: *mut c_void) -> u32 {
+    let source: &mut ComponentSource = &mut *data.cast();
+    source.width
+}
+
+unsafe extern "C" fn get_height(data: *mut c_void) -> u32 {
+    let source: &mut ComponentSource = &mut *data.cast();
+    source.height
+}
+
+unsafe extern "C" fn video_render(data: *mut c_void, _: *mut gs_effect_t) {
+    let source: &mut ComponentSource = &mut *data.cast();
+
+    source
+        .layout
+        .update_state(&mut source.state, &source.timer.read().unwrap().snapshot());
+
+    let state_hash = hash_layout_state(&source.state);
+    if source.rendered_state != Some(state_hash) {
+        source.rendered_state = Some(state_hash);
+        source
+            .renderer
+            .render(&source.state, [source.width, source.height]);
+        gs_texture_set_image(
+            source.texture,
+            source.renderer.image_data().as_ptr(),
+            source.width * 4,
+            false,
+        );
+    }
+
+    draw_texture(source.texture, [source.width, source.height], true);
+}
+
+unsafe extern "C" fn get_properties(_: *mut c_void) -> *mut obs_properties_t {
+    let props = obs_properties_create();
+    obs_properties_add_path(
+        props,
+        SETTINGS_SPLITS_PATH,
+        cstr!("Splits"),
+        OBS_PATH_FILE,
+        cstr!("LiveSplit Splits (*.lss)"),
+        ptr::null(),
+    );
+    add_timer_group_property(props);
+    let component = obs_properties_add_list(
+        props,
+        SETTINGS_COMPONENT,
+        cstr!("Component"),
+        OBS_COMBO_TYPE_LIST,
+        OBS_COMBO_FORMAT_STRING,
+    );
+    for name in layout_builder::COMPONENTS {
+        obs_property_list_add_string(component, name, name);
+    }
+    obs_properties_add_int(props, SETTINGS_WIDTH, cstr!("Width"), 10, 8200, 10);
+    obs_properties_add_int(props, SETTINGS_HEIGHT, cstr!("Height"), 10, 8200, 10);
+    layout_builder::add_style_properties(props);
+    props
+}
+
+unsafe extern "C" fn get_defaults(settings: *mut obs_data_t) {
+    obs_data_set_default_string(settings, SETTINGS_COMPONENT, cstr!("Timer"));
+    obs_data_set_default_int(settings, SETTINGS_WIDTH, 300);
+    obs_data_set_default_int(settings, SETTINGS_HEIGHT, 100);
+    layout_builder::set_defaults(settings);
+}
+
+unsafe extern "C" fn update(data: *mut c_void, settings: *mut obs_data_t) {
+    let source: &mut ComponentSource = &mut *data.cast();
+    source.timer = splits_timer(settings);
+    source.layout = parse_layout(settings);
+
+    let (width, height) = parse_size(settings);
+    if source.width != width || source.height != height {
+        source.width = width;
+        source.height = height;
+
+        obs_enter_graphics();
+        let mut texture = gs_texture_create(width, height, GS_RGBA, 1, ptr::null_mut(), GS_DYNAMIC);
+        mem::swap(&mut source.texture, &mut texture);
+        gs_texture_destroy(texture);
+        obs_leave_graphics();
+        source.rendered_state = None;
+    }
+}
+
+pub fn register() {
+    static SOURCE_INFO: UnsafeMultiThread<obs_source_info> = UnsafeMultiThread(obs_source_info {
+        id: cstr!("livesplit-one-component"),
+        type_: OBS_SOURCE_TYPE_INPUT,
+        output_flags: OBS_SOURCE_VIDEO | OBS_SOURCE_CUSTOM_DRAW,
+        get_name: Some(get_name),
+        create: Some(create),
+        destroy: Some(destroy),
+        get_width: Some(get_width),
+        get_height: Some(get_height),
+        video_render: Some(video_render),
+        mouse_wheel: None,
+        get_properties: Some(get_properties),
+        get_defaults: Some(get_defaults),
+        update: Some(update),
+        icon_type: OBS_ICON_TYPE_GAME_CAPTURE,
+        activate: None,
+        deactivate: None,
+        show: None,
+        hide: None,
+        video_tick: None,
+        filter_video: None,
+        filter_audio: None,
+        enum_active_sources: None,
+        save: None,
+        load: None,
+        mouse_click: None,
+        mouse_move: None,
+        focus: None,
+        key_click: None,
+        filter_remove: None,
+        type_data: ptr::null_mut(),
+        free_type_data: None,
+        audio_render: None,
+        enum_all_sources: None,
+        transition_start: None,
+        transition_stop: None,
+        get_defaults2: None,
+        get_properties2: None,
+        audio_mix: None,
+        media_play_pause: None,
+        media_restart: None,
+        media_stop: None,
+        media_next: None,
+        media_previous: None,
+        media_get_duration: None,
+        media_get_time: None,
+        media_set_time: None,
+        media_get_state: None,
+        version: 0,
+        unversioned_id: ptr::null(),
+        missing_files: None,
+        video_get_color_space: None,
+    });
+
+    unsafe {
+        register_source(&SOURCE_INFO.0);
+    }
+}
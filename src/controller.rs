@@ -0,0 +1,179 @@
+use std::{ffi::c_void, os::raw::c_char, ptr};
+
+use livesplit_core::SharedTimer;
+
+use crate::{
+    add_timer_group_property,
+    command::{self, Command},
+    ffi::{
+        obs_data_t, obs_hotkey_id, obs_hotkey_register_source, obs_hotkey_t,
+        obs_properties_add_path, obs_properties_create, obs_properties_t, obs_source_info,
+        obs_source_t, OBS_ICON_TYPE_GAME_CAPTURE, OBS_PATH_FILE, OBS_SOURCE_TYPE_INPUT,
+    },
+    register_source, splits_timer, UnsafeMultiThread,
+};
+
+const SETTINGS_SPLITS_PATH: *const c_char = cstr!("splits_path");
+
+/// A source without any output that only provides the hotkeys for the timer
+/// of the splits. This is useful when the timer is shown somewhere else, like
+/// in a different scene collection.
+struct Controller {
+    timer: SharedTimer,
+}
+
+macro_rules! hotkey {
+    ($name:ident, $command:expr) => {
+        unsafe extern "C" fn $name(
+            data: *mut c_void,
+            _: obs_hotkey_id,
+            _: *mut obs_hotkey_t,
+            pressed: bool,
+        ) {
+            if pressed {
+                let controller: &Controller = &*data.cast();
+                let _ = command::execute($command, &controller.timer);
+            }
+        }
+    };
+}
+
+hotkey!(split, Command::SplitOrStart);
+hotkey!(reset, Command::Reset { save_attempt: None });
+hotkey!(undo, Command::UndoSplit);
+hotkey!(skip, Command::SkipSplit);
+hotkey!(pause, Command::TogglePauseOrStart);
+hotkey!(undo_all_pauses, Command::UndoAllPauses);
+hotkey!(previous_comparison, Command::SwitchToPreviousComparison);
+hotkey!(next_comparison, Command::SwitchToNextComparison);
+hotkey!(toggle_timing_method, Command::ToggleTimingMethod);
+
+type HotkeyFunc = unsafe extern "C" fn(*mut c_void, obs_hotkey_id, *mut obs_hotkey_t, bool);
+
+const HOTKEYS: [(*const c_char, *const c_char, HotkeyFunc); 9] = [
+    (cstr!("hotkey_split"), cstr!("Split"), split),
+    (cstr!("hotkey_reset"), cstr!("Reset"), reset),
+    (cstr!("hotkey_undo"), cstr!("Undo Split"), undo),
+    (cstr!("hotkey_skip"), cstr!("Skip Split"), skip),
+    (cstr!("hotkey_pause"), cstr!("Pause"), pause),
+    (
+        cstr!("hotkey_undo_all_pauses"),
+        cstr!("Undo All Pauses"),
+        undo_all_pauses,
+    ),
+    (
+        cstr!("hotkey_previous_comparison"),
+        cstr!("Previous Comparison"),
+        previous_comparison,
+    ),
+    (
+        cstr!("hotkey_next_comparison"),
+        cstr!("Next Comparison"),
+        next_comparison,
+    ),
+    (
+        cstr!("hotkey_toggle_timing_method"),
+        cstr!("Toggle Timing Method"),
+        toggle_timing_method,
+    ),
+];
+
+unsafe extern "C" fn get_name(_: *mut c_void) -> *const c_char {
+    cstr!("LiveSplit One Controller")
+}
+
+unsafe extern "C" fn create(settings: *mut obs_data_t, source: *mut obs_source_t) -> *mut c_void {
+    let data = Box::into_raw(Box::new(Controller {
+        timer: splits_timer(settings),
+    }))
+    .cast();
+
+    for (name, description, func) in HOTKEYS {
+        obs_hotkey_register_source(source, name, description, Some(func), data);
+    }
+
+    data
+}
+
+unsafe extern "C" fn destroy(data: *mut c_void) {
+    drop(Box::<Controller>::from_raw(data.cast()));
+}
+
+unsafe extern "C" fn update(data: *mut c_void, settings: *mut obs_data_t) {
+    let controller: &mut Controller = &mut *data.cast();
+    controller.timer = splits_timer(settings);
+}
+
+unsafe extern "C" fn get_properties(_: *mut c_void) -> *mut obs_properties_t {
+    let props = obs_properties_create();
+    obs_properties_add_path(
+        props,
+        SETTINGS_SPLITS_PATH,
+        cstr!("Splits"),
+        OBS_PATH_FILE,
+        cstr!("LiveSplit Splits (*.lss)"),
+        ptr::null(),
+    );
+    add_timer_group_property(props);
+    props
+}
+
+pub fn register() {
+    static SOURCE_INFO: UnsafeMultiThread<obs_source_info> = UnsafeMultiThread(obs_source_info {
+        id: cstr!("livesplit-one-controller"),
+        type_: OBS_SOURCE_TYPE_INPUT,
+        output_flags: 0,
+        get_name: Some(get_name),
+        create: Some(create),
+        destroy: Some(destroy),
+        get_width: None,
+        get_height: None,
+        video_render: None,
+        mouse_wheel: None,
+        get_properties: Some(get_properties),
+        get_defaults: None,
+        update: Some(update),
+        icon_type: OBS_ICON_TYPE_GAME_CAPTURE,
+        activate: None,
+        deactivate: None,
+        show: None,
+        hide: None,
+        video_tick: None,
+        filter_video: None,
+        filter_audio: None,
+        enum_active_sources: None,
+        save: None,
+        load: None,
+        mouse_click: None,
+        mouse_move: None,
+        focus: None,
+        key_click: None,
+        filter_remove: None,
+        type_data: ptr::null_mut(),
+        free_type_data: None,
+        audio_render: None,
+        enum_all_sources: None,
+        transition_start: None,
+        transition_stop: None,
+        get_defaults2: None,
+        get_properties2: None,
+        audio_mix: None,
+        media_play_pause: None,
+        media_restart: None,
+        media_stop: None,
+        media_next: None,
+        media_previous: None,
+        media_get_duration: None,
+        media_get_time: None,
+        media_set_time: None,
+        media_get_state: None,
+        version: 0,
+        unversioned_id: ptr::null(),
+        missing_files: None,
+        video_get_color_space: None,
+    });
+
+    unsafe {
+        register_source(&SOURCE_INFO.0);
+    }
+}
@@ -0,0 +1,151 @@
+use std::{
+    ffi::{c_void, CStr},
+    os::raw::c_char,
+};
+
+use livesplit_core::{
+    comparison::personal_best, Run, Time, TimeSpan, Timer, TimerPhase, TimingMethod,
+};
+
+use crate::{
+    ffi::{
+        obs_data_get_string, obs_data_set_default_string, obs_data_t, obs_properties_add_button,
+        obs_properties_add_group, obs_properties_add_text, obs_properties_create, obs_properties_t,
+        obs_property_set_long_description, obs_property_t, OBS_GROUP_NORMAL, OBS_TEXT_DEFAULT,
+    },
+    State,
+};
+
+const SETTINGS_CUSTOM_COMPARISON: *const c_char = cstr!("custom_comparison");
+const SETTINGS_CUSTOM_COMPARISON_NAME: *const c_char = cstr!("custom_comparison_name");
+const SETTINGS_CUSTOM_COMPARISON_SAVE: *const c_char = cstr!("custom_comparison_save");
+
+/// The split times of an attempt that are waiting to be saved as a comparison.
+pub struct PendingComparison {
+    name: String,
+    splits: Vec<Time>,
+}
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let group = obs_properties_create();
+    obs_properties_add_text(
+        group,
+        SETTINGS_CUSTOM_COMPARISON_NAME,
+        cstr!("Name"),
+        OBS_TEXT_DEFAULT,
+    );
+    let save = obs_properties_add_button(
+        group,
+        SETTINGS_CUSTOM_COMPARISON_SAVE,
+        cstr!("Save Attempt as Comparison"),
+        Some(save),
+    );
+    obs_property_set_long_description(
+        save,
+        cstr!("Saves the current attempt, or the last one if the timer isn't running, as a comparison with the name. An existing comparison with the name is replaced."),
+    );
+
+    obs_properties_add_group(
+        props,
+        SETTINGS_CUSTOM_COMPARISON,
+        cstr!("Save as Comparison"),
+        OBS_GROUP_NORMAL,
+        group,
+    );
+}
+
+pub unsafe fn set_defaults(settings: *mut obs_data_t) {
+    obs_data_set_default_string(
+        settings,
+        SETTINGS_CUSTOM_COMPARISON_NAME,
+        cstr!("Today's Best"),
+    );
+}
+
+pub unsafe fn parse_name(settings: *mut obs_data_t) -> String {
+    CStr::from_ptr(obs_data_get_string(
+        settings,
+        SETTINGS_CUSTOM_COMPARISON_NAME,
+    ))
+    .to_string_lossy()
+    .trim()
+    .to_owned()
+}
+
+/// The split times of the attempt that was last reset, put back together from
+/// the history of its segments.
+fn last_attempt(run: &Run) -> Option<Vec<Time>> {
+    let index = run.attempt_history().last()?.index();
+    let mut total = Time::new()
+        .with_real_time(Some(TimeSpan::zero()))
+        .with_game_time(Some(TimeSpan::zero()));
+    let mut reached = true;
+    let splits = run
+        .segments()
+        .iter()
+        .map(|segment| {
+            let time = segment.segment_history().get(index);
+            reached &= time.is_some();
+            if !reached {
+                return Time::default();
+            }
+            let time = time.unwrap_or_default();
+            // Skipped splits have no time, so theirs counts towards the next
+            // segment.
+            let mut split = Time::default();
+            for method in [TimingMethod::RealTime, TimingMethod::GameTime] {
+                if let Some(segment_time) = time[method] {
+                    total[method] = total[method].map(|total| total + segment_time);
+                    split[method] = total[method];
+                }
+            }
+            split
+        })
+        .collect();
+    Some(splits)
+}
+
+/// Captures the split times of the attempt in progress, or of the last one if
+/// the timer isn't running.
+pub fn capture(name: String, timer: &Timer) -> Option<PendingComparison> {
+    let run = timer.run();
+    let splits = if timer.current_phase() == TimerPhase::NotRunning {
+        last_attempt(run)?
+    } else {
+        run.segments()
+            .iter()
+            .map(|segment| segment.split_time())
+            .collect()
+    };
+    Some(PendingComparison { name, splits })
+}
+
+impl PendingComparison {
+    /// Stores the split times as the comparison in the run, replacing the
+    /// comparison if it already exists.
+    pub fn apply(self, run: &mut Run) -> bool {
+        // The personal best is only changed by finishing runs.
+        let exists = run.custom_comparisons().iter().any(|c| *c == self.name);
+        if self.name == personal_best::NAME
+            || !exists && run.add_custom_comparison(&*self.name).is_err()
+        {
+            log::warn!("{} can't be used as the name of a comparison.", self.name);
+            return false;
+        }
+        for (segment, time) in run.segments_mut().iter_mut().zip(self.splits) {
+            *segment.comparison_mut(&self.name) = time;
+        }
+        log::info!("Saved the attempt as the comparison {}.", self.name);
+        true
+    }
+}
+
+unsafe extern "C" fn save(
+    _: *mut obs_properties_t,
+    _: *mut obs_property_t,
+    data: *mut c_void,
+) -> bool {
+    let state: &mut State = &mut *data.cast();
+    state.save_comparison();
+    false
+}
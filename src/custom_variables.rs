@@ -0,0 +1,55 @@
+use std::{ffi::CStr, os::raw::c_char};
+
+use livesplit_core::Timer;
+
+use crate::ffi::{
+    obs_data_get_string, obs_data_t, obs_properties_add_text, obs_properties_t,
+    obs_property_set_long_description, OBS_TEXT_MULTILINE,
+};
+
+const SETTINGS_CUSTOM_VARIABLES: *const c_char = cstr!("custom_variables");
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let variables = obs_properties_add_text(
+        props,
+        SETTINGS_CUSTOM_VARIABLES,
+        cstr!("Custom Variables"),
+        OBS_TEXT_MULTILINE,
+    );
+    obs_property_set_long_description(
+        variables,
+        cstr!("One variable per line, like \"Runner = Alice\". Text components of the layout can show them."),
+    );
+}
+
+pub unsafe fn parse_settings(settings: *mut obs_data_t) -> Vec<(String, String)> {
+    parse_lines(
+        &CStr::from_ptr(obs_data_get_string(settings, SETTINGS_CUSTOM_VARIABLES)).to_string_lossy(),
+    )
+}
+
+/// Parses lines like `Deaths = 3` into the variables and their values.
+pub fn parse_lines(text: &str) -> Vec<(String, String)> {
+    text.lines()
+        .filter_map(|line| {
+            let (name, value) = line.split_once('=')?;
+            let name = name.trim();
+            if name.is_empty() {
+                return None;
+            }
+            Some((name.to_owned(), value.trim().to_owned()))
+        })
+        .collect()
+}
+
+/// Sets the variables that changed since the settings were last applied, or
+/// that the run doesn't have yet. Variables that kept their value in the
+/// settings aren't touched, so values that were set at runtime survive changes
+/// to unrelated settings.
+pub fn apply(timer: &mut Timer, previous: &[(String, String)], variables: &[(String, String)]) {
+    for variable @ (name, value) in variables {
+        if !previous.contains(variable) || timer.run().metadata().custom_variable(name).is_none() {
+            timer.set_custom_variable(name, value);
+        }
+    }
+}
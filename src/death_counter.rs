@@ -0,0 +1,171 @@
+use std::{ffi::CStr, os::raw::c_char};
+
+use livesplit_core::{SharedTimer, Timer};
+
+use crate::{
+    events::{EventTracker, TimerEvent},
+    ffi::{
+        obs_data_get_bool, obs_data_get_string, obs_data_set_default_string, obs_data_t,
+        obs_properties_add_bool, obs_properties_add_group, obs_properties_add_text,
+        obs_properties_create, obs_properties_t, obs_property_set_long_description,
+        OBS_GROUP_NORMAL, OBS_TEXT_DEFAULT,
+    },
+};
+
+const SETTINGS_DEATH_COUNTER: *const c_char = cstr!("death_counter");
+const SETTINGS_DEATH_COUNTER_VARIABLE: *const c_char = cstr!("death_counter_variable");
+const SETTINGS_DEATH_COUNTER_SIGNAL: *const c_char = cstr!("death_counter_signal");
+const SETTINGS_DEATH_COUNTER_RESET_ON_START: *const c_char = cstr!("death_counter_reset_on_start");
+
+#[derive(PartialEq, Eq)]
+pub struct Config {
+    variable: String,
+    signal: Option<String>,
+    reset_on_start: bool,
+}
+
+/// Counts deaths in a custom variable of the run, so text components of the
+/// layout can show them. Besides the hotkeys, an auto splitter can count them
+/// by changing a variable of its own whenever the runner dies.
+pub struct DeathCounter {
+    config: Config,
+    tracker: EventTracker,
+    last_signal: Option<String>,
+}
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let group = obs_properties_create();
+    obs_properties_add_text(
+        group,
+        SETTINGS_DEATH_COUNTER_VARIABLE,
+        cstr!("Variable"),
+        OBS_TEXT_DEFAULT,
+    );
+    let signal = obs_properties_add_text(
+        group,
+        SETTINGS_DEATH_COUNTER_SIGNAL,
+        cstr!("Auto Splitter Variable"),
+        OBS_TEXT_DEFAULT,
+    );
+    obs_property_set_long_description(
+        signal,
+        cstr!("A variable that the auto splitter changes whenever the runner dies. Every change counts as a death."),
+    );
+    obs_properties_add_bool(
+        group,
+        SETTINGS_DEATH_COUNTER_RESET_ON_START,
+        cstr!("Reset When Starting a Run"),
+    );
+
+    obs_properties_add_group(
+        props,
+        SETTINGS_DEATH_COUNTER,
+        cstr!("Death Counter"),
+        OBS_GROUP_NORMAL,
+        group,
+    );
+}
+
+pub unsafe fn set_defaults(settings: *mut obs_data_t) {
+    obs_data_set_default_string(settings, SETTINGS_DEATH_COUNTER_VARIABLE, cstr!("Deaths"));
+}
+
+pub unsafe fn parse_settings(settings: *mut obs_data_t) -> Config {
+    let get = |name| {
+        CStr::from_ptr(obs_data_get_string(settings, name))
+            .to_string_lossy()
+            .trim()
+            .to_owned()
+    };
+    let variable = get(SETTINGS_DEATH_COUNTER_VARIABLE);
+    Config {
+        variable: if variable.is_empty() {
+            "Deaths".into()
+        } else {
+            variable
+        },
+        signal: Some(get(SETTINGS_DEATH_COUNTER_SIGNAL)).filter(|signal| !signal.is_empty()),
+        reset_on_start: obs_data_get_bool(settings, SETTINGS_DEATH_COUNTER_RESET_ON_START),
+    }
+}
+
+fn variable<'a>(timer: &'a Timer, name: &str) -> Option<&'a str> {
+    timer
+        .run()
+        .metadata()
+        .custom_variable(name)
+        .map(|variable| variable.value.as_str())
+}
+
+impl DeathCounter {
+    pub fn new(config: Config, timer: &Timer) -> Self {
+        let last_signal = config
+            .signal
+            .as_deref()
+            .and_then(|signal| variable(timer, signal))
+            .map(str::to_owned);
+        Self {
+            config,
+            tracker: EventTracker::new(timer),
+            last_signal,
+        }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn count(&self, timer: &Timer) -> u32 {
+        variable(timer, &self.config.variable)
+            .and_then(|count| count.trim().parse().ok())
+            .unwrap_or(0)
+    }
+
+    fn set(&self, timer: &mut Timer, count: u32) {
+        timer.set_custom_variable(&self.config.variable, &count.to_string());
+    }
+
+    pub fn increment(&self, timer: &mut Timer) {
+        self.set(timer, self.count(timer).saturating_add(1));
+    }
+
+    pub fn decrement(&self, timer: &mut Timer) {
+        self.set(timer, self.count(timer).saturating_sub(1));
+    }
+
+    pub fn reset(&self, timer: &mut Timer) {
+        self.set(timer, 0);
+    }
+
+    /// Looks for a start and a changed signal every frame, which only needs
+    /// to read the timer. It's only locked for writing when the count changes,
+    /// so the other users of the timer aren't kept waiting.
+    pub fn poll(&mut self, timer: &SharedTimer) {
+        let (reset, died) = {
+            let timer = timer.read().unwrap();
+            let reset = self.config.reset_on_start
+                && self.tracker.poll(&timer).contains(&TimerEvent::Started);
+
+            let mut died = false;
+            if let Some(signal) = &self.config.signal {
+                let value = variable(&timer, signal);
+                if value != self.last_signal.as_deref() {
+                    self.last_signal = value.map(str::to_owned);
+                    // The auto splitter clearing its variable isn't a death.
+                    died = self.last_signal.is_some();
+                }
+            }
+            (reset, died)
+        };
+
+        if reset || died {
+            let mut timer = timer.write().unwrap();
+            if reset {
+                self.reset(&mut timer);
+            }
+            if died {
+                self.increment(&mut timer);
+            }
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use std::{
+    sync::mpsc,
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use discord_rich_presence::{activity, DiscordIpc, DiscordIpcClient};
+use livesplit_core::{
+    analysis::state_helper,
+    timing::formatter::{Complete, Delta, TimeFormatter},
+    Timer, TimerPhase,
+};
+
+use crate::events::EventTracker;
+
+struct Presence {
+    details: String,
+    state: String,
+    start: Option<i64>,
+}
+
+/// Shows the current split and how the run is going in the user's Discord
+/// status. Discord is talked to on a separate thread, so connecting to it
+/// doesn't block rendering.
+pub struct RichPresence {
+    application_id: String,
+    tracker: EventTracker,
+    sender: mpsc::Sender<Option<Presence>>,
+}
+
+fn set_presence(
+    client: &mut DiscordIpcClient,
+    presence: &Option<Presence>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(presence) = presence else {
+        return client.clear_activity();
+    };
+    let mut activity = activity::Activity::new()
+        .details(&presence.details)
+        .state(&presence.state);
+    if let Some(start) = presence.start {
+        activity = activity.timestamps(activity::Timestamps::new().start(start));
+    }
+    client.set_activity(activity)
+}
+
+impl RichPresence {
+    pub fn new(application_id: String, timer: &Timer) -> Option<Self> {
+        let (sender, receiver) = mpsc::channel::<Option<Presence>>();
+
+        thread::Builder::new()
+            .name("Discord Rich Presence".into())
+            .spawn({
+                let application_id = application_id.clone();
+                move || {
+                    let mut client: Option<DiscordIpcClient> = None;
+                    for presence in receiver {
+                        // Discord may not be running yet or may have been closed
+                        // in the meantime, so we reconnect whenever necessary.
+                        if client.is_none() {
+                            match DiscordIpcClient::new(&application_id).and_then(|mut c| {
+                                c.connect()?;
+                                Ok(c)
+                            }) {
+                                Ok(c) => client = Some(c),
+                                Err(e) => {
+                                    log::debug!("Failed to connect to Discord: {e}");
+                                    continue;
+                                }
+                            }
+                        }
+                        if let Some(c) = &mut client {
+                            if let Err(e) = set_presence(c, &presence) {
+                                log::debug!("Failed to set the Discord presence: {e}");
+                                client = None;
+                            }
+                        }
+                    }
+                    if let Some(mut client) = client {
+                        let _ = client.close();
+                    }
+                }
+            })
+            .map_err(|e| log::warn!("Failed to start the Discord Rich Presence: {e}"))
+            .ok()?;
+
+        let mut rich_presence = Self {
+            application_id,
+            tracker: EventTracker::new(timer),
+            sender,
+        };
+        rich_presence.send(timer);
+        Some(rich_presence)
+    }
+
+    pub fn application_id(&self) -> &str {
+        &self.application_id
+    }
+
+    pub fn poll(&mut self, timer: &Timer) {
+        if !self.tracker.poll(timer).is_empty() {
+            self.send(timer);
+        }
+    }
+
+    fn send(&mut self, timer: &Timer) {
+        let _ = self.sender.send(presence(timer));
+    }
+}
+
+fn presence(timer: &Timer) -> Option<Presence> {
+    let phase = timer.current_phase();
+    if phase == TimerPhase::NotRunning {
+        return None;
+    }
+
+    let run = timer.run();
+    let method = timer.current_timing_method();
+    let current_time = timer.current_time()[method];
+
+    let details = format!("{} - {}", run.game_name(), run.category_name());
+
+    let state = match timer.current_split_index() {
+        Some(index) if phase != TimerPhase::Ended => {
+            let name = run.segment(index).name();
+            let delta = index.checked_sub(1).and_then(|previous| {
+                state_helper::last_delta(run, previous, timer.current_comparison(), method)
+            });
+            match delta {
+                Some(delta) => format!("{name} ({})", Delta::new().format(delta)),
+                None => name.to_owned(),
+            }
+        }
+        _ => format!("Finished in {}", Complete.format(current_time)),
+    };
+
+    // Discord counts up on its own, so it only needs to know when the attempt
+    // started, as long as the timer isn't paused or stopped.
+    let start = if phase == TimerPhase::Running {
+        current_time.and_then(|time| {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?;
+            Some((now.as_secs_f64() - time.total_seconds()) as i64)
+        })
+    } else {
+        None
+    };
+
+    Some(Presence {
+        details,
+        state,
+        start,
+    })
+}
@@ -0,0 +1,202 @@
+use livesplit_core::{
+    analysis::{self, state_helper},
+    TimeSpan, Timer, TimerPhase,
+};
+
+/// Something that happened to a timer between two polls.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TimerEvent {
+    Started,
+    Splitted { index: usize },
+    SkippedSplit { index: usize },
+    UndoneSplit { index: usize },
+    Finished,
+    Reset,
+    Paused,
+    Resumed,
+}
+
+/// The timer doesn't notify anyone about changes, so this keeps track of the
+/// parts of its state that we care about and turns differences between polls
+/// into events.
+pub struct EventTracker {
+    phase: TimerPhase,
+    split_index: Option<usize>,
+    attempt_count: u32,
+}
+
+impl EventTracker {
+    pub fn new(timer: &Timer) -> Self {
+        Self {
+            phase: timer.current_phase(),
+            split_index: timer.current_split_index(),
+            attempt_count: timer.run().attempt_count(),
+        }
+    }
+
+    pub fn poll(&mut self, timer: &Timer) -> Vec<TimerEvent> {
+        let mut events = Vec::new();
+
+        let phase = timer.current_phase();
+        let split_index = timer.current_split_index();
+        let attempt_count = timer.run().attempt_count();
+
+        let new_attempt = attempt_count != self.attempt_count;
+
+        if self.phase != TimerPhase::NotRunning && (phase == TimerPhase::NotRunning || new_attempt)
+        {
+            events.push(TimerEvent::Reset);
+            self.split_index = None;
+        }
+
+        if phase != TimerPhase::NotRunning && (self.phase == TimerPhase::NotRunning || new_attempt)
+        {
+            events.push(TimerEvent::Started);
+            self.split_index = Some(0);
+        }
+
+        if let (Some(previous), Some(current)) = (self.split_index, split_index) {
+            let method = timer.current_timing_method();
+            for index in previous..current {
+                let split_time = timer.run().segment(index).split_time()[method];
+                if split_time.is_some() {
+                    events.push(TimerEvent::Splitted { index });
+                } else {
+                    events.push(TimerEvent::SkippedSplit { index });
+                }
+            }
+            for index in (current..previous).rev() {
+                events.push(TimerEvent::UndoneSplit { index });
+            }
+        }
+
+        if phase == TimerPhase::Ended && self.phase != TimerPhase::Ended {
+            events.push(TimerEvent::Finished);
+        }
+
+        if !new_attempt {
+            match (self.phase, phase) {
+                (TimerPhase::Running, TimerPhase::Paused) => events.push(TimerEvent::Paused),
+                (TimerPhase::Paused, TimerPhase::Running) => events.push(TimerEvent::Resumed),
+                _ => {}
+            }
+        }
+
+        self.phase = phase;
+        self.split_index = split_index;
+        self.attempt_count = attempt_count;
+
+        events
+    }
+}
+
+/// Returns whether the split at the index was the best segment so far.
+pub fn is_gold(timer: &Timer, index: usize) -> bool {
+    analysis::check_best_segment(timer, index, timer.current_timing_method())
+}
+
+/// Returns the delta of the last split. The delta of the current split is only
+/// known once it's split.
+pub fn last_delta(timer: &Timer) -> Option<TimeSpan> {
+    let run = timer.run();
+    let last_split = match timer.current_split_index() {
+        Some(_) if timer.current_phase() == TimerPhase::Ended => run.len().checked_sub(1),
+        Some(index) => index.checked_sub(1),
+        None => None,
+    }?;
+    state_helper::last_delta(
+        run,
+        last_split,
+        timer.current_comparison(),
+        timer.current_timing_method(),
+    )
+}
+
+/// Returns whether the finished run beats the personal best. This needs to be
+/// checked before the timer is reset, as that updates the personal best.
+pub fn is_personal_best(timer: &Timer) -> bool {
+    let method = timer.current_timing_method();
+    let Some(last) = timer.run().segments().last() else {
+        return false;
+    };
+    match (
+        last.split_time()[method],
+        last.personal_best_split_time()[method],
+    ) {
+        (Some(time), Some(pb)) => time < pb,
+        (Some(_), None) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use livesplit_core::{Run, Segment};
+
+    use super::*;
+
+    fn timer() -> Timer {
+        let mut run = Run::new();
+        for name in ["One", "Two", "Three"] {
+            run.push_segment(Segment::new(name));
+        }
+        Timer::new(run).unwrap()
+    }
+
+    #[test]
+    fn reports_the_progress_of_an_attempt() {
+        let mut timer = timer();
+        let mut tracker = EventTracker::new(&timer);
+        assert_eq!(tracker.poll(&timer), []);
+
+        timer.start();
+        timer.split();
+        assert_eq!(
+            tracker.poll(&timer),
+            [TimerEvent::Started, TimerEvent::Splitted { index: 0 }]
+        );
+        assert!(!is_personal_best(&timer));
+
+        timer.skip_split();
+        assert_eq!(
+            tracker.poll(&timer),
+            [TimerEvent::SkippedSplit { index: 1 }]
+        );
+        timer.undo_split();
+        assert_eq!(tracker.poll(&timer), [TimerEvent::UndoneSplit { index: 1 }]);
+
+        timer.pause();
+        assert_eq!(tracker.poll(&timer), [TimerEvent::Paused]);
+        timer.resume();
+        assert_eq!(tracker.poll(&timer), [TimerEvent::Resumed]);
+
+        timer.split();
+        timer.split();
+        assert_eq!(
+            tracker.poll(&timer),
+            [
+                TimerEvent::Splitted { index: 1 },
+                TimerEvent::Splitted { index: 2 },
+                TimerEvent::Finished,
+            ]
+        );
+        assert!(is_personal_best(&timer));
+
+        timer.reset(true);
+        assert_eq!(tracker.poll(&timer), [TimerEvent::Reset]);
+    }
+
+    #[test]
+    fn reports_restarts_between_polls() {
+        let mut timer = timer();
+        timer.start();
+        let mut tracker = EventTracker::new(&timer);
+
+        timer.reset(false);
+        timer.start();
+        assert_eq!(
+            tracker.poll(&timer),
+            [TimerEvent::Reset, TimerEvent::Started]
+        );
+    }
+}
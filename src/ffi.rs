@@ -41,6 +41,9 @@ extern "C" {
         func: obs_hotkey_func,
         data: *mut c_void,
     ) -> obs_hotkey_id;
+    pub fn obs_hotkey_unregister(id: obs_hotkey_id);
+    pub fn obs_key_from_virtual_key(code: c_int) -> obs_key_t;
+    pub fn obs_key_from_name(name: *const c_char) -> obs_key_t;
     pub fn obs_properties_create() -> *mut obs_properties_t;
     pub fn obs_properties_add_path(
         props: *mut obs_properties_t,
@@ -60,6 +63,14 @@ extern "C" {
         max: c_int,
         step: c_int,
     ) -> *mut obs_property_t;
+    pub fn obs_properties_add_int_slider(
+        props: *mut obs_properties_t,
+        name: *const c_char,
+        description: *const c_char,
+        min: c_int,
+        max: c_int,
+        step: c_int,
+    ) -> *mut obs_property_t;
     pub fn obs_data_get_int(data: *mut obs_data_t, name: *const c_char) -> c_longlong;
     pub fn gs_texture_destroy(tex: *mut gs_texture_t);
     pub fn gs_draw_sprite(tex: *mut gs_texture_t, flip: u32, width: u32, height: u32);
@@ -72,11 +83,15 @@ extern "C" {
         name: *const c_char,
     ) -> *mut gs_technique_t;
     pub fn gs_effect_set_texture(param: *mut gs_eparam_t, val: *mut gs_texture_t);
+    pub fn gs_effect_set_texture_srgb(param: *mut gs_eparam_t, val: *mut gs_texture_t);
+    pub fn gs_framebuffer_srgb_enabled() -> bool;
+    pub fn gs_enable_framebuffer_srgb(enable: bool);
     pub fn gs_technique_begin(technique: *mut gs_technique_t) -> size_t;
     pub fn gs_technique_begin_pass(technique: *mut gs_technique_t, pass: size_t) -> bool;
     pub fn gs_technique_end(technique: *mut gs_technique_t);
     pub fn gs_technique_end_pass(technique: *mut gs_technique_t);
     pub fn obs_get_base_effect(effect: obs_base_effect) -> *mut gs_effect_t;
+    pub fn obs_get_version() -> u32;
     pub fn obs_data_set_default_int(data: *mut obs_data_t, name: *const c_char, val: c_longlong);
     pub fn obs_properties_add_button(
         props: *mut obs_properties_t,
@@ -84,4 +99,167 @@ extern "C" {
         text: *const c_char,
         callback: obs_property_clicked_t,
     ) -> *mut obs_property_t;
+    pub fn obs_properties_add_bool(
+        props: *mut obs_properties_t,
+        name: *const c_char,
+        description: *const c_char,
+    ) -> *mut obs_property_t;
+    pub fn obs_data_get_bool(data: *mut obs_data_t, name: *const c_char) -> bool;
+    pub fn obs_data_set_default_bool(data: *mut obs_data_t, name: *const c_char, val: bool);
+    pub fn obs_data_get_json(data: *mut obs_data_t) -> *const c_char;
+    pub fn obs_data_create_from_json(json_string: *const c_char) -> *mut obs_data_t;
+    pub fn obs_data_apply(target: *mut obs_data_t, apply_data: *mut obs_data_t);
+    pub fn obs_data_release(data: *mut obs_data_t);
+    pub fn obs_get_proc_handler() -> *mut proc_handler_t;
+    pub fn proc_handler_call(
+        handler: *mut proc_handler_t,
+        name: *const c_char,
+        params: *mut calldata_t,
+    ) -> bool;
+    pub fn calldata_set_data(
+        data: *mut calldata_t,
+        name: *const c_char,
+        in_: *const c_void,
+        new_size: size_t,
+    );
+    pub fn calldata_get_data(
+        data: *const calldata_t,
+        name: *const c_char,
+        out: *mut c_void,
+        size: size_t,
+    ) -> bool;
+    pub fn calldata_get_string(
+        data: *const calldata_t,
+        name: *const c_char,
+        str: *mut *const c_char,
+    ) -> bool;
+    pub fn bfree(ptr: *mut c_void);
+    pub fn obs_properties_add_text(
+        props: *mut obs_properties_t,
+        name: *const c_char,
+        description: *const c_char,
+        type_: obs_text_type,
+    ) -> *mut obs_property_t;
+    pub fn obs_data_set_default_string(
+        data: *mut obs_data_t,
+        name: *const c_char,
+        val: *const c_char,
+    );
+    pub fn obs_source_get_settings(source: *const obs_source_t) -> *mut obs_data_t;
+    pub fn obs_data_set_string(data: *mut obs_data_t, name: *const c_char, val: *const c_char);
+    pub fn obs_data_set_int(data: *mut obs_data_t, name: *const c_char, val: c_longlong);
+    pub fn obs_data_set_bool(data: *mut obs_data_t, name: *const c_char, val: bool);
+    pub fn obs_properties_add_group(
+        props: *mut obs_properties_t,
+        name: *const c_char,
+        description: *const c_char,
+        type_: obs_group_type,
+        group: *mut obs_properties_t,
+    ) -> *mut obs_property_t;
+    pub fn obs_property_set_modified_callback2(
+        p: *mut obs_property_t,
+        modified: obs_property_modified2_t,
+        priv_: *mut c_void,
+    );
+    pub fn obs_properties_get(
+        props: *mut obs_properties_t,
+        property: *const c_char,
+    ) -> *mut obs_property_t;
+    pub fn obs_property_int_set_limits(p: *mut obs_property_t, min: c_int, max: c_int, step: c_int);
+    pub fn obs_properties_add_editable_list(
+        props: *mut obs_properties_t,
+        name: *const c_char,
+        description: *const c_char,
+        type_: obs_editable_list_type,
+        filter: *const c_char,
+        default_path: *const c_char,
+    ) -> *mut obs_property_t;
+    pub fn obs_properties_add_color_alpha(
+        props: *mut obs_properties_t,
+        name: *const c_char,
+        description: *const c_char,
+    ) -> *mut obs_property_t;
+    pub fn obs_properties_add_list(
+        props: *mut obs_properties_t,
+        name: *const c_char,
+        description: *const c_char,
+        type_: obs_combo_type,
+        format: obs_combo_format,
+    ) -> *mut obs_property_t;
+    pub fn obs_property_list_add_string(
+        p: *mut obs_property_t,
+        name: *const c_char,
+        val: *const c_char,
+    ) -> size_t;
+    pub fn obs_property_list_add_int(
+        p: *mut obs_property_t,
+        name: *const c_char,
+        val: c_longlong,
+    ) -> size_t;
+    pub fn obs_data_get_array(data: *mut obs_data_t, name: *const c_char) -> *mut obs_data_array_t;
+    pub fn obs_data_array_count(array: *mut obs_data_array_t) -> size_t;
+    pub fn obs_data_array_item(array: *mut obs_data_array_t, idx: size_t) -> *mut obs_data_t;
+    pub fn obs_data_array_release(array: *mut obs_data_array_t);
+    pub fn obs_add_tick_callback(tick: obs_tick_callback_t, param: *mut c_void);
+    pub fn obs_remove_tick_callback(tick: obs_tick_callback_t, param: *mut c_void);
+    pub fn obs_module_get_config_path(
+        module: *mut obs_module_t,
+        file: *const c_char,
+    ) -> *mut c_char;
+    pub fn obs_source_update(source: *mut obs_source_t, settings: *mut obs_data_t);
+    pub fn obs_source_update_properties(source: *mut obs_source_t);
+    pub fn obs_enum_scenes(enum_proc: obs_enum_proc_t, param: *mut c_void);
+    pub fn obs_source_get_name(source: *const obs_source_t) -> *const c_char;
+    pub fn obs_get_source_by_name(name: *const c_char) -> *mut obs_source_t;
+    pub fn obs_source_release(source: *mut obs_source_t);
+    pub fn obs_source_output_audio(source: *mut obs_source_t, audio: *const obs_source_audio);
+    pub fn obs_source_set_audio_active(source: *mut obs_source_t, show: bool);
+    pub fn os_gettime_ns() -> u64;
+    pub fn obs_data_has_user_value(data: *mut obs_data_t, name: *const c_char) -> bool;
+    pub fn obs_property_set_long_description(
+        p: *mut obs_property_t,
+        long_description: *const c_char,
+    );
+    pub fn obs_data_create() -> *mut obs_data_t;
+    pub fn obs_missing_files_create() -> *mut obs_missing_files_t;
+    pub fn obs_missing_file_create(
+        path: *const c_char,
+        callback: obs_missing_file_cb,
+        src_type: obs_missing_file_src,
+        src: *mut c_void,
+        data: *mut c_void,
+    ) -> *mut obs_missing_file_t;
+    pub fn obs_missing_files_add_file(
+        files: *mut obs_missing_files_t,
+        file: *mut obs_missing_file_t,
+    );
+    pub fn obs_source_get_proc_handler(source: *const obs_source_t) -> *mut proc_handler_t;
+    pub fn obs_source_get_signal_handler(source: *const obs_source_t) -> *mut signal_handler_t;
+    pub fn signal_handler_add(handler: *mut signal_handler_t, signal_decl: *const c_char) -> bool;
+    pub fn signal_handler_signal(
+        handler: *mut signal_handler_t,
+        signal: *const c_char,
+        params: *mut calldata_t,
+    );
+    pub fn proc_handler_add(
+        handler: *mut proc_handler_t,
+        decl_string: *const c_char,
+        proc_: proc_handler_proc_t,
+        data: *mut c_void,
+    );
+    pub fn obs_enum_sources(enum_proc: obs_enum_proc_t, param: *mut c_void);
+    pub fn obs_source_get_unversioned_id(source: *const obs_source_t) -> *const c_char;
+    pub fn obs_source_create_private(
+        id: *const c_char,
+        name: *const c_char,
+        settings: *mut obs_data_t,
+    ) -> *mut obs_source_t;
+    pub fn obs_queue_task(type_: obs_task_type, task: obs_task_t, param: *mut c_void, wait: bool);
+}
+
+#[cfg(windows)]
+#[link(name = "obs", kind = "dylib")]
+extern "C" {
+    pub fn os_utf8_to_wcs_ptr(str: *const c_char, len: size_t, pstr: *mut *mut wchar_t) -> size_t;
+    pub fn os_wcs_to_utf8_ptr(str: *const wchar_t, len: size_t, pstr: *mut *mut c_char) -> size_t;
 }
@@ -5,8 +5,15 @@ use std::{
     os::raw::{c_char, c_int, c_long},
 };
 
+#[cfg(windows)]
+pub type wchar_t = u16;
+
 pub type gs_color_format = u32;
 pub const GS_RGBA: gs_color_format = 3;
+
+pub type gs_color_space = u32;
+pub const GS_CS_SRGB: gs_color_space = 0;
+
 pub type gs_effect_t = gs_effect;
 
 #[repr(C)]
@@ -58,6 +65,9 @@ pub type obs_hotkey_func = Option<
 pub type size_t = usize;
 pub type obs_hotkey_id = size_t;
 
+pub type obs_key_t = c_int;
+pub const OBS_KEY_NONE: obs_key_t = 0;
+
 pub type obs_hotkey_t = obs_hotkey;
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
@@ -83,8 +93,50 @@ pub struct obs_mouse_event {
     pub y: i32,
 }
 
+pub const MOUSE_LEFT: i32 = 0;
+pub const MOUSE_MIDDLE: i32 = 1;
+pub const MOUSE_RIGHT: i32 = 2;
+
 pub type obs_path_type = u32;
 pub const OBS_PATH_FILE: obs_path_type = 0;
+pub const OBS_PATH_FILE_SAVE: obs_path_type = 1;
+pub const OBS_PATH_DIRECTORY: obs_path_type = 2;
+
+pub type obs_text_type = u32;
+pub const OBS_TEXT_DEFAULT: obs_text_type = 0;
+pub const OBS_TEXT_PASSWORD: obs_text_type = 1;
+pub const OBS_TEXT_MULTILINE: obs_text_type = 2;
+pub const OBS_TEXT_INFO: obs_text_type = 3;
+
+pub type obs_editable_list_type = u32;
+pub const OBS_EDITABLE_LIST_TYPE_STRINGS: obs_editable_list_type = 0;
+pub const OBS_EDITABLE_LIST_TYPE_FILES: obs_editable_list_type = 1;
+
+pub type obs_combo_type = u32;
+pub const OBS_COMBO_TYPE_LIST: obs_combo_type = 2;
+
+pub type obs_combo_format = u32;
+pub const OBS_COMBO_FORMAT_INT: obs_combo_format = 1;
+pub const OBS_COMBO_FORMAT_STRING: obs_combo_format = 3;
+
+pub type obs_data_array_t = obs_data_array;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct obs_data_array {
+    _unused: [u8; 0],
+}
+
+pub type obs_group_type = u32;
+pub const OBS_GROUP_NORMAL: obs_group_type = 1;
+
+pub type obs_property_modified2_t = Option<
+    unsafe extern "C" fn(
+        priv_: *mut c_void,
+        props: *mut obs_properties_t,
+        property: *mut obs_property_t,
+        settings: *mut obs_data_t,
+    ) -> bool,
+>;
 
 pub type obs_properties_t = obs_properties;
 #[repr(C)]
@@ -219,6 +271,14 @@ pub struct obs_source_info {
     pub media_get_state: Option<unsafe extern "C" fn(data: *mut c_void) -> obs_media_state>,
     pub version: u32,
     pub unversioned_id: *const c_char,
+    pub missing_files: Option<unsafe extern "C" fn(data: *mut c_void) -> *mut obs_missing_files_t>,
+    pub video_get_color_space: Option<
+        unsafe extern "C" fn(
+            data: *mut c_void,
+            count: size_t,
+            preferred_spaces: *const gs_color_space,
+        ) -> gs_color_space,
+    >,
 }
 
 pub type obs_source_type = u32;
@@ -307,4 +367,81 @@ pub const LOG_DEBUG: _bindgen_ty_1 = 400;
 pub const OBS_SOURCE_CUSTOM_DRAW: u32 = 8;
 pub const OBS_SOURCE_INTERACTION: u32 = 32;
 pub const OBS_SOURCE_VIDEO: u32 = 1;
+pub const OBS_SOURCE_AUDIO: u32 = 1 << 1;
+pub const OBS_SOURCE_CAP_DISABLED: u32 = 1 << 10;
 pub const OBS_SOURCE_CONTROLLABLE_MEDIA: u32 = 1 << 13;
+
+pub type proc_handler_t = proc_handler;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct proc_handler {
+    _unused: [u8; 0],
+}
+
+pub type signal_handler_t = signal_handler;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct signal_handler {
+    _unused: [u8; 0],
+}
+
+pub type obs_missing_files_t = obs_missing_files;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct obs_missing_files {
+    _unused: [u8; 0],
+}
+
+pub type obs_missing_file_t = obs_missing_file;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct obs_missing_file {
+    _unused: [u8; 0],
+}
+
+pub type obs_missing_file_cb =
+    Option<unsafe extern "C" fn(src: *mut c_void, new_path: *const c_char, data: *mut c_void)>;
+
+pub type obs_missing_file_src = u32;
+pub const OBS_MISSING_FILE_SOURCE: obs_missing_file_src = 0;
+
+pub type calldata_t = calldata;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct calldata {
+    pub stack: *mut u8,
+    pub size: size_t,
+    pub capacity: size_t,
+    pub fixed: bool,
+}
+
+pub type obs_enum_proc_t =
+    Option<unsafe extern "C" fn(param: *mut c_void, source: *mut obs_source_t) -> bool>;
+
+pub type proc_handler_proc_t = Option<unsafe extern "C" fn(data: *mut c_void, cd: *mut calldata_t)>;
+
+pub type obs_tick_callback_t = Option<unsafe extern "C" fn(param: *mut c_void, seconds: f32)>;
+
+pub type obs_task_type = u32;
+pub const OBS_TASK_UI: obs_task_type = 0;
+
+pub type obs_task_t = Option<unsafe extern "C" fn(param: *mut c_void)>;
+
+pub const MAX_AV_PLANES: usize = 8;
+
+pub type speaker_layout = u32;
+pub const SPEAKERS_UNKNOWN: speaker_layout = 0;
+
+pub type audio_format = u32;
+pub const AUDIO_FORMAT_FLOAT: audio_format = 4;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct obs_source_audio {
+    pub data: [*const u8; MAX_AV_PLANES],
+    pub frames: u32,
+    pub speakers: speaker_layout,
+    pub format: audio_format,
+    pub samples_per_sec: u32,
+    pub timestamp: u64,
+}
@@ -0,0 +1,204 @@
+use std::{
+    fs,
+    os::raw::c_char,
+    path::{Path, PathBuf},
+    ptr,
+    sync::Mutex,
+};
+
+use livesplit_core::rendering::software::Renderer;
+
+use crate::{
+    ffi::{
+        obs_data_get_string, obs_data_t, obs_properties_add_path, obs_properties_t,
+        obs_property_set_long_description, OBS_PATH_DIRECTORY,
+    },
+    path,
+};
+
+const SETTINGS_FONTS_FOLDER: *const c_char = cstr!("fonts_folder");
+
+const FONT_EXTENSIONS: [&str; 4] = ["ttf", "otf", "ttc", "otc"];
+
+/// System fonts that have the characters that the fonts of most layouts lack,
+/// like Japanese, Chinese and Korean ones and emoji. Each entry lists the
+/// places where a distribution may have put the same font, of which the first
+/// one that exists gets loaded.
+#[cfg(target_os = "linux")]
+const FALLBACK_FONTS: &[&[&str]] = &[
+    &[
+        "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+        "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+        "/usr/share/fonts/google-noto-cjk/NotoSansCJK-Regular.ttc",
+        "/usr/share/fonts/OTF/NotoSansCJK-Regular.ttc",
+    ],
+    &[
+        "/usr/share/fonts/truetype/noto/NotoColorEmoji.ttf",
+        "/usr/share/fonts/noto/NotoColorEmoji.ttf",
+        "/usr/share/fonts/google-noto-emoji/NotoColorEmoji.ttf",
+        "/usr/share/fonts/TTF/NotoColorEmoji.ttf",
+    ],
+];
+
+/// System fonts that have the characters that the fonts of most layouts lack,
+/// like Japanese, Chinese and Korean ones and emoji. They are in the fonts
+/// folder of Windows.
+#[cfg(windows)]
+const FALLBACK_FONTS: &[&[&str]] = &[
+    &["seguiemj.ttf"],
+    &["msyh.ttc"],
+    &["msgothic.ttc"],
+    &["malgun.ttf"],
+];
+
+/// System fonts that have the characters that the fonts of most layouts lack,
+/// like Japanese, Chinese and Korean ones and emoji.
+#[cfg(target_os = "macos")]
+const FALLBACK_FONTS: &[&[&str]] = &[
+    &["/System/Library/Fonts/Apple Color Emoji.ttc"],
+    &["/System/Library/Fonts/PingFang.ttc"],
+    &[
+        "/System/Library/Fonts/ヒラギノ角ゴシック W3.ttc",
+        "/System/Library/Fonts/Hiragino Sans GB.ttc",
+    ],
+    &["/System/Library/Fonts/AppleSDGothicNeo.ttc"],
+];
+
+#[cfg(not(any(target_os = "linux", windows, target_os = "macos")))]
+const FALLBACK_FONTS: &[&[&str]] = &[];
+
+/// The fonts that were loaded from the fonts folders of all the sources. The
+/// folders are remembered, so that they aren't read again whenever a source
+/// that uses them is updated. The fallback fonts of the system are loaded
+/// along with the first renderer.
+struct Loaded {
+    folders: Vec<PathBuf>,
+    fonts: Vec<Vec<u8>>,
+    fallback_loaded: bool,
+}
+
+static LOADED: Mutex<Loaded> = Mutex::new(Loaded {
+    folders: Vec::new(),
+    fonts: Vec::new(),
+    fallback_loaded: false,
+});
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let folder = obs_properties_add_path(
+        props,
+        SETTINGS_FONTS_FOLDER,
+        cstr!("Fonts Folder"),
+        OBS_PATH_DIRECTORY,
+        ptr::null(),
+        ptr::null(),
+    );
+    obs_property_set_long_description(
+        folder,
+        cstr!("A folder with fonts that the layout uses, but that aren't installed. They're only loaded into OBS, not installed."),
+    );
+}
+
+pub unsafe fn parse_settings(settings: *mut obs_data_t) -> PathBuf {
+    path::from_obs(obs_data_get_string(settings, SETTINGS_FONTS_FOLDER))
+}
+
+fn is_font(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .map_or(false, |extension| {
+            FONT_EXTENSIONS
+                .iter()
+                .any(|font| extension.eq_ignore_ascii_case(font))
+        })
+}
+
+/// Loads the fonts of the folder into memory, so that the renderers that are
+/// created from then on can use them. Returns whether fonts were added, in
+/// which case the renderers need to be recreated.
+pub fn load_folder(folder: &Path) -> bool {
+    if folder.as_os_str().is_empty() {
+        return false;
+    }
+    let mut loaded = LOADED.lock().unwrap();
+    if loaded.folders.iter().any(|loaded| loaded == folder) {
+        return false;
+    }
+    loaded.folders.push(folder.to_owned());
+
+    let entries = match fs::read_dir(folder) {
+        Ok(entries) => entries,
+        Err(e) => {
+            log::warn!("Failed to read the fonts folder: {e}");
+            return false;
+        }
+    };
+
+    let mut added = false;
+    for path in entries.filter_map(|entry| Some(entry.ok()?.path())) {
+        if !is_font(&path) {
+            continue;
+        }
+        match fs::read(&path) {
+            Ok(data) => {
+                log::info!("Loaded the font {}.", path.display());
+                loaded.fonts.push(data);
+                added = true;
+            }
+            Err(e) => log::warn!("Failed to load the font {}: {e}", path.display()),
+        }
+    }
+    added
+}
+
+/// The path of a fallback font. On Windows, the fonts are in the fonts folder
+/// of the system, wherever Windows is installed.
+fn fallback_path(path: &str) -> PathBuf {
+    #[cfg(windows)]
+    {
+        let windows = std::env::var_os("WINDIR").unwrap_or_else(|| r"C:\Windows".into());
+        Path::new(&windows).join("Fonts").join(path)
+    }
+    #[cfg(not(windows))]
+    {
+        PathBuf::from(path)
+    }
+}
+
+/// Loads the fonts of the system that the renderer falls back to for
+/// characters that the layout's fonts lack. Fonts that aren't installed are
+/// skipped.
+fn load_fallback(loaded: &mut Loaded) {
+    if loaded.fallback_loaded {
+        return;
+    }
+    loaded.fallback_loaded = true;
+
+    for candidates in FALLBACK_FONTS {
+        let Some(path) = candidates
+            .iter()
+            .map(|path| fallback_path(path))
+            .find(|path| path.is_file())
+        else {
+            continue;
+        };
+        match fs::read(&path) {
+            Ok(data) => {
+                log::info!("Loaded the fallback font {}.", path.display());
+                loaded.fonts.push(data);
+            }
+            Err(e) => log::warn!("Failed to load the fallback font {}: {e}", path.display()),
+        }
+    }
+}
+
+/// Creates a renderer that knows the fonts that were loaded, besides the ones
+/// that are installed.
+pub fn renderer() -> Renderer {
+    let mut renderer = Renderer::new();
+    let mut loaded = LOADED.lock().unwrap();
+    load_fallback(&mut loaded);
+    for font in &loaded.fonts {
+        renderer.load_font_data(font.clone());
+    }
+    renderer
+}
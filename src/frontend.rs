@@ -0,0 +1,126 @@
+// obs-frontend-api is only available when running inside of the OBS frontend,
+// so instead of linking against it, its functions are looked up at runtime.
+// If they can't be found, the features that need them don't do anything.
+
+use std::{ffi::c_void, os::raw::c_char, sync::OnceLock};
+
+use libloading::Library;
+
+use crate::ffi::{obs_queue_task, obs_source_t, OBS_TASK_UI};
+
+struct FrontendApi {
+    set_current_scene: unsafe extern "C" fn(scene: *mut obs_source_t),
+    recording_start: unsafe extern "C" fn(),
+    recording_stop: unsafe extern "C" fn(),
+    recording_active: unsafe extern "C" fn() -> bool,
+    replay_buffer_save: unsafe extern "C" fn(),
+    add_tools_menu_item: unsafe extern "C" fn(
+        name: *const c_char,
+        callback: unsafe extern "C" fn(data: *mut c_void),
+        data: *mut c_void,
+    ),
+    open_source_properties: unsafe extern "C" fn(source: *mut obs_source_t),
+}
+
+static API: OnceLock<Option<FrontendApi>> = OnceLock::new();
+
+fn load() -> Option<FrontendApi> {
+    // The frontend already loaded the library and keeps it loaded, so we just
+    // need a handle to it to look up the functions.
+    #[cfg(windows)]
+    let library: Library =
+        libloading::os::windows::Library::open_already_loaded("obs-frontend-api")
+            .ok()?
+            .into();
+    #[cfg(not(windows))]
+    let library: Library = libloading::os::unix::Library::this().into();
+
+    let api = unsafe {
+        FrontendApi {
+            set_current_scene: *library.get(b"obs_frontend_set_current_scene\0").ok()?,
+            recording_start: *library.get(b"obs_frontend_recording_start\0").ok()?,
+            recording_stop: *library.get(b"obs_frontend_recording_stop\0").ok()?,
+            recording_active: *library.get(b"obs_frontend_recording_active\0").ok()?,
+            replay_buffer_save: *library.get(b"obs_frontend_replay_buffer_save\0").ok()?,
+            add_tools_menu_item: *library.get(b"obs_frontend_add_tools_menu_item\0").ok()?,
+            open_source_properties: *library.get(b"obs_frontend_open_source_properties\0").ok()?,
+        }
+    };
+    Some(api)
+}
+
+fn api() -> Option<&'static FrontendApi> {
+    API.get_or_init(|| {
+        let api = load();
+        if api.is_none() {
+            log::warn!("obs-frontend-api is not available.");
+        }
+        api
+    })
+    .as_ref()
+}
+
+type Task = Box<dyn FnOnce() + Send>;
+
+unsafe extern "C" fn run_task(param: *mut c_void) {
+    let task: Box<Task> = Box::from_raw(param.cast());
+    task();
+}
+
+/// Runs the task on the UI thread, as that's the only thread the frontend may
+/// be used from. The task runs later, so the caller doesn't wait for it.
+pub fn queue_ui_task(task: impl FnOnce() + Send + 'static) {
+    let task: Box<Task> = Box::new(Box::new(task));
+    unsafe {
+        obs_queue_task(
+            OBS_TASK_UI,
+            Some(run_task),
+            Box::into_raw(task).cast(),
+            false,
+        );
+    }
+}
+
+pub unsafe fn set_current_scene(scene: *mut obs_source_t) {
+    if let Some(api) = api() {
+        (api.set_current_scene)(scene);
+    }
+}
+
+pub unsafe fn recording_start() {
+    if let Some(api) = api() {
+        if !(api.recording_active)() {
+            (api.recording_start)();
+        }
+    }
+}
+
+pub unsafe fn recording_stop() {
+    if let Some(api) = api() {
+        if (api.recording_active)() {
+            (api.recording_stop)();
+        }
+    }
+}
+
+pub unsafe fn replay_buffer_save() {
+    if let Some(api) = api() {
+        (api.replay_buffer_save)();
+    }
+}
+
+pub unsafe fn add_tools_menu_item(
+    name: *const c_char,
+    callback: unsafe extern "C" fn(data: *mut c_void),
+    data: *mut c_void,
+) {
+    if let Some(api) = api() {
+        (api.add_tools_menu_item)(name, callback, data);
+    }
+}
+
+pub unsafe fn open_source_properties(source: *mut obs_source_t) {
+    if let Some(api) = api() {
+        (api.open_source_properties)(source);
+    }
+}
@@ -0,0 +1,156 @@
+use std::{
+    ffi::CStr,
+    os::raw::c_char,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+use sysinfo::{ProcessExt, System, SystemExt};
+
+use crate::{
+    ffi::{
+        obs_data_create, obs_data_get_string, obs_data_release, obs_data_set_string, obs_data_t,
+        obs_properties_add_text, obs_properties_t, obs_property_set_long_description, obs_source_t,
+        obs_source_update, OBS_TEXT_MULTILINE,
+    },
+    path, SETTINGS_AUTO_SPLITTER_PATH, SETTINGS_SPLITS_PATH,
+};
+
+const SETTINGS_GAME_DETECTION: *const c_char = cstr!("game_detection");
+
+/// Starting a game takes a moment anyway, so there's no need to look for it
+/// more often.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Clone, PartialEq)]
+pub struct Game {
+    pub process: String,
+    pub auto_splitter: PathBuf,
+    pub splits: Option<PathBuf>,
+}
+
+/// Watches the running processes for the games that are listed in the
+/// settings. Listing the processes can take a while on some systems, so it
+/// happens on a separate thread.
+pub struct GameDetection {
+    games: Vec<Game>,
+    receiver: mpsc::Receiver<usize>,
+    _stop: mpsc::Sender<()>,
+}
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let games = obs_properties_add_text(
+        props,
+        SETTINGS_GAME_DETECTION,
+        cstr!("Game Detection"),
+        OBS_TEXT_MULTILINE,
+    );
+    obs_property_set_long_description(
+        games,
+        cstr!("One game per line, like \"Celeste.exe = C:\\Auto Splitters\\celeste.wasm | C:\\Splits\\Celeste.lss\". When the process starts, its auto splitter and optionally its splits are chosen."),
+    );
+}
+
+/// Parses lines like `Celeste.exe = celeste.wasm | Celeste.lss` into the
+/// processes along with their auto splitters and splits.
+pub unsafe fn parse_settings(settings: *mut obs_data_t) -> Option<Vec<Game>> {
+    let games: Vec<_> = CStr::from_ptr(obs_data_get_string(settings, SETTINGS_GAME_DETECTION))
+        .to_string_lossy()
+        .lines()
+        .filter_map(|line| {
+            let (process, paths) = line.split_once('=')?;
+            let (auto_splitter, splits) = match paths.split_once('|') {
+                Some((auto_splitter, splits)) => (auto_splitter, Some(splits.trim())),
+                None => (paths, None),
+            };
+            let (process, auto_splitter) = (process.trim(), auto_splitter.trim());
+            if process.is_empty() || auto_splitter.is_empty() {
+                return None;
+            }
+            Some(Game {
+                process: process.to_owned(),
+                auto_splitter: auto_splitter.into(),
+                splits: splits.filter(|splits| !splits.is_empty()).map(Into::into),
+            })
+        })
+        .collect();
+    if games.is_empty() {
+        return None;
+    }
+    Some(games)
+}
+
+/// Checks whether the process is the game. The names of processes are
+/// shortened on Linux, so the name of the executable is checked as well.
+fn is_game(process: &impl ProcessExt, game: &str) -> bool {
+    process.name().eq_ignore_ascii_case(game)
+        || process.exe().file_name().map_or(false, |name| {
+            name.to_string_lossy().eq_ignore_ascii_case(game)
+        })
+}
+
+impl GameDetection {
+    pub fn new(games: Vec<Game>) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let (stop, stopped) = mpsc::channel::<()>();
+        let processes: Vec<_> = games.iter().map(|game| game.process.clone()).collect();
+        thread::spawn(move || {
+            let mut system = System::new();
+            let mut running = None;
+            loop {
+                system.refresh_processes();
+                let found = processes.iter().position(|game| {
+                    system
+                        .processes()
+                        .values()
+                        .any(|process| is_game(process, game))
+                });
+                // Only games that were just started are reported, so choosing
+                // something else while the game runs sticks.
+                if found != running {
+                    running = found;
+                    if let Some(index) = found {
+                        let _ = sender.send(index);
+                    }
+                }
+                match stopped.recv_timeout(POLL_INTERVAL) {
+                    Err(RecvTimeoutError::Timeout) => {}
+                    _ => break,
+                }
+            }
+        });
+        Self {
+            games,
+            receiver,
+            _stop: stop,
+        }
+    }
+
+    pub fn games(&self) -> &[Game] {
+        &self.games
+    }
+
+    /// Returns the game that was started since the last call, if any.
+    pub fn poll(&self) -> Option<&Game> {
+        self.receiver
+            .try_iter()
+            .last()
+            .and_then(|index| self.games.get(index))
+    }
+}
+
+/// Chooses the auto splitter and the splits of the game. This goes through
+/// the settings, so that the choice shows up in the properties and is stored.
+pub unsafe fn switch_to(source: *mut obs_source_t, auto_splitter: &Path, splits: Option<&Path>) {
+    let changes = obs_data_create();
+    if let Some(auto_splitter) = path::to_obs(auto_splitter) {
+        obs_data_set_string(changes, SETTINGS_AUTO_SPLITTER_PATH, auto_splitter.as_ptr());
+    }
+    if let Some(splits) = splits.and_then(path::to_obs) {
+        obs_data_set_string(changes, SETTINGS_SPLITS_PATH, splits.as_ptr());
+    }
+    obs_source_update(source, changes);
+    obs_data_release(changes);
+}
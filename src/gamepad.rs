@@ -0,0 +1,203 @@
+use std::{ffi::CStr, os::raw::c_char};
+
+use gilrs::{Button, Event, EventType, Gilrs};
+
+use crate::{
+    command::Command,
+    ffi::{
+        obs_data_get_string, obs_data_t, obs_properties_add_group, obs_properties_add_list,
+        obs_properties_create, obs_properties_t, obs_property_list_add_string,
+        obs_property_set_long_description, obs_property_t, OBS_COMBO_FORMAT_STRING,
+        OBS_COMBO_TYPE_LIST, OBS_GROUP_NORMAL,
+    },
+};
+
+const SETTINGS_GAMEPAD: *const c_char = cstr!("gamepad");
+const SETTINGS_GAMEPAD_MODIFIER: *const c_char = cstr!("gamepad_modifier");
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Action {
+    Split,
+    Undo,
+    Pause,
+    Reset,
+}
+
+/// The settings of the actions, along with their names in the properties.
+const ACTIONS: [(*const c_char, *const c_char, Action); 4] = [
+    (
+        cstr!("gamepad_split"),
+        cstr!("Start / Split"),
+        Action::Split,
+    ),
+    (cstr!("gamepad_undo"), cstr!("Undo Split"), Action::Undo),
+    (cstr!("gamepad_pause"), cstr!("Pause"), Action::Pause),
+    (cstr!("gamepad_reset"), cstr!("Reset"), Action::Reset),
+];
+
+/// The buttons that can be chosen, with their names in the properties and in
+/// the settings. They are named after the layout of Xbox controllers.
+const BUTTONS: [(*const c_char, *const c_char, Button); 17] = [
+    (cstr!("A"), cstr!("south"), Button::South),
+    (cstr!("B"), cstr!("east"), Button::East),
+    (cstr!("X"), cstr!("west"), Button::West),
+    (cstr!("Y"), cstr!("north"), Button::North),
+    (
+        cstr!("Left Bumper"),
+        cstr!("left_bumper"),
+        Button::LeftTrigger,
+    ),
+    (
+        cstr!("Right Bumper"),
+        cstr!("right_bumper"),
+        Button::RightTrigger,
+    ),
+    (
+        cstr!("Left Trigger"),
+        cstr!("left_trigger"),
+        Button::LeftTrigger2,
+    ),
+    (
+        cstr!("Right Trigger"),
+        cstr!("right_trigger"),
+        Button::RightTrigger2,
+    ),
+    (cstr!("Back"), cstr!("select"), Button::Select),
+    (cstr!("Start"), cstr!("start"), Button::Start),
+    (cstr!("Guide"), cstr!("mode"), Button::Mode),
+    (cstr!("Left Stick"), cstr!("left_thumb"), Button::LeftThumb),
+    (
+        cstr!("Right Stick"),
+        cstr!("right_thumb"),
+        Button::RightThumb,
+    ),
+    (cstr!("D-Pad Up"), cstr!("dpad_up"), Button::DPadUp),
+    (cstr!("D-Pad Down"), cstr!("dpad_down"), Button::DPadDown),
+    (cstr!("D-Pad Left"), cstr!("dpad_left"), Button::DPadLeft),
+    (cstr!("D-Pad Right"), cstr!("dpad_right"), Button::DPadRight),
+];
+
+#[derive(PartialEq)]
+pub struct Config {
+    modifier: Option<Button>,
+    bindings: Vec<(Button, Action)>,
+}
+
+/// Triggers timer actions with the buttons of any connected gamepad, for
+/// runners whose hands never leave the controller. The gamepads are read even
+/// when OBS isn't focused, which is why XInput rather than Windows.Gaming.Input
+/// is used on Windows.
+pub struct Gamepad {
+    gilrs: Gilrs,
+    config: Config,
+}
+
+unsafe fn add_button_list(
+    props: *mut obs_properties_t,
+    setting: *const c_char,
+    name: *const c_char,
+) -> *mut obs_property_t {
+    let list = obs_properties_add_list(
+        props,
+        setting,
+        name,
+        OBS_COMBO_TYPE_LIST,
+        OBS_COMBO_FORMAT_STRING,
+    );
+    obs_property_list_add_string(list, cstr!("None"), cstr!(""));
+    for (label, value, _) in BUTTONS {
+        obs_property_list_add_string(list, label, value);
+    }
+    list
+}
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let group = obs_properties_create();
+    for (setting, name, _) in ACTIONS {
+        add_button_list(group, setting, name);
+    }
+    let modifier = add_button_list(group, SETTINGS_GAMEPAD_MODIFIER, cstr!("Hold"));
+    obs_property_set_long_description(
+        modifier,
+        cstr!("A button that needs to be held for the other buttons to do anything, so they can still be used in the game on their own."),
+    );
+
+    obs_properties_add_group(
+        props,
+        SETTINGS_GAMEPAD,
+        cstr!("Gamepad"),
+        OBS_GROUP_NORMAL,
+        group,
+    );
+}
+
+pub unsafe fn parse_settings(settings: *mut obs_data_t) -> Option<Config> {
+    let get = |setting| {
+        let value = CStr::from_ptr(obs_data_get_string(settings, setting)).to_bytes();
+        BUTTONS
+            .iter()
+            .find(|&&(_, name, _)| CStr::from_ptr(name).to_bytes() == value)
+            .map(|&(_, _, button)| button)
+    };
+    let bindings: Vec<_> = ACTIONS
+        .iter()
+        .filter_map(|&(setting, _, action)| Some((get(setting)?, action)))
+        .collect();
+    if bindings.is_empty() {
+        return None;
+    }
+    Some(Config {
+        modifier: get(SETTINGS_GAMEPAD_MODIFIER),
+        bindings,
+    })
+}
+
+impl Action {
+    fn command(self) -> Command {
+        match self {
+            Action::Split => Command::SplitOrStart,
+            Action::Undo => Command::UndoSplit,
+            Action::Pause => Command::TogglePauseOrStart,
+            Action::Reset => Command::Reset { save_attempt: None },
+        }
+    }
+}
+
+impl Gamepad {
+    pub fn new(config: Config) -> Option<Self> {
+        let gilrs = Gilrs::new()
+            .map_err(|e| log::warn!("Failed to access the gamepads: {e}"))
+            .ok()?;
+        Some(Self { gilrs, config })
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Returns the commands of the buttons that were pressed since the last
+    /// call.
+    pub fn poll(&mut self) -> Vec<Command> {
+        let mut commands = Vec::new();
+        while let Some(Event { id, event, .. }) = self.gilrs.next_event() {
+            let EventType::ButtonPressed(button, _) = event else {
+                continue;
+            };
+            let held = self
+                .config
+                .modifier
+                .map_or(true, |modifier| self.gilrs.gamepad(id).is_pressed(modifier));
+            if !held {
+                continue;
+            }
+            commands.extend(
+                self.config
+                    .bindings
+                    .iter()
+                    .filter(|&&(bound, _)| bound == button)
+                    .map(|&(_, action)| action.command()),
+            );
+        }
+        commands
+    }
+}
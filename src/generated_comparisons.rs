@@ -0,0 +1,73 @@
+use std::os::raw::c_char;
+
+use livesplit_core::comparison::{
+    average_segments, balanced_pb, latest_run, median_segments, worst_segments,
+};
+
+use crate::ffi::{
+    obs_data_get_bool, obs_data_set_default_bool, obs_data_t, obs_properties_add_bool,
+    obs_properties_add_group, obs_properties_create, obs_properties_t, OBS_GROUP_NORMAL,
+};
+
+const SETTINGS_GENERATED_COMPARISONS: *const c_char = cstr!("generated_comparisons");
+
+/// The generated comparisons that can be left out when switching between
+/// comparisons, with the settings that keep them in and their labels.
+const COMPARISONS: [(*const c_char, *const c_char, &str); 5] = [
+    (
+        cstr!("comparison_balanced_pb"),
+        cstr!("Balanced PB"),
+        balanced_pb::NAME,
+    ),
+    (
+        cstr!("comparison_average_segments"),
+        cstr!("Average Segments"),
+        average_segments::NAME,
+    ),
+    (
+        cstr!("comparison_median_segments"),
+        cstr!("Median Segments"),
+        median_segments::NAME,
+    ),
+    (
+        cstr!("comparison_worst_segments"),
+        cstr!("Worst Segments"),
+        worst_segments::NAME,
+    ),
+    (
+        cstr!("comparison_latest_run"),
+        cstr!("Latest Run"),
+        latest_run::NAME,
+    ),
+];
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let group = obs_properties_create();
+    for (setting, label, _) in COMPARISONS {
+        obs_properties_add_bool(group, setting, label);
+    }
+
+    obs_properties_add_group(
+        props,
+        SETTINGS_GENERATED_COMPARISONS,
+        cstr!("Comparisons to Switch Between"),
+        OBS_GROUP_NORMAL,
+        group,
+    );
+}
+
+pub unsafe fn set_defaults(settings: *mut obs_data_t) {
+    for (setting, _, _) in COMPARISONS {
+        obs_data_set_default_bool(settings, setting, true);
+    }
+}
+
+/// Returns the comparisons that are skipped when switching to the previous or
+/// next comparison.
+pub unsafe fn parse_settings(settings: *mut obs_data_t) -> Vec<&'static str> {
+    COMPARISONS
+        .iter()
+        .filter(|&&(setting, _, _)| !obs_data_get_bool(settings, setting))
+        .map(|&(_, _, name)| name)
+        .collect()
+}
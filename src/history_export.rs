@@ -0,0 +1,179 @@
+use std::{error::Error, ffi::c_void, fmt::Write, fs, os::raw::c_char, path::Path, ptr};
+
+use livesplit_core::{Run, Time, TimeSpan};
+use serde_json::{json, Value};
+
+use crate::{
+    ffi::{
+        obs_data_get_string, obs_data_release, obs_properties_add_button, obs_properties_add_group,
+        obs_properties_add_path, obs_properties_create, obs_properties_t,
+        obs_property_set_long_description, obs_property_t, obs_source_get_settings,
+        OBS_GROUP_NORMAL, OBS_PATH_FILE_SAVE,
+    },
+    path, State,
+};
+
+const SETTINGS_HISTORY_EXPORT: *const c_char = cstr!("history_export");
+const SETTINGS_HISTORY_EXPORT_PATH: *const c_char = cstr!("history_export_path");
+const SETTINGS_HISTORY_EXPORT_SAVE: *const c_char = cstr!("history_export_save");
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let group = obs_properties_create();
+    let path = obs_properties_add_path(
+        group,
+        SETTINGS_HISTORY_EXPORT_PATH,
+        cstr!("File"),
+        OBS_PATH_FILE_SAVE,
+        cstr!("CSV Files (*.csv);;JSON Files (*.json)"),
+        ptr::null(),
+    );
+    obs_property_set_long_description(
+        path,
+        cstr!("The file that all attempts and the history of every segment are written to. Its extension decides between CSV and JSON."),
+    );
+    obs_properties_add_button(
+        group,
+        SETTINGS_HISTORY_EXPORT_SAVE,
+        cstr!("Export History"),
+        Some(export),
+    );
+
+    obs_properties_add_group(
+        props,
+        SETTINGS_HISTORY_EXPORT,
+        cstr!("History Export"),
+        OBS_GROUP_NORMAL,
+        group,
+    );
+}
+
+/// Times are written in seconds, so spreadsheets can calculate with them.
+fn seconds(time: Option<TimeSpan>) -> Option<f64> {
+    time.map(TimeSpan::total_seconds)
+}
+
+/// Returns the time that the segment took in the attempt, if it was split.
+fn segment_time(run: &Run, segment: usize, attempt: i32) -> Option<Time> {
+    run.segment(segment)
+        .segment_history()
+        .iter()
+        .find(|&&(index, _)| index == attempt)
+        .map(|&(_, time)| time)
+}
+
+fn to_json(run: &Run) -> Value {
+    let attempts: Vec<_> = run
+        .attempt_history()
+        .iter()
+        .map(|attempt| {
+            let index = attempt.index();
+            let segments: Vec<_> = run
+                .segments()
+                .iter()
+                .enumerate()
+                .map(|(segment, data)| {
+                    let time = segment_time(run, segment, index).unwrap_or_default();
+                    json!({
+                        "name": data.name(),
+                        "realTime": seconds(time.real_time),
+                        "gameTime": seconds(time.game_time),
+                    })
+                })
+                .collect();
+            json!({
+                "id": index,
+                "started": attempt.started().map(|started| started.time.unix_timestamp()),
+                "ended": attempt.ended().map(|ended| ended.time.unix_timestamp()),
+                "realTime": seconds(attempt.time().real_time),
+                "gameTime": seconds(attempt.time().game_time),
+                "pauseTime": seconds(attempt.pause_time()),
+                "segments": segments,
+            })
+        })
+        .collect();
+    json!({
+        "game": run.game_name(),
+        "category": run.category_name(),
+        "attempts": attempts,
+    })
+}
+
+/// Quotes the field if it contains anything that would break the row.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+fn csv_time(time: Option<TimeSpan>) -> String {
+    seconds(time).map_or_else(String::new, |seconds| format!("{seconds:.3}"))
+}
+
+/// Writes a row for every segment of every attempt, followed by a row with
+/// the attempt's total time.
+fn to_csv(run: &Run) -> String {
+    let mut csv = String::from("Attempt,Started,Segment,Real Time,Game Time\n");
+    for attempt in run.attempt_history() {
+        let index = attempt.index();
+        let started = attempt
+            .started()
+            .map(|started| started.time.unix_timestamp().to_string())
+            .unwrap_or_default();
+        for (segment, data) in run.segments().iter().enumerate() {
+            let Some(time) = segment_time(run, segment, index) else {
+                continue;
+            };
+            let _ = writeln!(
+                csv,
+                "{index},{started},{},{},{}",
+                csv_field(data.name()),
+                csv_time(time.real_time),
+                csv_time(time.game_time),
+            );
+        }
+        let _ = writeln!(
+            csv,
+            "{index},{started},Total,{},{}",
+            csv_time(attempt.time().real_time),
+            csv_time(attempt.time().game_time),
+        );
+    }
+    csv
+}
+
+fn write_history(run: &Run, path: &Path) -> Result<(), Box<dyn Error>> {
+    let is_json = path
+        .extension()
+        .map_or(false, |extension| extension.eq_ignore_ascii_case("json"));
+    let contents = if is_json {
+        serde_json::to_string_pretty(&to_json(run))?
+    } else {
+        to_csv(run)
+    };
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+unsafe extern "C" fn export(
+    _: *mut obs_properties_t,
+    _: *mut obs_property_t,
+    data: *mut c_void,
+) -> bool {
+    let state: &State = &*data.cast();
+    let settings = obs_source_get_settings(state.source);
+    let path = path::from_obs(obs_data_get_string(settings, SETTINGS_HISTORY_EXPORT_PATH));
+    obs_data_release(settings);
+
+    if path.as_os_str().is_empty() {
+        log::warn!("Choose the file to export the history to first.");
+        return false;
+    }
+    let timer = state.main_timer().read().unwrap();
+    match write_history(timer.run(), &path) {
+        Ok(()) => log::info!("Exported the history to {}.", path.display()),
+        Err(e) => log::warn!("Failed to export the history: {e}"),
+    }
+    false
+}
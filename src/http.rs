@@ -0,0 +1,11 @@
+use std::time::Duration;
+
+/// How long a request may take at most. Without a limit, a server that stops
+/// answering keeps the request waiting forever.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Returns an agent for requests that are made on a thread of their own, so
+/// they don't keep OBS waiting. Their threads still give up eventually.
+pub fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new().timeout(TIMEOUT).build()
+}
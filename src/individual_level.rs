@@ -0,0 +1,150 @@
+use std::{ffi::CString, os::raw::c_char, sync::Arc};
+
+use livesplit_core::{Run, Segment, SharedTimer, Time, Timer, TimerPhase, TimingMethod};
+
+use crate::{
+    events::{EventTracker, TimerEvent},
+    ffi::{
+        obs_data_get_int, obs_data_set_default_int, obs_data_t, obs_properties_add_list,
+        obs_properties_t, obs_property_list_add_int, obs_property_set_long_description,
+        OBS_COMBO_FORMAT_INT, OBS_COMBO_TYPE_LIST,
+    },
+};
+
+const SETTINGS_IL_SEGMENT: *const c_char = cstr!("il_segment");
+
+/// Times a single segment of the splits as its own run. The source shows a
+/// timer of its own for it, while the timer of the whole run is kept around,
+/// so that the times can be recorded into its history.
+pub struct IndividualLevel {
+    main: SharedTimer,
+    segment: usize,
+    timer: SharedTimer,
+    tracker: EventTracker,
+}
+
+pub unsafe fn add_properties(props: *mut obs_properties_t, timer: Option<&SharedTimer>) {
+    let segments = obs_properties_add_list(
+        props,
+        SETTINGS_IL_SEGMENT,
+        cstr!("Individual Level"),
+        OBS_COMBO_TYPE_LIST,
+        OBS_COMBO_FORMAT_INT,
+    );
+    obs_property_set_long_description(
+        segments,
+        cstr!("Times only this segment, as a run of its own. Finished attempts are recorded into the segment's history and best segment."),
+    );
+    obs_property_list_add_int(segments, cstr!("Off"), -1);
+    if let Some(timer) = timer {
+        let timer = timer.read().unwrap();
+        for (index, segment) in timer.run().segments().iter().enumerate() {
+            if let Ok(name) = CString::new(format!("{}. {}", index + 1, segment.name())) {
+                obs_property_list_add_int(segments, name.as_ptr(), index as _);
+            }
+        }
+    }
+}
+
+pub unsafe fn set_defaults(settings: *mut obs_data_t) {
+    obs_data_set_default_int(settings, SETTINGS_IL_SEGMENT, -1);
+}
+
+pub unsafe fn parse_settings(settings: *mut obs_data_t) -> Option<usize> {
+    let segment = obs_data_get_int(settings, SETTINGS_IL_SEGMENT);
+    Some(segment)
+        .filter(|&segment| segment >= 0)
+        .map(|segment| segment as usize)
+}
+
+impl IndividualLevel {
+    pub fn new(main: SharedTimer, segment: usize) -> Option<Self> {
+        let run = {
+            let main = main.read().unwrap();
+            let main_run = main.run();
+            let Some(source) = main_run.segments().get(segment) else {
+                log::warn!("The splits don't have a segment {}.", segment + 1);
+                return None;
+            };
+            let mut run = Run::new();
+            run.set_game_name(main_run.game_name());
+            run.set_category_name(format!("{} ({})", main_run.category_name(), source.name()));
+            let mut level = Segment::new(source.name());
+            // The best segment is the personal best of the level.
+            level.set_personal_best_split_time(source.best_segment_time());
+            *level.best_segment_time_mut() = source.best_segment_time();
+            run.push_segment(level);
+            run
+        };
+        let timer = Timer::new(run).ok()?.into_shared();
+        let tracker = EventTracker::new(&timer.read().unwrap());
+        log::info!("Timing the individual level {}.", segment + 1);
+        Some(Self {
+            main,
+            segment,
+            timer,
+            tracker,
+        })
+    }
+
+    /// Returns whether this times the segment of the run of the timer.
+    pub fn is_of(&self, main: &SharedTimer, segment: usize) -> bool {
+        self.segment == segment && Arc::ptr_eq(&self.main, main)
+    }
+
+    /// The timer of the whole run.
+    pub fn main_timer(&self) -> &SharedTimer {
+        &self.main
+    }
+
+    /// The timer of the level, which the source shows and controls.
+    pub fn timer(&self) -> &SharedTimer {
+        &self.timer
+    }
+
+    pub fn poll(&mut self) {
+        let timer = self.timer.read().unwrap();
+        for event in self.tracker.poll(&timer) {
+            if let TimerEvent::Finished = event {
+                let time = timer.run().segment(0).split_time();
+                record(&self.main, self.segment, time);
+            }
+        }
+    }
+}
+
+/// Adds the time of the level to the history of the segment and makes it the
+/// best segment if it's faster.
+fn record(main: &SharedTimer, index: usize, time: Time) {
+    let mut main = main.write().unwrap();
+    // Changing the run would throw the attempt of the whole run away.
+    if main.current_phase() != TimerPhase::NotRunning {
+        log::warn!("The time of the individual level can't be recorded during a run.");
+        return;
+    }
+    let mut run = main.run().clone();
+    let segment = run.segment_mut(index);
+    let history_index = segment
+        .segment_history()
+        .iter()
+        .map(|&(index, _)| index)
+        .min()
+        .unwrap_or(1)
+        .min(1)
+        - 1;
+    segment.segment_history_mut().insert(history_index, time);
+    let best = segment.best_segment_time_mut();
+    let mut improved = false;
+    for method in [TimingMethod::RealTime, TimingMethod::GameTime] {
+        if let Some(time) = time[method] {
+            if best[method].map_or(true, |best| time < best) {
+                best[method] = Some(time);
+                improved = true;
+            }
+        }
+    }
+    if improved {
+        log::info!("New best segment for {}.", segment.name());
+    }
+    let _ = main.set_run(run);
+}
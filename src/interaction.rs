@@ -0,0 +1,264 @@
+use std::{
+    ffi::{CStr, CString},
+    os::raw::c_char,
+};
+
+use crate::{
+    command::Command,
+    ffi::{
+        obs_data_get_int, obs_data_get_string, obs_data_set_default_int,
+        obs_data_set_default_string, obs_data_t, obs_key_from_name, obs_key_t,
+        obs_properties_add_group, obs_properties_add_int, obs_properties_add_list,
+        obs_properties_add_text, obs_properties_create, obs_properties_t,
+        obs_property_list_add_string, obs_property_set_long_description, MOUSE_LEFT, MOUSE_RIGHT,
+        OBS_COMBO_FORMAT_STRING, OBS_COMBO_TYPE_LIST, OBS_GROUP_NORMAL, OBS_KEY_NONE,
+        OBS_TEXT_DEFAULT,
+    },
+};
+
+const SETTINGS_INTERACTION: *const c_char = cstr!("interaction");
+const SETTINGS_LEFT_CLICK: *const c_char = cstr!("interaction_left_click");
+const SETTINGS_RIGHT_CLICK: *const c_char = cstr!("interaction_right_click");
+const SETTINGS_WHEEL: *const c_char = cstr!("interaction_wheel");
+const SETTINGS_WHEEL_STEPS: *const c_char = cstr!("interaction_wheel_steps");
+const SETTINGS_KEY_SPLIT_OR_START: *const c_char = cstr!("interaction_key_split_or_start");
+const SETTINGS_KEY_RESET: *const c_char = cstr!("interaction_key_reset");
+const SETTINGS_KEY_UNDO_SPLIT: *const c_char = cstr!("interaction_key_undo_split");
+const SETTINGS_KEY_SKIP_SPLIT: *const c_char = cstr!("interaction_key_skip_split");
+const SETTINGS_KEY_TOGGLE_PAUSE_OR_START: *const c_char =
+    cstr!("interaction_key_toggle_pause_or_start");
+
+/// What a click or a key in OBS' Interact window does to the timer.
+#[derive(Copy, Clone)]
+pub enum Action {
+    Nothing,
+    SplitOrStart,
+    Reset,
+    UndoSplit,
+    SkipSplit,
+    TogglePauseOrStart,
+}
+
+const ACTIONS: [(*const c_char, *const c_char); 6] = [
+    (cstr!("Nothing"), cstr!("nothing")),
+    (cstr!("Split or Start"), cstr!("split_or_start")),
+    (cstr!("Reset"), cstr!("reset")),
+    (cstr!("Undo Split"), cstr!("undo_split")),
+    (cstr!("Skip Split"), cstr!("skip_split")),
+    (cstr!("Pause or Start"), cstr!("toggle_pause_or_start")),
+];
+
+/// The settings of the keys, with their description, their default key and
+/// their action.
+const KEYS: [(*const c_char, *const c_char, *const c_char, Action); 5] = [
+    (
+        SETTINGS_KEY_SPLIT_OR_START,
+        cstr!("Split or Start Key"),
+        cstr!("SPACE"),
+        Action::SplitOrStart,
+    ),
+    (
+        SETTINGS_KEY_RESET,
+        cstr!("Reset Key"),
+        cstr!(""),
+        Action::Reset,
+    ),
+    (
+        SETTINGS_KEY_UNDO_SPLIT,
+        cstr!("Undo Split Key"),
+        cstr!("BACKSPACE"),
+        Action::UndoSplit,
+    ),
+    (
+        SETTINGS_KEY_SKIP_SPLIT,
+        cstr!("Skip Split Key"),
+        cstr!(""),
+        Action::SkipSplit,
+    ),
+    (
+        SETTINGS_KEY_TOGGLE_PAUSE_OR_START,
+        cstr!("Pause Key"),
+        cstr!(""),
+        Action::TogglePauseOrStart,
+    ),
+];
+
+/// What turning the mouse wheel in OBS' Interact window does.
+#[derive(Copy, Clone)]
+pub enum WheelAction {
+    Scroll,
+    SwitchComparison,
+    PageSplits,
+}
+
+/// How much the wheel reports for a single notch.
+const WHEEL_NOTCH: i32 = 120;
+
+impl Action {
+    unsafe fn parse(settings: *mut obs_data_t, name: *const c_char) -> Self {
+        match CStr::from_ptr(obs_data_get_string(settings, name)).to_bytes() {
+            b"split_or_start" => Self::SplitOrStart,
+            b"reset" => Self::Reset,
+            b"undo_split" => Self::UndoSplit,
+            b"skip_split" => Self::SkipSplit,
+            b"toggle_pause_or_start" => Self::TogglePauseOrStart,
+            _ => Self::Nothing,
+        }
+    }
+
+    pub fn command(self) -> Option<Command> {
+        Some(match self {
+            Self::Nothing => return None,
+            Self::SplitOrStart => Command::SplitOrStart,
+            Self::Reset => Command::Reset { save_attempt: None },
+            Self::UndoSplit => Command::UndoSplit,
+            Self::SkipSplit => Command::SkipSplit,
+            Self::TogglePauseOrStart => Command::TogglePauseOrStart,
+        })
+    }
+}
+
+pub struct Config {
+    left_click: Action,
+    right_click: Action,
+    keys: Vec<(obs_key_t, Action)>,
+    pub wheel: WheelAction,
+    wheel_steps: i32,
+}
+
+impl Config {
+    /// Returns the action of the mouse button. The middle button isn't
+    /// configurable, as it's hard to click on many mice.
+    pub fn click(&self, button: i32) -> Action {
+        match button {
+            MOUSE_LEFT => self.left_click,
+            MOUSE_RIGHT => self.right_click,
+            _ => Action::Nothing,
+        }
+    }
+
+    /// Adds the delta of the wheel to the delta that didn't make up a whole
+    /// notch yet, and returns the amount of steps to take. Positive steps go
+    /// up.
+    pub fn wheel_steps(&self, remaining_delta: &mut i32, delta: i32) -> i32 {
+        *remaining_delta += delta;
+        let notches = *remaining_delta / WHEEL_NOTCH;
+        *remaining_delta %= WHEEL_NOTCH;
+        notches * self.wheel_steps
+    }
+
+    /// Returns the action of the key.
+    pub fn key(&self, key: obs_key_t) -> Action {
+        self.keys
+            .iter()
+            .find(|&&(k, _)| k == key)
+            .map_or(Action::Nothing, |&(_, action)| action)
+    }
+}
+
+/// Looks up a key by the name OBS gives it without the `OBS_KEY_` prefix, such
+/// as `SPACE`, `A` or `NUM1`.
+unsafe fn parse_key(name: &CStr) -> obs_key_t {
+    let name = name.to_string_lossy();
+    let name = name.trim();
+    if name.is_empty() {
+        return OBS_KEY_NONE;
+    }
+    let Ok(full_name) = CString::new(format!("OBS_KEY_{}", name.to_ascii_uppercase())) else {
+        return OBS_KEY_NONE;
+    };
+    let key = obs_key_from_name(full_name.as_ptr());
+    if key == OBS_KEY_NONE {
+        log::warn!("Invalid key: {name}");
+    }
+    key
+}
+
+unsafe fn add_action_list(
+    props: *mut obs_properties_t,
+    name: *const c_char,
+    description: *const c_char,
+) {
+    let list = obs_properties_add_list(
+        props,
+        name,
+        description,
+        OBS_COMBO_TYPE_LIST,
+        OBS_COMBO_FORMAT_STRING,
+    );
+    for (description, value) in ACTIONS {
+        obs_property_list_add_string(list, description, value);
+    }
+}
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let group = obs_properties_create();
+    add_action_list(group, SETTINGS_LEFT_CLICK, cstr!("Left Click"));
+    add_action_list(group, SETTINGS_RIGHT_CLICK, cstr!("Right Click"));
+    let wheel = obs_properties_add_list(
+        group,
+        SETTINGS_WHEEL,
+        cstr!("Mouse Wheel"),
+        OBS_COMBO_TYPE_LIST,
+        OBS_COMBO_FORMAT_STRING,
+    );
+    obs_property_list_add_string(wheel, cstr!("Scroll the Splits"), cstr!("scroll"));
+    obs_property_list_add_string(wheel, cstr!("Page the Splits"), cstr!("page"));
+    obs_property_list_add_string(wheel, cstr!("Switch Comparisons"), cstr!("comparison"));
+    obs_properties_add_int(
+        group,
+        SETTINGS_WHEEL_STEPS,
+        cstr!("Steps per Wheel Notch"),
+        1,
+        10,
+        1,
+    );
+    for (name, description, _, _) in KEYS {
+        let key = obs_properties_add_text(group, name, description, OBS_TEXT_DEFAULT);
+        obs_property_set_long_description(
+            key,
+            cstr!("The key while the Interact window is focused, e.g. SPACE, BACKSPACE, A, F1 or NUM1. Leave it empty to not use a key."),
+        );
+    }
+
+    obs_properties_add_group(
+        props,
+        SETTINGS_INTERACTION,
+        cstr!("Interaction"),
+        OBS_GROUP_NORMAL,
+        group,
+    );
+}
+
+pub unsafe fn set_defaults(settings: *mut obs_data_t) {
+    obs_data_set_default_string(settings, SETTINGS_LEFT_CLICK, cstr!("split_or_start"));
+    obs_data_set_default_string(settings, SETTINGS_RIGHT_CLICK, cstr!("reset"));
+    obs_data_set_default_string(settings, SETTINGS_WHEEL, cstr!("scroll"));
+    obs_data_set_default_int(settings, SETTINGS_WHEEL_STEPS, 1);
+    for (name, _, default, _) in KEYS {
+        obs_data_set_default_string(settings, name, default);
+    }
+}
+
+pub unsafe fn parse_settings(settings: *mut obs_data_t) -> Config {
+    Config {
+        left_click: Action::parse(settings, SETTINGS_LEFT_CLICK),
+        right_click: Action::parse(settings, SETTINGS_RIGHT_CLICK),
+        keys: KEYS
+            .iter()
+            .map(|&(name, _, _, action)| {
+                (
+                    parse_key(CStr::from_ptr(obs_data_get_string(settings, name))),
+                    action,
+                )
+            })
+            .filter(|&(key, _)| key != OBS_KEY_NONE)
+            .collect(),
+        wheel: match CStr::from_ptr(obs_data_get_string(settings, SETTINGS_WHEEL)).to_bytes() {
+            b"page" => WheelAction::PageSplits,
+            b"comparison" => WheelAction::SwitchComparison,
+            _ => WheelAction::Scroll,
+        },
+        wheel_steps: obs_data_get_int(settings, SETTINGS_WHEEL_STEPS).clamp(1, 10) as i32,
+    }
+}
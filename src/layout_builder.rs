@@ -0,0 +1,185 @@
+use std::{ffi::CStr, os::raw::c_char, ptr};
+
+use livesplit_core::{
+    component::{
+        blank_space, current_comparison, current_pace, delta, detailed_timer, graph, pb_chance,
+        possible_time_save, previous_segment, segment_time, separator, splits, sum_of_best, text,
+        timer, title, total_playtime,
+    },
+    layout::Component,
+    settings::{Color, Gradient},
+    timing::formatter::Accuracy,
+    Layout,
+};
+
+use crate::{
+    ffi::{
+        obs_data_get_int, obs_data_get_string, obs_data_set_default_int,
+        obs_data_set_default_string, obs_data_t, obs_properties_add_color_alpha,
+        obs_properties_add_editable_list, obs_properties_add_group, obs_properties_add_int,
+        obs_properties_add_list, obs_properties_create, obs_properties_t,
+        obs_property_list_add_string, OBS_COMBO_FORMAT_STRING, OBS_COMBO_TYPE_LIST,
+        OBS_EDITABLE_LIST_TYPE_STRINGS, OBS_GROUP_NORMAL,
+    },
+    get_string_list,
+};
+
+const SETTINGS_LAYOUT_BUILDER: *const c_char = cstr!("layout_builder");
+const SETTINGS_COMPONENTS: *const c_char = cstr!("layout_components");
+const SETTINGS_BACKGROUND: *const c_char = cstr!("layout_background");
+const SETTINGS_TIMER_ACCURACY: *const c_char = cstr!("layout_timer_accuracy");
+const SETTINGS_SPLITS_ROWS: *const c_char = cstr!("layout_splits_rows");
+
+const ACCURACIES: [(*const c_char, *const c_char); 4] = [
+    (cstr!("Seconds"), cstr!("seconds")),
+    (cstr!("Tenths"), cstr!("tenths")),
+    (cstr!("Hundredths"), cstr!("hundredths")),
+    (cstr!("Milliseconds"), cstr!("milliseconds")),
+];
+
+/// The components that are used when the list of components is empty. These
+/// match the default layout.
+const DEFAULT_COMPONENTS: [&str; 6] = [
+    "Title",
+    "Splits",
+    "Timer",
+    "Previous Segment",
+    "Sum of Best",
+    "Possible Time Save",
+];
+
+/// The names of all the components that can be used.
+pub const COMPONENTS: [*const c_char; 17] = [
+    cstr!("Blank Space"),
+    cstr!("Current Comparison"),
+    cstr!("Current Pace"),
+    cstr!("Delta"),
+    cstr!("Detailed Timer"),
+    cstr!("Graph"),
+    cstr!("PB Chance"),
+    cstr!("Possible Time Save"),
+    cstr!("Previous Segment"),
+    cstr!("Segment Time"),
+    cstr!("Separator"),
+    cstr!("Splits"),
+    cstr!("Sum of Best"),
+    cstr!("Text"),
+    cstr!("Timer"),
+    cstr!("Title"),
+    cstr!("Total Playtime"),
+];
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let group = obs_properties_create();
+    obs_properties_add_editable_list(
+        group,
+        SETTINGS_COMPONENTS,
+        cstr!("Components"),
+        OBS_EDITABLE_LIST_TYPE_STRINGS,
+        ptr::null(),
+        ptr::null(),
+    );
+    add_style_properties(group);
+
+    obs_properties_add_group(
+        props,
+        SETTINGS_LAYOUT_BUILDER,
+        cstr!("Layout Builder (used without a layout file)"),
+        OBS_GROUP_NORMAL,
+        group,
+    );
+}
+
+/// Adds the properties that style the components, but not the ones choosing
+/// them.
+pub unsafe fn add_style_properties(group: *mut obs_properties_t) {
+    obs_properties_add_color_alpha(group, SETTINGS_BACKGROUND, cstr!("Background"));
+    let accuracy = obs_properties_add_list(
+        group,
+        SETTINGS_TIMER_ACCURACY,
+        cstr!("Timer Accuracy"),
+        OBS_COMBO_TYPE_LIST,
+        OBS_COMBO_FORMAT_STRING,
+    );
+    for (name, value) in ACCURACIES {
+        obs_property_list_add_string(accuracy, name, value);
+    }
+    obs_properties_add_int(group, SETTINGS_SPLITS_ROWS, cstr!("Splits Rows"), 1, 100, 1);
+}
+
+pub unsafe fn set_defaults(settings: *mut obs_data_t) {
+    obs_data_set_default_int(settings, SETTINGS_BACKGROUND, 0xFF0F0F0F);
+    obs_data_set_default_string(settings, SETTINGS_TIMER_ACCURACY, cstr!("hundredths"));
+    obs_data_set_default_int(settings, SETTINGS_SPLITS_ROWS, 16);
+}
+
+/// Builds a layout out of the settings, for users without a layout file.
+pub unsafe fn build(settings: *mut obs_data_t) -> Layout {
+    let mut components = get_string_list(settings, SETTINGS_COMPONENTS);
+    if components.is_empty() {
+        components = DEFAULT_COMPONENTS.iter().map(|&c| c.to_owned()).collect();
+    }
+    build_with(settings, &components)
+}
+
+/// Builds a layout with the given components, styled by the settings.
+pub unsafe fn build_with(settings: *mut obs_data_t, components: &[String]) -> Layout {
+    let accuracy =
+        match CStr::from_ptr(obs_data_get_string(settings, SETTINGS_TIMER_ACCURACY)).to_bytes() {
+            b"seconds" => Accuracy::Seconds,
+            b"tenths" => Accuracy::Tenths,
+            b"milliseconds" => Accuracy::Milliseconds,
+            _ => Accuracy::Hundredths,
+        };
+    let splits_rows = obs_data_get_int(settings, SETTINGS_SPLITS_ROWS).max(1) as usize;
+
+    let mut layout = Layout::new();
+    for name in components {
+        match component(name, accuracy, splits_rows) {
+            Some(component) => layout.push(component),
+            None => log::warn!("Unknown layout component: {name}"),
+        }
+    }
+
+    // OBS stores colors as 0xAABBGGRR.
+    let [r, g, b, a] = (obs_data_get_int(settings, SETTINGS_BACKGROUND) as u32).to_le_bytes();
+    layout.general_settings_mut().background = Gradient::Plain(Color::rgba(
+        r as f32 / 255.0,
+        g as f32 / 255.0,
+        b as f32 / 255.0,
+        a as f32 / 255.0,
+    ));
+
+    layout
+}
+
+fn component(name: &str, accuracy: Accuracy, splits_rows: usize) -> Option<Component> {
+    Some(match name.trim().to_ascii_lowercase().as_str() {
+        "blank space" => blank_space::Component::new().into(),
+        "current comparison" => current_comparison::Component::new().into(),
+        "current pace" => current_pace::Component::new().into(),
+        "delta" => delta::Component::new().into(),
+        "detailed timer" => detailed_timer::Component::new().into(),
+        "graph" => graph::Component::new().into(),
+        "pb chance" => pb_chance::Component::new().into(),
+        "possible time save" => possible_time_save::Component::new().into(),
+        "previous segment" => previous_segment::Component::new().into(),
+        "segment time" => segment_time::Component::new().into(),
+        "separator" => separator::Component::new().into(),
+        "splits" => splits::Component::with_settings(splits::Settings {
+            visual_split_count: splits_rows,
+            ..Default::default()
+        })
+        .into(),
+        "sum of best" => sum_of_best::Component::new().into(),
+        "text" => text::Component::new().into(),
+        "timer" => timer::Component::with_settings(timer::Settings {
+            accuracy,
+            ..Default::default()
+        })
+        .into(),
+        "title" => title::Component::new().into(),
+        "total playtime" => total_playtime::Component::new().into(),
+        _ => return None,
+    })
+}
@@ -1,55 +1,169 @@
 use core::fmt;
 use std::{
-    cmp::Ordering,
-    ffi::{c_void, CStr},
+    collections::hash_map::DefaultHasher,
+    ffi::{c_void, CStr, CString},
     fs::{self, File},
+    hash::{Hash, Hasher},
     io::{BufWriter, Cursor},
     mem,
     os::raw::{c_char, c_int},
     path::{Path, PathBuf},
     ptr,
-    sync::{Arc, Mutex, RwLock, Weak},
+    sync::{mpsc, Arc, Mutex, RwLock, Weak},
+    time::{Duration, Instant},
 };
 
+macro_rules! cstr {
+    ($f:literal) => {
+        concat!($f, '\0').as_ptr().cast()
+    };
+}
+
+mod attempt_history;
+mod audio_cues;
+#[cfg(feature = "auto-splitting")]
+mod auto_splitter_list;
+#[cfg(feature = "auto-splitting")]
+mod auto_splitter_loader;
+#[cfg(feature = "auto-splitting")]
+mod auto_splitter_log;
+#[cfg(feature = "auto-splitting")]
+mod auto_splitter_settings;
+#[cfg(feature = "auto-splitting")]
+mod auto_splitter_status;
+mod backup;
+mod calldata;
+mod command;
+mod component;
+mod controller;
+mod custom_comparison;
+mod custom_variables;
+mod death_counter;
+mod discord;
+mod events;
 mod ffi;
 mod ffi_types;
-
+mod fonts;
+mod frontend;
+#[cfg(feature = "auto-splitting")]
+mod game_detection;
+#[cfg(feature = "gamepad")]
+mod gamepad;
+mod generated_comparisons;
+mod history_export;
+mod http;
+mod individual_level;
+mod interaction;
+mod layout_builder;
+mod media_controls;
+#[cfg(feature = "midi")]
+mod midi;
+mod missing_files;
+mod mqtt;
+mod new_splits;
+mod obs_websocket;
+mod osc;
+mod path;
+mod playlist;
+mod practice;
+mod procs;
+mod recording;
+mod recovery;
+mod relay;
+mod remote_splits;
+mod render_thread;
+mod run_editor;
+mod run_maintenance;
+mod run_manager;
+mod run_summary;
+mod saved_attempt;
+mod scene_switcher;
+mod server;
+mod signals;
+mod speedrun_com;
+mod splits_statistics;
+mod state_export;
+mod summary_image;
+mod sync;
+mod therun;
+mod twitch;
+mod watcher;
+mod webhook;
+
+use audio_cues::AudioCues;
+#[cfg(feature = "auto-splitting")]
+use auto_splitter_loader::Loader;
+use command::{Command, ResetPolicy};
+use death_counter::DeathCounter;
+use discord::RichPresence;
 use ffi::{
-    blog, gs_draw_sprite, gs_effect_get_param_by_name, gs_effect_get_technique,
-    gs_effect_set_texture, gs_effect_t, gs_technique_begin, gs_technique_begin_pass,
-    gs_technique_end, gs_technique_end_pass, gs_texture_create, gs_texture_destroy,
-    gs_texture_set_image, gs_texture_t, obs_data_get_int, obs_data_get_string,
-    obs_data_set_default_int, obs_data_t, obs_enter_graphics, obs_get_base_effect, obs_hotkey_id,
-    obs_hotkey_register_source, obs_hotkey_t, obs_leave_graphics, obs_module_t, obs_mouse_event,
-    obs_properties_add_button, obs_properties_add_int, obs_properties_add_path,
-    obs_properties_create, obs_properties_t, obs_property_t, obs_register_source_s,
-    obs_source_info, obs_source_t, GS_DYNAMIC, GS_RGBA, LOG_WARNING,
-    OBS_EFFECT_PREMULTIPLIED_ALPHA, OBS_ICON_TYPE_GAME_CAPTURE, OBS_PATH_FILE,
+    bfree, blog, gs_color_space, gs_draw_sprite, gs_effect_get_param_by_name,
+    gs_effect_get_technique, gs_effect_set_texture, gs_effect_set_texture_srgb, gs_effect_t,
+    gs_enable_framebuffer_srgb, gs_framebuffer_srgb_enabled, gs_technique_begin,
+    gs_technique_begin_pass, gs_technique_end, gs_technique_end_pass, gs_texture_create,
+    gs_texture_destroy, gs_texture_set_image, gs_texture_t, obs_data_array_count,
+    obs_data_array_item, obs_data_array_release, obs_data_get_array, obs_data_get_bool,
+    obs_data_get_int, obs_data_get_string, obs_data_release, obs_data_set_default_bool,
+    obs_data_set_default_int, obs_data_set_default_string, obs_data_t, obs_enter_graphics,
+    obs_get_base_effect, obs_get_version, obs_hotkey_id, obs_hotkey_register_source, obs_hotkey_t,
+    obs_hotkey_unregister, obs_key_event, obs_key_from_virtual_key, obs_key_t, obs_leave_graphics,
+    obs_module_get_config_path, obs_module_t, obs_mouse_event, obs_properties_add_bool,
+    obs_properties_add_button, obs_properties_add_editable_list, obs_properties_add_int,
+    obs_properties_add_int_slider, obs_properties_add_list, obs_properties_add_path,
+    obs_properties_add_text, obs_properties_create, obs_properties_t, obs_property_list_add_string,
+    obs_property_set_long_description, obs_property_t, obs_register_source_s,
+    obs_source_get_settings, obs_source_info, obs_source_set_audio_active, obs_source_t,
+    obs_source_update_properties, size_t, GS_CS_SRGB, GS_DYNAMIC, GS_RGBA, LOG_WARNING,
+    OBS_COMBO_FORMAT_STRING, OBS_COMBO_TYPE_LIST, OBS_EDITABLE_LIST_TYPE_STRINGS,
+    OBS_EFFECT_PREMULTIPLIED_ALPHA, OBS_ICON_TYPE_GAME_CAPTURE, OBS_PATH_FILE, OBS_SOURCE_AUDIO,
     OBS_SOURCE_CONTROLLABLE_MEDIA, OBS_SOURCE_CUSTOM_DRAW, OBS_SOURCE_INTERACTION,
-    OBS_SOURCE_TYPE_INPUT, OBS_SOURCE_VIDEO,
+    OBS_SOURCE_TYPE_INPUT, OBS_SOURCE_VIDEO, OBS_TEXT_DEFAULT, OBS_TEXT_MULTILINE,
+    OBS_TEXT_PASSWORD,
 };
 use ffi_types::{
     obs_media_state, LOG_DEBUG, LOG_ERROR, LOG_INFO, OBS_MEDIA_STATE_ENDED, OBS_MEDIA_STATE_PAUSED,
     OBS_MEDIA_STATE_PLAYING, OBS_MEDIA_STATE_STOPPED,
 };
 #[cfg(feature = "auto-splitting")]
+use game_detection::GameDetection;
+#[cfg(feature = "gamepad")]
+use gamepad::Gamepad;
+use individual_level::IndividualLevel;
+use interaction::WheelAction;
+#[cfg(feature = "auto-splitting")]
 use livesplit_core::auto_splitting;
 use livesplit_core::{
-    layout::{self, LayoutSettings, LayoutState},
-    rendering::software::Renderer,
+    hotkey::{Hook, Hotkey},
+    layout::{self, ComponentState, LayoutSettings, LayoutState},
     run::{
         parser::{composite, TimerKind},
-        saver::livesplit::{save_timer, IoWrite},
+        saver::livesplit::{save_run, IoWrite},
     },
-    Layout, Run, Segment, SharedTimer, Timer, TimerPhase,
+    HotkeyConfig, HotkeySystem, Layout, Run, Segment, SharedTimer, TimeSpan, Timer, TimerPhase,
+    TimingMethod,
 };
 use log::{Level, LevelFilter, Log, Metadata, Record};
-
-macro_rules! cstr {
-    ($f:literal) => {
-        concat!($f, '\0').as_ptr().cast()
-    };
-}
+#[cfg(feature = "midi")]
+use midi::Midi;
+use mqtt::Mqtt;
+use osc::Osc;
+use playlist::Playlist;
+use recording::Recorder;
+use recovery::Recovery;
+use relay::Relay;
+use render_thread::RenderThread;
+use saved_attempt::SavedAttempt;
+use scene_switcher::SceneSwitcher;
+use serde_json::Value;
+use server::Server;
+use signals::Signals;
+use state_export::StateExport;
+use summary_image::SummaryImage;
+use sync::Mirror;
+use therun::TheRun;
+use twitch::StreamMarkers;
+use watcher::FileWatcher;
+use webhook::Webhook;
 
 static mut OBS_MODULE_POINTER: *mut obs_module_t = ptr::null_mut();
 
@@ -65,36 +179,177 @@ pub extern "C" fn obs_module_ver() -> u32 {
     (26 << 24) | (1 << 16) | 1
 }
 
+/// The versions of OBS that added `missing_files` and `video_get_color_space`
+/// to the end of `obs_source_info`.
+const OBS_27: u32 = 27 << 24;
+const OBS_28: u32 = 28 << 24;
+
+/// Registers the source with OBS, which copies the info. Older versions of OBS
+/// refuse infos that are larger than their own, so the callbacks they don't
+/// know about are cut off.
+unsafe fn register_source(info: &obs_source_info) {
+    let info = *info;
+    let offset = |field: *const u8| field as usize - ptr::addr_of!(info) as usize;
+    let version = obs_get_version();
+    let size = if version < OBS_27 {
+        offset(ptr::addr_of!(info.missing_files).cast())
+    } else if version < OBS_28 {
+        offset(ptr::addr_of!(info.video_get_color_space).cast())
+    } else {
+        mem::size_of_val(&info)
+    };
+    obs_register_source_s(&info, size as _);
+}
+
 struct UnsafeMultiThread<T>(T);
 
 unsafe impl<T> Sync for UnsafeMultiThread<T> {}
 unsafe impl<T> Send for UnsafeMultiThread<T> {}
 
-static TIMERS: Mutex<Vec<(PathBuf, Weak<RwLock<Timer>>)>> = Mutex::new(Vec::new());
+static TIMERS: Mutex<Vec<(PathBuf, String, Weak<RwLock<Timer>>)>> = Mutex::new(Vec::new());
+
+/// The largest width or height that the layout is rendered at. Larger textures
+/// take a lot of memory and time to render, if the GPU supports them at all.
+const MAX_RENDER_SIZE: u32 = 8192;
 
 struct State {
+    source: *mut obs_source_t,
     timer: SharedTimer,
     splits_path: PathBuf,
     can_save_splits: bool,
+    backup_count: usize,
+    splits_watcher: Option<FileWatcher>,
+    splits_reload_pending: bool,
+    #[cfg(feature = "auto-splitting")]
+    auto_splitter: Arc<auto_splitting::Runtime>,
+    #[cfg(feature = "auto-splitting")]
+    auto_splitter_loader: Loader,
+    #[cfg(feature = "auto-splitting")]
+    auto_splitter_path: PathBuf,
+    #[cfg(feature = "auto-splitting")]
+    auto_splitter_watcher: Option<FileWatcher>,
     #[cfg(feature = "auto-splitting")]
-    auto_splitter: auto_splitting::Runtime,
+    auto_splitter_enabled: bool,
+    #[cfg(feature = "auto-splitting")]
+    auto_splitter_settings_pending: bool,
+    #[cfg(feature = "auto-splitting")]
+    auto_splitter_error: Option<String>,
+    #[cfg(feature = "auto-splitting")]
+    auto_splitter_downloads: auto_splitter_list::Downloads,
+    #[cfg(feature = "auto-splitting")]
+    game_detection: Option<GameDetection>,
     layout: Layout,
+    layout_from_file: bool,
+    layout_path: PathBuf,
+    layout_watcher: Option<FileWatcher>,
+    main_layout_path: PathBuf,
+    alternate_layout_path: PathBuf,
+    alternate_layout_active: bool,
     state: LayoutState,
-    renderer: Renderer,
+    rendered_state: Option<u64>,
+    render_thread: RenderThread,
     texture: *mut gs_texture_t,
     width: u32,
     height: u32,
+    auto_size: bool,
+    render_scale: u32,
+    opacity: f32,
+    update_interval: Option<Duration>,
+    last_update: Instant,
+    update_pending: bool,
+    srgb: bool,
+    server: Option<Server>,
+    mirror: Option<Mirror>,
+    global_hotkeys: Option<GlobalHotkeys>,
+    therun: Option<TheRun>,
+    stream_markers: Option<StreamMarkers>,
+    webhook: Option<Webhook>,
+    mqtt: Option<Mqtt>,
+    osc: Option<Osc>,
+    #[cfg(feature = "midi")]
+    midi: Option<Midi>,
+    #[cfg(feature = "gamepad")]
+    gamepad: Option<Gamepad>,
+    rich_presence: Option<RichPresence>,
+    scene_switcher: Option<SceneSwitcher>,
+    recorder: Option<Recorder>,
+    state_export: Option<StateExport>,
+    summary_image: Option<SummaryImage>,
+    audio_cues: Option<AudioCues>,
+    playlist: Option<Playlist>,
+    relay: Option<Relay>,
+    custom_variables: Vec<(String, String)>,
+    individual_level: Option<IndividualLevel>,
+    speedrun_com_lookup: Option<mpsc::Receiver<Result<speedrun_com::Lookup, String>>>,
+    practice: practice::Config,
+    death_counter: DeathCounter,
+    media_controls: media_controls::Config,
+    interaction: interaction::Config,
+    held_keys: Vec<obs_key_t>,
+    wheel_delta: i32,
+    recovery: Option<Recovery>,
+    signals: Signals,
+    restored_offset: Option<TimeSpan>,
+    pending_comparison: Option<custom_comparison::PendingComparison>,
+    hotkeys: Vec<obs_hotkey_id>,
+    jump_hotkeys: Vec<obs_hotkey_id>,
+    comparison_hotkeys: Vec<(obs_hotkey_id, String)>,
 }
 
+/// The settings of the source. The splits and the layout files aren't parsed
+/// here, so that they are only parsed again when they change.
 struct Settings {
-    run: Run,
     splits_path: PathBuf,
-    can_save_splits: bool,
-    layout: Layout,
+    timer_group: String,
+    backup_count: usize,
+    built_layout: Layout,
+    layout_path: PathBuf,
+    alternate_layout_path: PathBuf,
+    fonts_folder: PathBuf,
+    #[cfg(feature = "auto-splitting")]
+    auto_splitter_path: PathBuf,
     #[cfg(feature = "auto-splitting")]
-    auto_splitter_path: String,
+    game_detection: Option<Vec<game_detection::Game>>,
+    #[cfg(feature = "auto-splitting")]
+    auto_splitter_log_level: LevelFilter,
     width: u32,
     height: u32,
+    auto_size: bool,
+    render_scale: u32,
+    opacity: f32,
+    update_interval: Option<Duration>,
+    srgb: bool,
+    server: Option<server::Config>,
+    sync_address: Option<String>,
+    global_hotkeys: Option<HotkeyConfig>,
+    comparison: String,
+    hidden_comparisons: Vec<&'static str>,
+    timing_method: TimingMethod,
+    reset_policy: ResetPolicy,
+    therun_upload_key: Option<String>,
+    twitch_credentials: Option<twitch::Credentials>,
+    webhook: Option<webhook::Config>,
+    mqtt: Option<mqtt::Config>,
+    osc: Option<osc::Config>,
+    #[cfg(feature = "midi")]
+    midi: Option<midi::Config>,
+    #[cfg(feature = "gamepad")]
+    gamepad: Option<gamepad::Config>,
+    discord_application_id: Option<String>,
+    scene_switcher: Option<scene_switcher::Config>,
+    recording: Option<recording::Config>,
+    state_export: Option<state_export::Config>,
+    summary_image: Option<summary_image::Config>,
+    audio_cues: Option<audio_cues::Config>,
+    playlist: Option<playlist::Config>,
+    relay: Option<relay::Config>,
+    custom_variables: Vec<(String, String)>,
+    individual_level: Option<usize>,
+    practice: practice::Config,
+    death_counter: death_counter::Config,
+    media_controls: media_controls::Config,
+    interaction: interaction::Config,
+    recovery_interval: Option<Duration>,
 }
 
 fn parse_run(path: &Path) -> Option<(Run, bool)> {
@@ -119,9 +374,17 @@ fn log(level: Level, target: &str, args: &fmt::Arguments<'_>) {
     }
 }
 
-fn parse_layout(path: &CStr) -> Option<Layout> {
-    let path = path.to_str().ok()?;
-    if path.is_empty() {
+/// Loads the layout from the file, or falls back to the layout that is built
+/// from the settings. Also returns whether the layout came from the file.
+fn load_layout(path: &Path, built_layout: Layout) -> (Layout, bool) {
+    match parse_layout(path) {
+        Some(layout) => (layout, true),
+        None => (built_layout, false),
+    }
+}
+
+fn parse_layout(path: &Path) -> Option<Layout> {
+    if path.as_os_str().is_empty() {
         return None;
     }
     let file_data = fs::read_to_string(path).ok()?;
@@ -134,122 +397,1169 @@ fn parse_layout(path: &CStr) -> Option<Layout> {
 }
 
 unsafe fn parse_settings(settings: *mut obs_data_t) -> Settings {
-    let splits_path = CStr::from_ptr(obs_data_get_string(settings, SETTINGS_SPLITS_PATH).cast());
-    let splits_path = PathBuf::from(splits_path.to_string_lossy().into_owned());
-    let (run, can_save_splits) = parse_run(&splits_path).unwrap_or_else(default_run);
+    let splits_url = remote_splits::get_url(settings);
+    let splits_path = if splits_url.is_empty() {
+        path::from_obs(obs_data_get_string(settings, SETTINGS_SPLITS_PATH))
+    } else {
+        remote_splits::cached_splits(&splits_url)
+    };
+    let timer_group = get_timer_group(settings);
+    let backup_count = backup::parse_settings(settings);
 
-    let layout_path = CStr::from_ptr(obs_data_get_string(settings, SETTINGS_LAYOUT_PATH).cast());
-    let layout = parse_layout(layout_path).unwrap_or_else(Layout::default_layout);
+    let layout_path = path::from_obs(obs_data_get_string(settings, SETTINGS_LAYOUT_PATH));
+    let alternate_layout_path = path::from_obs(obs_data_get_string(
+        settings,
+        SETTINGS_ALTERNATE_LAYOUT_PATH,
+    ));
+    let built_layout = layout_builder::build(settings);
+    let fonts_folder = fonts::parse_settings(settings);
 
     #[cfg(feature = "auto-splitting")]
-    let auto_splitter_path = CStr::from_ptr(obs_data_get_string(
-        settings,
-        SETTINGS_AUTO_SPLITTER_PATH.cast(),
-    ))
-    .to_str()
-    .unwrap_or_default()
-    .to_owned();
+    let auto_splitter_path =
+        path::from_obs(obs_data_get_string(settings, SETTINGS_AUTO_SPLITTER_PATH));
+    #[cfg(feature = "auto-splitting")]
+    let game_detection = game_detection::parse_settings(settings);
+    #[cfg(feature = "auto-splitting")]
+    let auto_splitter_log_level = auto_splitter_log::parse_settings(settings);
 
     let width = obs_data_get_int(settings, SETTINGS_WIDTH) as u32;
     let height = obs_data_get_int(settings, SETTINGS_HEIGHT) as u32;
+    let auto_size = obs_data_get_bool(settings, SETTINGS_AUTO_SIZE);
+    let render_scale = obs_data_get_int(settings, SETTINGS_RENDER_SCALE).clamp(1, 4) as u32;
+    let opacity = obs_data_get_int(settings, SETTINGS_OPACITY).clamp(0, 100) as f32 / 100.0;
+    let srgb = obs_data_get_bool(settings, SETTINGS_SRGB);
+    let update_fps = obs_data_get_int(settings, SETTINGS_UPDATE_FPS);
+    let update_interval = if update_fps > 0 {
+        Some(Duration::from_secs_f64(1.0 / update_fps as f64))
+    } else {
+        None
+    };
+
+    let server = if obs_data_get_bool(settings, SETTINGS_SERVER_ENABLED) {
+        Some(server::Config {
+            port: obs_data_get_int(settings, SETTINGS_SERVER_PORT) as u16,
+            lan: obs_data_get_bool(settings, SETTINGS_SERVER_LAN),
+            token: CStr::from_ptr(obs_data_get_string(settings, SETTINGS_SERVER_TOKEN))
+                .to_string_lossy()
+                .trim()
+                .to_owned(),
+            allowed_origins: get_string_list(settings, SETTINGS_SERVER_ALLOWED_ORIGINS)
+                .into_iter()
+                .map(|origin| origin.trim_end_matches('/').to_owned())
+                .collect(),
+        })
+    } else {
+        None
+    };
+
+    let sync_address = sync::parse_settings(settings);
+
+    let global_hotkeys = if obs_data_get_bool(settings, SETTINGS_GLOBAL_HOTKEYS) {
+        let hotkey = |name| parse_hotkey(CStr::from_ptr(obs_data_get_string(settings, name)));
+        let mut config = HotkeyConfig::default();
+        config.split = hotkey(SETTINGS_GLOBAL_HOTKEY_SPLIT);
+        config.reset = hotkey(SETTINGS_GLOBAL_HOTKEY_RESET);
+        config.undo = hotkey(SETTINGS_GLOBAL_HOTKEY_UNDO);
+        config.skip = hotkey(SETTINGS_GLOBAL_HOTKEY_SKIP);
+        config.pause = hotkey(SETTINGS_GLOBAL_HOTKEY_PAUSE);
+        config.undo_all_pauses = hotkey(SETTINGS_GLOBAL_HOTKEY_UNDO_ALL_PAUSES);
+        config.previous_comparison = hotkey(SETTINGS_GLOBAL_HOTKEY_PREVIOUS_COMPARISON);
+        config.next_comparison = hotkey(SETTINGS_GLOBAL_HOTKEY_NEXT_COMPARISON);
+        config.toggle_timing_method = hotkey(SETTINGS_GLOBAL_HOTKEY_TOGGLE_TIMING_METHOD);
+        Some(config)
+    } else {
+        None
+    };
+
+    let comparison = CStr::from_ptr(obs_data_get_string(settings, SETTINGS_COMPARISON))
+        .to_string_lossy()
+        .into_owned();
+    let hidden_comparisons = generated_comparisons::parse_settings(settings);
+
+    let timing_method =
+        match CStr::from_ptr(obs_data_get_string(settings, SETTINGS_TIMING_METHOD)).to_bytes() {
+            b"game_time" => TimingMethod::GameTime,
+            _ => TimingMethod::RealTime,
+        };
+
+    let reset_policy =
+        match CStr::from_ptr(obs_data_get_string(settings, SETTINGS_RESET_POLICY)).to_bytes() {
+            b"never" => ResetPolicy::Never,
+            b"ask" => ResetPolicy::Ask,
+            _ => ResetPolicy::Always,
+        };
+
+    let therun_upload_key = CStr::from_ptr(obs_data_get_string(settings, SETTINGS_THERUN_KEY))
+        .to_string_lossy()
+        .trim()
+        .to_owned();
+    let therun_upload_key = Some(therun_upload_key).filter(|key| !key.is_empty());
+
+    let get_trimmed = |name| {
+        CStr::from_ptr(obs_data_get_string(settings, name))
+            .to_string_lossy()
+            .trim()
+            .to_owned()
+    };
+    let twitch_client_id = get_trimmed(SETTINGS_TWITCH_CLIENT_ID);
+    let twitch_token = get_trimmed(SETTINGS_TWITCH_TOKEN);
+    let twitch_credentials = if !twitch_client_id.is_empty() && !twitch_token.is_empty() {
+        Some(twitch::Credentials {
+            client_id: twitch_client_id,
+            token: twitch_token,
+        })
+    } else {
+        None
+    };
+
+    let webhook_url = get_trimmed(SETTINGS_WEBHOOK_URL);
+    let webhook = if !webhook_url.is_empty() {
+        Some(webhook::Config {
+            url: webhook_url,
+            template: CStr::from_ptr(obs_data_get_string(settings, SETTINGS_WEBHOOK_TEMPLATE))
+                .to_string_lossy()
+                .into_owned(),
+        })
+    } else {
+        None
+    };
+
+    let discord_application_id =
+        Some(get_trimmed(SETTINGS_DISCORD_APPLICATION_ID)).filter(|id| !id.is_empty());
+    let mqtt = mqtt::parse_settings(settings);
+    let osc = osc::parse_settings(settings);
+    #[cfg(feature = "midi")]
+    let midi = midi::parse_settings(settings);
+    #[cfg(feature = "gamepad")]
+    let gamepad = gamepad::parse_settings(settings);
+
+    let scene_switcher = scene_switcher::parse_settings(settings);
+    let recording = recording::parse_settings(settings);
+    let state_export = state_export::parse_settings(settings);
+    let summary_image = summary_image::parse_settings(settings);
+    let audio_cues = audio_cues::parse_settings(settings);
+    let playlist = playlist::parse_settings(settings);
+    let relay = relay::parse_settings(settings);
+    let custom_variables = custom_variables::parse_settings(settings);
+    let individual_level = individual_level::parse_settings(settings);
+    let practice = practice::parse_settings(settings);
+    let death_counter = death_counter::parse_settings(settings);
+    let media_controls = media_controls::parse_settings(settings);
+    let interaction = interaction::parse_settings(settings);
+    let recovery_interval = recovery::parse_settings(settings);
 
     Settings {
-        run,
         splits_path,
-        can_save_splits,
-        layout,
+        timer_group,
+        backup_count,
+        built_layout,
+        layout_path,
+        alternate_layout_path,
+        fonts_folder,
         #[cfg(feature = "auto-splitting")]
         auto_splitter_path,
+        #[cfg(feature = "auto-splitting")]
+        game_detection,
+        #[cfg(feature = "auto-splitting")]
+        auto_splitter_log_level,
         width,
         height,
+        auto_size,
+        render_scale,
+        opacity,
+        update_interval,
+        srgb,
+        server,
+        sync_address,
+        global_hotkeys,
+        comparison,
+        hidden_comparisons,
+        timing_method,
+        reset_policy,
+        therun_upload_key,
+        twitch_credentials,
+        webhook,
+        mqtt,
+        osc,
+        #[cfg(feature = "midi")]
+        midi,
+        #[cfg(feature = "gamepad")]
+        gamepad,
+        discord_application_id,
+        scene_switcher,
+        recording,
+        state_export,
+        summary_image,
+        audio_cues,
+        playlist,
+        relay,
+        custom_variables,
+        individual_level,
+        practice,
+        death_counter,
+        media_controls,
+        interaction,
+        recovery_interval,
+    }
+}
+
+fn configure_timer(timer: &SharedTimer, comparison: &str, timing_method: TimingMethod) {
+    let mut timer = timer.write().unwrap();
+    if !comparison.is_empty() && timer.set_current_comparison(comparison).is_err() {
+        log::warn!("The comparison {comparison} doesn't exist.");
+    }
+    timer.set_current_timing_method(timing_method);
+}
+
+unsafe fn get_list<T>(
+    settings: *mut obs_data_t,
+    name: *const c_char,
+    parse: impl Fn(*const c_char) -> T,
+) -> Vec<T> {
+    let array = obs_data_get_array(settings, name);
+    if array.is_null() {
+        return Vec::new();
+    }
+    let list = (0..obs_data_array_count(array))
+        .map(|i| {
+            let item = obs_data_array_item(array, i);
+            let value = parse(obs_data_get_string(item, cstr!("value")));
+            obs_data_release(item);
+            value
+        })
+        .collect();
+    obs_data_array_release(array);
+    list
+}
+
+unsafe fn get_string_list(settings: *mut obs_data_t, name: *const c_char) -> Vec<String> {
+    get_list(settings, name, |value| {
+        CStr::from_ptr(value).to_string_lossy().into_owned()
+    })
+}
+
+/// Returns the paths in the list, converted the same way as the path of the
+/// splits, so paths that aren't valid Unicode still work.
+unsafe fn get_path_list(settings: *mut obs_data_t, name: *const c_char) -> Vec<PathBuf> {
+    get_list(settings, name, |value| path::from_obs(value))
+}
+
+/// Resolves the path of the splits, so that different spellings of the same
+/// file, like relative paths, symbolic links or a different case on Windows,
+/// share the same timer.
+fn canonical_path(splits_path: &Path) -> PathBuf {
+    fs::canonicalize(splits_path).unwrap_or_else(|_| splits_path.to_owned())
+}
+
+/// Returns the timer that is shared by all the sources with the same splits,
+/// along with the path of its splits. If there is none yet, a new one is
+/// created with the run. Sources in a timer group share the timer of the group
+/// instead, whichever splits they chose. The splits of a group are the ones of
+/// the source that created its timer, so all of its sources save to and reload
+/// from those.
+fn shared_timer(
+    splits_path: &Path,
+    timer_group: &str,
+    run: impl FnOnce() -> Run,
+) -> (SharedTimer, PathBuf) {
+    let splits_path = canonical_path(splits_path);
+    let mut timers = TIMERS.lock().unwrap();
+    timers.retain(|(_, _, timer)| timer.strong_count() > 0);
+    if let Some((timer, path)) = timers.iter().find_map(|(path, group, timer)| {
+        let shared = if timer_group.is_empty() {
+            group.is_empty() && *path == splits_path
+        } else {
+            group == timer_group
+        };
+        Some((timer.upgrade()?, path.clone())).filter(|_| shared)
+    }) {
+        log::debug!("Found timer to reuse.");
+        if path != splits_path {
+            log::info!(
+                "The timer group {timer_group} uses the splits {}.",
+                path.display()
+            );
+        }
+        (timer, path)
+    } else {
+        log::debug!("Storing timer for reuse.");
+        let timer = Timer::new(run()).unwrap().into_shared();
+        timers.push((
+            splits_path.clone(),
+            timer_group.to_owned(),
+            Arc::downgrade(&timer),
+        ));
+        (timer, splits_path)
+    }
+}
+
+/// Returns whether the splits can be saved without losing anything, which is
+/// only the case for LiveSplit's own format.
+fn can_save_splits(splits_path: &Path) -> bool {
+    parse_run(splits_path).map_or(false, |(_, can_save_splits)| can_save_splits)
+}
+
+/// Returns the shared timer for the splits chosen in the settings of a source
+/// that doesn't own the timer itself.
+unsafe fn splits_timer(settings: *mut obs_data_t) -> SharedTimer {
+    let splits_path = path::from_obs(obs_data_get_string(settings, SETTINGS_SPLITS_PATH));
+    shared_timer(&splits_path, &get_timer_group(settings), || {
+        parse_run(&splits_path).unwrap_or_else(default_run).0
+    })
+    .0
+}
+
+unsafe fn get_timer_group(settings: *mut obs_data_t) -> String {
+    CStr::from_ptr(obs_data_get_string(settings, SETTINGS_TIMER_GROUP))
+        .to_string_lossy()
+        .trim()
+        .to_owned()
+}
+
+unsafe fn add_timer_group_property(props: *mut obs_properties_t) {
+    let group = obs_properties_add_text(
+        props,
+        SETTINGS_TIMER_GROUP,
+        cstr!("Timer Group"),
+        OBS_TEXT_DEFAULT,
+    );
+    obs_property_set_long_description(
+        group,
+        cstr!("Sources with the same timer group share one timer, whichever splits they chose, apart from the sources that aren't in the group. The timer uses the splits of the first source of the group. Leave this empty to share the timer between the sources with the same splits."),
+    );
+}
+
+/// Resolves a file in OBS' config directory for this plugin.
+unsafe fn module_config_path(file: &str) -> Option<PathBuf> {
+    let file = CString::new(file).ok()?;
+    let path = obs_module_get_config_path(OBS_MODULE_POINTER, file.as_ptr());
+    if path.is_null() {
+        return None;
+    }
+    let path_buf = path::from_obs(path);
+    bfree(path.cast());
+    Some(path_buf)
+}
+
+fn parse_hotkey(hotkey: &CStr) -> Option<Hotkey> {
+    let hotkey = hotkey.to_str().ok()?.trim();
+    if hotkey.is_empty() {
+        return None;
+    }
+    hotkey
+        .parse()
+        .map_err(|_| log::warn!("Invalid global hotkey: {hotkey}"))
+        .ok()
+}
+
+/// The global hotkeys. Resetting and switching comparisons follow the policy
+/// of the timer, so their hotkeys are registered on their own rather than
+/// with the hotkey system, which controls the timer directly.
+struct GlobalHotkeys {
+    _hotkey_system: HotkeySystem,
+    _hook: Hook,
+}
+
+fn start_global_hotkeys(timer: SharedTimer, mut config: HotkeyConfig) -> Option<GlobalHotkeys> {
+    let commands: [(Option<Hotkey>, fn() -> Command); 3] = [
+        (config.reset.take(), || Command::Reset {
+            save_attempt: None,
+        }),
+        (config.previous_comparison.take(), || {
+            Command::SwitchToPreviousComparison
+        }),
+        (config.next_comparison.take(), || {
+            Command::SwitchToNextComparison
+        }),
+    ];
+    let result = Hook::new().and_then(|hook| {
+        for (hotkey, command) in commands {
+            if let Some(hotkey) = hotkey {
+                let timer = timer.clone();
+                hook.register(hotkey, move || {
+                    let _ = command::execute(command(), &timer);
+                })?;
+            }
+        }
+        Ok(GlobalHotkeys {
+            _hotkey_system: HotkeySystem::with_config(timer, config)?,
+            _hook: hook,
+        })
+    });
+    match result {
+        Ok(global_hotkeys) => Some(global_hotkeys),
+        Err(e) => {
+            log::error!("Failed to register the global hotkeys: {e}");
+            None
+        }
+    }
+}
+
+fn start_mirror(address: String) -> Option<Mirror> {
+    match Mirror::start(address) {
+        Ok(mirror) => Some(mirror),
+        Err(e) => {
+            log::error!("Failed to mirror the timer: {e}");
+            None
+        }
+    }
+}
+
+fn start_osc(config: osc::Config, timer: &Timer) -> Option<Osc> {
+    match Osc::new(config, timer) {
+        Ok(osc) => Some(osc),
+        Err(e) => {
+            log::error!("Failed to set up OSC: {e}");
+            None
+        }
+    }
+}
+
+fn start_server(config: server::Config, timer: SharedTimer) -> Option<Server> {
+    let port = config.port;
+    match Server::start(config, timer) {
+        Ok(server) => Some(server),
+        Err(e) => {
+            log::error!("Failed to start the server on port {port}: {e}");
+            None
+        }
     }
 }
 
 impl State {
     unsafe fn new(
+        source: *mut obs_source_t,
         Settings {
-            run,
             splits_path,
-            can_save_splits,
-            layout,
+            timer_group,
+            backup_count,
+            built_layout,
+            layout_path,
+            alternate_layout_path,
+            fonts_folder,
             #[cfg(feature = "auto-splitting")]
             auto_splitter_path,
+            #[cfg(feature = "auto-splitting")]
+            game_detection,
+            #[cfg(feature = "auto-splitting")]
+            auto_splitter_log_level,
             width,
             height,
+            auto_size,
+            render_scale,
+            opacity,
+            update_interval,
+            srgb,
+            server,
+            sync_address,
+            global_hotkeys,
+            comparison,
+            hidden_comparisons,
+            timing_method,
+            reset_policy,
+            therun_upload_key,
+            twitch_credentials,
+            webhook,
+            mqtt,
+            osc,
+            #[cfg(feature = "midi")]
+            midi,
+            #[cfg(feature = "gamepad")]
+            gamepad,
+            discord_application_id,
+            scene_switcher,
+            recording,
+            state_export,
+            summary_image,
+            audio_cues,
+            playlist,
+            relay,
+            custom_variables,
+            individual_level,
+            practice,
+            death_counter,
+            media_controls,
+            interaction,
+            recovery_interval,
         }: Settings,
     ) -> Self {
         log::info!("Loading settings.");
 
-        let timer = {
-            let mut timers = TIMERS.lock().unwrap();
-            timers.retain(|(_, timer)| timer.strong_count() > 0);
-            if let Some(timer) = timers.iter().find_map(|(path, timer)| {
-                if path == &splits_path {
-                    timer.upgrade()
-                } else {
-                    None
-                }
-            }) {
-                log::debug!("Found timer to reuse.");
-                timer
-            } else {
-                log::debug!("Storing timer for reuse.");
-                let timer = Timer::new(run).unwrap().into_shared();
-                timers.push((splits_path.clone(), Arc::downgrade(&timer)));
-                timer
-            }
-        };
+        let mut can_save = None;
+        let (timer, splits_path) = shared_timer(&splits_path, &timer_group, || {
+            let (run, can_save_splits) = parse_run(&splits_path).unwrap_or_else(default_run);
+            can_save = Some(can_save_splits);
+            run
+        });
+        let can_save_splits = can_save.unwrap_or_else(|| can_save_splits(&splits_path));
+        let individual_level =
+            individual_level.and_then(|segment| IndividualLevel::new(timer.clone(), segment));
+        // The source shows and controls the timer of the level instead.
+        let timer = individual_level
+            .as_ref()
+            .map_or(timer, |level| level.timer().clone());
+        let (layout, layout_from_file) = load_layout(&layout_path, built_layout);
+
+        configure_timer(&timer, &comparison, timing_method);
+        command::policy(&timer)
+            .lock()
+            .unwrap()
+            .configure(reset_policy, hidden_comparisons);
 
         #[cfg(feature = "auto-splitting")]
-        let auto_splitter = auto_splitting::Runtime::new(timer.clone());
+        auto_splitter_log::set_level(source, auto_splitter_log_level);
+        #[cfg(feature = "auto-splitting")]
+        let auto_splitter = Arc::new(auto_splitting::Runtime::new(timer.clone()));
+        #[cfg(feature = "auto-splitting")]
+        let mut auto_splitter_loader = Loader::new(auto_splitter.clone());
         #[cfg(feature = "auto-splitting")]
-        if !auto_splitter_path.is_empty() {
-            auto_splitter
-                .load_script_blocking(PathBuf::from(auto_splitter_path))
-                .ok();
+        if !auto_splitter_path.as_os_str().is_empty() {
+            auto_splitter_loader.load(auto_splitter_path.clone());
         }
+        #[cfg(feature = "auto-splitting")]
+        let auto_splitter_watcher = FileWatcher::new(&auto_splitter_path);
+        #[cfg(feature = "auto-splitting")]
+        let game_detection = game_detection.map(GameDetection::new);
 
         let state = LayoutState::default();
-        let renderer = Renderer::new();
+        // The fonts need to be loaded before the renderer is created.
+        fonts::load_folder(&fonts_folder);
+        let render_thread = RenderThread::new();
 
         obs_enter_graphics();
-        let texture = gs_texture_create(width, height, GS_RGBA, 1, ptr::null_mut(), GS_DYNAMIC);
+        let texture = create_render_texture(width, height, render_scale);
         obs_leave_graphics();
 
+        let server = server.and_then(|config| start_server(config, timer.clone()));
+        let mirror = sync_address.and_then(start_mirror);
+
+        let global_hotkeys =
+            global_hotkeys.and_then(|config| start_global_hotkeys(timer.clone(), config));
+
+        let therun = therun_upload_key.and_then(|key| TheRun::new(key, &timer.read().unwrap()));
+        let stream_markers = twitch_credentials
+            .and_then(|credentials| StreamMarkers::new(credentials, &timer.read().unwrap()));
+        let webhook = webhook.and_then(|config| Webhook::new(config, &timer.read().unwrap()));
+        let mqtt = mqtt.and_then(|config| Mqtt::new(config, &timer.read().unwrap()));
+        let osc = osc.and_then(|config| start_osc(config, &timer.read().unwrap()));
+        #[cfg(feature = "midi")]
+        let midi = midi.and_then(Midi::new);
+        #[cfg(feature = "gamepad")]
+        let gamepad = gamepad.and_then(Gamepad::new);
+        let rich_presence =
+            discord_application_id.and_then(|id| RichPresence::new(id, &timer.read().unwrap()));
+        let scene_switcher =
+            scene_switcher.map(|config| SceneSwitcher::new(config, &timer.read().unwrap()));
+        let recorder = recording.map(|config| Recorder::new(config, &timer.read().unwrap()));
+        let state_export = state_export.map(StateExport::new);
+        let summary_image =
+            summary_image.map(|config| SummaryImage::new(config, &timer.read().unwrap()));
+        let audio_cues = audio_cues.map(|config| AudioCues::new(config, &timer.read().unwrap()));
+        let playlist = playlist.map(|config| Playlist::new(config, &timer.read().unwrap()));
+        let relay = relay.map(|config| Relay::new(config, &mut timer.write().unwrap()));
+        let death_counter = DeathCounter::new(death_counter, &timer.read().unwrap());
+        custom_variables::apply(&mut timer.write().unwrap(), &[], &custom_variables);
+        let recovery = recovery_interval.and_then(|interval| Recovery::new(interval, &splits_path));
+        let signals = Signals::new(&timer.read().unwrap());
+
+        let splits_watcher = FileWatcher::new(&splits_path);
+        let layout_watcher = FileWatcher::new(&layout_path);
+
         Self {
+            source,
             timer,
             splits_path,
             can_save_splits,
+            backup_count,
+            splits_watcher,
+            splits_reload_pending: false,
             layout,
+            layout_from_file,
+            main_layout_path: layout_path.clone(),
+            layout_path,
+            layout_watcher,
+            alternate_layout_path,
+            alternate_layout_active: false,
             #[cfg(feature = "auto-splitting")]
             auto_splitter,
+            #[cfg(feature = "auto-splitting")]
+            auto_splitter_loader,
+            #[cfg(feature = "auto-splitting")]
+            auto_splitter_path,
+            #[cfg(feature = "auto-splitting")]
+            auto_splitter_watcher,
+            #[cfg(feature = "auto-splitting")]
+            auto_splitter_enabled: true,
+            #[cfg(feature = "auto-splitting")]
+            auto_splitter_settings_pending: false,
+            #[cfg(feature = "auto-splitting")]
+            auto_splitter_error: None,
+            #[cfg(feature = "auto-splitting")]
+            auto_splitter_downloads: Default::default(),
+            #[cfg(feature = "auto-splitting")]
+            game_detection,
             state,
-            renderer,
+            rendered_state: None,
+            render_thread,
             texture,
             width,
             height,
+            auto_size,
+            render_scale,
+            opacity,
+            update_interval,
+            last_update: Instant::now(),
+            update_pending: true,
+            srgb,
+            server,
+            mirror,
+            global_hotkeys,
+            therun,
+            stream_markers,
+            webhook,
+            mqtt,
+            osc,
+            #[cfg(feature = "midi")]
+            midi,
+            #[cfg(feature = "gamepad")]
+            gamepad,
+            rich_presence,
+            scene_switcher,
+            recorder,
+            state_export,
+            summary_image,
+            audio_cues,
+            playlist,
+            relay,
+            custom_variables,
+            individual_level,
+            speedrun_com_lookup: None,
+            practice,
+            death_counter,
+            media_controls,
+            interaction,
+            held_keys: Vec::new(),
+            wheel_delta: 0,
+            recovery,
+            signals,
+            restored_offset: None,
+            pending_comparison: None,
+            hotkeys: Vec::new(),
+            jump_hotkeys: Vec::new(),
+            comparison_hotkeys: Vec::new(),
+        }
+    }
+
+    fn reload_splits_if_changed(&mut self) {
+        if let Some(watcher) = &mut self.splits_watcher {
+            if watcher.poll() {
+                log::info!("Splits changed on disk.");
+                self.splits_reload_pending = true;
+            }
+        }
+
+        if !self.splits_reload_pending {
+            return;
+        }
+
+        let main_timer = self.main_timer().clone();
+        let mut timer = main_timer.write().unwrap();
+        // Reloading in the middle of an attempt would throw it away, so we
+        // wait for the timer to be reset first.
+        if timer.current_phase() != TimerPhase::NotRunning {
+            return;
+        }
+        self.splits_reload_pending = false;
+
+        if let Some((run, can_save_splits)) = parse_run(&self.splits_path) {
+            if timer.set_run(run).is_ok() {
+                log::info!("Reloaded splits.");
+                self.can_save_splits = can_save_splits;
+                drop(timer);
+                // The statistics in the properties come from the splits.
+                unsafe { obs_source_update_properties(self.source) };
+            }
+        } else {
+            log::warn!("Failed to reload splits.");
+        }
+    }
+
+    fn reload_layout_if_changed(&mut self) {
+        let Some(watcher) = &mut self.layout_watcher else {
+            return;
+        };
+        if !watcher.poll() {
+            return;
+        }
+        if let Some(layout) = parse_layout(&self.layout_path) {
+            log::info!("Reloaded layout.");
+            self.layout = layout;
+            self.layout_from_file = true;
+        } else {
+            log::warn!("Failed to reload layout.");
+        }
+    }
+
+    /// Shows the layout of the file, or the layout that is built from the
+    /// settings if there is none. A layout file is only parsed again when a
+    /// different one was chosen. A layout built from the settings is always
+    /// rebuilt, as the settings it is built from may have changed.
+    fn show_layout(&mut self, path: PathBuf, built_layout: Layout) {
+        if self.layout_path != path || !self.layout_from_file {
+            let (layout, layout_from_file) = load_layout(&path, built_layout);
+            self.layout = layout;
+            self.layout_from_file = layout_from_file;
+        }
+        if self.layout_path != path {
+            self.layout_watcher = FileWatcher::new(&path);
+        }
+        self.layout_path = path;
+    }
+
+    /// The path of the layout that is chosen to be shown, which is the
+    /// alternate one while it's switched to.
+    fn chosen_layout_path(&self) -> &Path {
+        if self.alternate_layout_active && !self.alternate_layout_path.as_os_str().is_empty() {
+            &self.alternate_layout_path
+        } else {
+            &self.main_layout_path
+        }
+    }
+
+    /// Switches between the main and the alternate layout. The timer and the
+    /// size of the source stay the same.
+    unsafe fn toggle_layout(&mut self) {
+        if self.alternate_layout_path.as_os_str().is_empty() {
+            return;
+        }
+        self.alternate_layout_active = !self.alternate_layout_active;
+        let settings = obs_source_get_settings(self.source);
+        let built_layout = layout_builder::build(settings);
+        obs_data_release(settings);
+        self.show_layout(self.chosen_layout_path().to_owned(), built_layout);
+    }
+
+    #[cfg(feature = "auto-splitting")]
+    fn reload_auto_splitter_if_changed(&mut self) {
+        let Some(watcher) = &mut self.auto_splitter_watcher else {
+            return;
+        };
+        if !watcher.poll() || !self.auto_splitter_enabled {
+            return;
+        }
+        self.load_auto_splitter();
+    }
+
+    /// Starts loading the chosen auto splitter, replacing the one that is
+    /// running. Without a path, this only unloads it.
+    #[cfg(feature = "auto-splitting")]
+    fn load_auto_splitter(&mut self) {
+        self.auto_splitter_error = None;
+        self.auto_splitter_loader
+            .load(self.auto_splitter_path.clone());
+    }
+
+    #[cfg(feature = "auto-splitting")]
+    fn unload_auto_splitter(&mut self) {
+        self.auto_splitter_loader.load(PathBuf::new());
+    }
+
+    /// Binds the auto splitter to the timer of the source. A runtime stays
+    /// bound to the timer it was created with, so a new one is created and the
+    /// auto splitter is loaded into it again.
+    #[cfg(feature = "auto-splitting")]
+    fn bind_auto_splitter(&mut self) {
+        self.auto_splitter = Arc::new(auto_splitting::Runtime::new(self.timer.clone()));
+        self.auto_splitter_loader = Loader::new(self.auto_splitter.clone());
+        if self.auto_splitter_enabled && !self.auto_splitter_path.as_os_str().is_empty() {
+            self.load_auto_splitter();
+        }
+        self.auto_splitter_settings_pending = true;
+    }
+
+    /// Takes over the auto splitter once it's loaded. Why it failed is kept,
+    /// so it can be shown in the properties.
+    #[cfg(feature = "auto-splitting")]
+    unsafe fn finish_loading_auto_splitter(&mut self) {
+        let Some(result) = self.auto_splitter_loader.poll() else {
+            return;
+        };
+        match result {
+            Ok(true) => {
+                log::info!("Loaded auto splitter.");
+                self.auto_splitter_settings_pending = true;
+            }
+            Ok(false) => {}
+            Err(e) => {
+                log::warn!("Failed to load the auto splitter: {e}");
+                self.auto_splitter_error = Some(e);
+            }
+        }
+        // The status and the settings of the auto splitter are part of the
+        // properties.
+        obs_source_update_properties(self.source);
+    }
+
+    /// Chooses the auto splitter and the splits of a game that was just
+    /// started. The splits are left alone while a run is in progress.
+    #[cfg(feature = "auto-splitting")]
+    unsafe fn switch_game(&mut self) {
+        let Some(game) = self
+            .game_detection
+            .as_ref()
+            .and_then(GameDetection::poll)
+            .cloned()
+        else {
+            return;
+        };
+        let splits = game.splits.filter(|splits| {
+            canonical_path(splits) != self.splits_path
+                && self.timer.read().unwrap().current_phase() == TimerPhase::NotRunning
+        });
+        if game.auto_splitter == self.auto_splitter_path && splits.is_none() {
+            return;
+        }
+        log::info!("Detected {}.", game.process);
+        if splits.is_some() {
+            self.save_splits();
+        }
+        game_detection::switch_to(self.source, &game.auto_splitter, splits.as_deref());
+    }
+
+    #[cfg(feature = "auto-splitting")]
+    fn toggle_auto_splitter(&mut self) {
+        self.auto_splitter_enabled = !self.auto_splitter_enabled;
+        if self.auto_splitter_enabled {
+            self.load_auto_splitter();
+            log::info!("Enabled auto splitter.");
+        } else {
+            self.unload_auto_splitter();
+            log::info!("Disabled auto splitter.");
+        }
+    }
+
+    /// The auto splitter declares its settings while it starts up, so they
+    /// can only be applied once that happened.
+    #[cfg(feature = "auto-splitting")]
+    unsafe fn apply_auto_splitter_settings(&mut self) {
+        if !self.auto_splitter_settings_pending {
+            return;
+        }
+        let settings = obs_source_get_settings(self.source);
+        if auto_splitter_settings::apply(&self.auto_splitter, settings) {
+            self.auto_splitter_settings_pending = false;
+        }
+        obs_data_release(settings);
+    }
+
+    /// Executes a command on the timer. All the ways of controlling the timer
+    /// of the source go through here, so that they always reach the timer that
+    /// the source currently uses and follow its settings.
+    fn execute(&mut self, command: Command) -> Result<Value, command::Error> {
+        command::execute(command, &self.timer)
+    }
+
+    /// Resets the timer without updating the splits if a requested reset
+    /// wasn't confirmed in time.
+    fn finish_unconfirmed_reset(&mut self) {
+        let policy = command::policy(&self.timer);
+        let mut policy = policy.lock().unwrap();
+        policy.finish_unconfirmed_reset(&mut self.timer.write().unwrap());
+    }
+
+    /// Saves the attempt as the comparison that is named in the settings.
+    unsafe fn save_comparison(&mut self) {
+        let settings = obs_source_get_settings(self.source);
+        let name = custom_comparison::parse_name(settings);
+        obs_data_release(settings);
+        if name.is_empty() {
+            log::warn!("Choose a name for the comparison first.");
+            return;
+        }
+        self.pending_comparison =
+            custom_comparison::capture(name, &self.main_timer().read().unwrap());
+        if self.pending_comparison.is_none() {
+            log::warn!("There's no attempt to save as a comparison.");
+            return;
+        }
+        self.save_pending_comparison();
+        if self.pending_comparison.is_some() {
+            log::info!("The attempt is saved as a comparison once it's over.");
+        }
+    }
+
+    /// The run can't be changed while the timer is running, so an attempt in
+    /// progress is only saved as a comparison once the timer is reset.
+    unsafe fn save_pending_comparison(&mut self) {
+        // This runs on every tick, so the timer isn't locked unless there's
+        // something to save.
+        if self.pending_comparison.is_none() {
+            return;
+        }
+        let main_timer = self.main_timer().clone();
+        {
+            let mut timer = main_timer.write().unwrap();
+            if timer.current_phase() != TimerPhase::NotRunning {
+                return;
+            }
+            let Some(pending) = self.pending_comparison.take() else {
+                return;
+            };
+            let mut run = timer.run().clone();
+            if !pending.apply(&mut run) || timer.set_run(run).is_err() {
+                return;
+            }
+        }
+        self.save_splits();
+    }
+
+    fn reset_scroll(&mut self) {
+        // Recreating the layout from its own settings is the simplest way to
+        // get rid of the scroll offsets of all of its components.
+        self.layout = Layout::from_settings(self.layout.settings());
+    }
+
+    /// Pauses the game time, which is how loads are removed by hand. The game
+    /// time is initialized first, as it only shows up once it is.
+    fn pause_game_time(&mut self) {
+        let _ = self.execute(Command::InitializeGameTime);
+        let _ = self.execute(Command::PauseGameTime);
+    }
+
+    /// Moves the timer to the time. The game time can simply be set, but the
+    /// real time can only be changed by restoring the attempt at the new time,
+    /// which loses the split times, so it's only done while the timer is
+    /// paused or practicing.
+    fn seek(&mut self, time: TimeSpan) {
+        let policy = command::policy(&self.timer);
+        let policy = policy.lock().unwrap();
+        let mut timer = self.timer.write().unwrap();
+        let phase = timer.current_phase();
+        if phase != TimerPhase::Running && phase != TimerPhase::Paused {
+            return;
+        }
+        if timer.current_timing_method() == TimingMethod::GameTime {
+            timer.initialize_game_time();
+            timer.set_game_time(time);
+            return;
+        }
+        if phase != TimerPhase::Paused && !policy.practicing {
+            log::warn!("Pause the timer to change its time.");
+            return;
+        }
+        let Some(mut attempt) = SavedAttempt::capture(&timer) else {
+            return;
+        };
+        attempt.set_real_time(time);
+        timer.reset(false);
+        if let Some(offset) = attempt.restore(&mut timer) {
+            self.restored_offset.get_or_insert(offset);
+        }
+    }
+
+    /// Starts the timer at the segment that is practiced. A practice attempt
+    /// that is in progress starts over, but a real one is left alone.
+    fn start_practice(&mut self) {
+        let policy = command::policy(&self.timer);
+        let mut policy = policy.lock().unwrap();
+        let mut timer = self.timer.write().unwrap();
+        if timer.current_phase() != TimerPhase::NotRunning {
+            if !policy.practicing {
+                log::warn!("Reset the timer before practicing.");
+                return;
+            }
+            timer.reset(false);
+            saved_attempt::restore_offset(&mut timer, &mut self.restored_offset);
+        }
+        if let Some(offset) = practice::start(&mut timer, &self.practice) {
+            self.restored_offset.get_or_insert(offset);
+            policy.practicing = true;
         }
     }
 
+    /// Saves the splits and switches to the run that is `offset` runs away in
+    /// the playlist.
+    unsafe fn switch_run(&mut self, offset: isize) {
+        let Some(splits_path) = self
+            .playlist
+            .as_ref()
+            .and_then(|playlist| playlist.neighbor(&self.splits_path, offset))
+            .map(Path::to_owned)
+        else {
+            return;
+        };
+        self.save_splits();
+        // The splits are only switched once OBS applies the settings. Until
+        // then the auto splitter would keep controlling the finished run, like
+        // starting it again when the game already starts the next one. It's
+        // loaded again for the timer of the next run.
+        #[cfg(feature = "auto-splitting")]
+        self.unload_auto_splitter();
+        playlist::switch_to(self.source, &splits_path);
+    }
+
+    /// Returns the timer of the splits. It's only a different one than the
+    /// source's timer while an individual level is timed.
+    fn main_timer(&self) -> &SharedTimer {
+        self.individual_level
+            .as_ref()
+            .map_or(&self.timer, IndividualLevel::main_timer)
+    }
+
+    unsafe fn save_splits(&mut self) {
+        if self.can_save_splits {
+            let mut run = self.main_timer().read().unwrap().clone().into_run(true);
+            // The offset was only moved to restore an attempt, so the splits
+            // keep the one from before.
+            if let (None, Some(offset)) = (&self.individual_level, self.restored_offset) {
+                run.set_offset(offset);
+            }
+            backup::backup(&self.splits_path, self.backup_count);
+            if let Ok(file) = File::create(&self.splits_path) {
+                let _ = save_run(&run, IoWrite(BufWriter::new(file)));
+            }
+            watcher::mark_unchanged(&self.splits_path);
+        }
+    }
+
+    /// Requests the layout to be rendered again in the next frame, but no more
+    /// often than the update rate that is configured.
+    fn schedule_update(&mut self) {
+        if let Some(interval) = self.update_interval {
+            if self.last_update.elapsed() < interval {
+                return;
+            }
+        }
+        self.last_update = Instant::now();
+        self.update_pending = true;
+    }
+
     unsafe fn update(&mut self) {
         self.layout
             .update_state(&mut self.state, &self.timer.read().unwrap().snapshot());
 
-        self.renderer.render(&self.state, [self.width, self.height]);
-        gs_texture_set_image(
-            self.texture,
-            self.renderer.image_data().as_ptr(),
-            self.width * 4,
-            false,
+        // The texture still shows the state if nothing changed, which is the
+        // case most of the time while the timer isn't running.
+        let state_hash = hash_layout_state(&self.state);
+        if self.rendered_state == Some(state_hash) {
+            return;
+        }
+        self.rendered_state = Some(state_hash);
+
+        self.render_thread
+            .request(&self.state, self.render_size(), self.opacity);
+    }
+
+    /// The size that the layout is rendered at. Rendering at a larger scale
+    /// keeps the layout sharp when the source is scaled up in the scene.
+    fn render_size(&self) -> [u32; 2] {
+        render_size(self.width, self.height, self.render_scale)
+    }
+
+    /// Uploads the frame that the render thread finished last.
+    unsafe fn upload_frame(&mut self) {
+        let Some(frame) = self.render_thread.take_frame() else {
+            return;
+        };
+        // Frames that were rendered before the source got resized don't fit
+        // into the texture.
+        if frame.size == self.render_size() {
+            gs_texture_set_image(self.texture, frame.image.as_ptr(), frame.size[0] * 4, false);
+        }
+        if let Some(server) = &self.server {
+            server.show_frame(&frame.image, frame.size);
+        }
+
+        // The layout asks for a different size when its contents don't fit,
+        // like when splits were added to a vertical layout.
+        if let (true, Some([width, height])) = (self.auto_size, frame.resize) {
+            // The scale is smaller than configured if the size got limited.
+            let scale = self.render_size()[0] as f32 / self.width as f32;
+            let width = (width / scale).round().max(1.0) as u32;
+            let height = (height / scale).round().max(1.0) as u32;
+            if [width, height] != [self.width, self.height] {
+                self.width = width;
+                self.height = height;
+                self.recreate_texture();
+            }
+        }
+
+        self.render_thread.recycle(frame);
+    }
+
+    /// Creates the texture for the current size. This needs to happen within
+    /// the graphics context.
+    unsafe fn recreate_texture(&mut self) {
+        let mut texture = create_render_texture(self.width, self.height, self.render_scale);
+        mem::swap(&mut self.texture, &mut texture);
+        gs_texture_destroy(texture);
+        self.rendered_state = None;
+        self.update_pending = true;
+    }
+}
+
+/// Scales the size of the source by the render scale, but scales it down again
+/// if it would be larger than what can be rendered.
+fn render_size(width: u32, height: u32, scale: u32) -> [u32; 2] {
+    let size = [width * scale, height * scale];
+    let largest = size[0].max(size[1]);
+    if largest <= MAX_RENDER_SIZE {
+        return size;
+    }
+    let factor = MAX_RENDER_SIZE as f64 / largest as f64;
+    size.map(|side| ((side as f64 * factor).round() as u32).max(1))
+}
+
+/// Creates the texture that the layout is rendered into. This needs to happen
+/// within the graphics context.
+unsafe fn create_render_texture(width: u32, height: u32, scale: u32) -> *mut gs_texture_t {
+    let [render_width, render_height] = render_size(width, height, scale);
+    if [render_width, render_height] != [width * scale, height * scale] {
+        log::warn!(
+            "The source is too large to be rendered at its full size, so it's rendered at {render_width}x{render_height}."
         );
     }
+    gs_texture_create(
+        render_width,
+        render_height,
+        GS_RGBA,
+        1,
+        ptr::null_mut(),
+        GS_DYNAMIC,
+    )
 }
 
 unsafe extern "C" fn get_name(_: *mut c_void) -> *const c_char {
     cstr!("LiveSplit One")
 }
 
-unsafe extern "C" fn split(
+macro_rules! hotkey {
+    ($name:ident, $command:expr) => {
+        unsafe extern "C" fn $name(
+            data: *mut c_void,
+            _: obs_hotkey_id,
+            _: *mut obs_hotkey_t,
+            pressed: bool,
+        ) {
+            let state: &mut State = &mut *data.cast();
+            if pressed && state.global_hotkeys.is_none() {
+                let _ = state.execute($command);
+            }
+        }
+    };
+}
+
+hotkey!(split, Command::SplitOrStart);
+hotkey!(reset, Command::Reset { save_attempt: None });
+hotkey!(undo, Command::UndoSplit);
+hotkey!(skip, Command::SkipSplit);
+hotkey!(pause, Command::TogglePauseOrStart);
+hotkey!(undo_all_pauses, Command::UndoAllPauses);
+hotkey!(previous_comparison, Command::SwitchToPreviousComparison);
+hotkey!(next_comparison, Command::SwitchToNextComparison);
+hotkey!(toggle_timing_method, Command::ToggleTimingMethod);
+
+unsafe extern "C" fn pause_game_time(
     data: *mut c_void,
     _: obs_hotkey_id,
     _: *mut obs_hotkey_t,
@@ -257,11 +1567,11 @@ unsafe extern "C" fn split(
 ) {
     if pressed {
         let state: &mut State = &mut *data.cast();
-        state.timer.write().unwrap().split_or_start();
+        state.pause_game_time();
     }
 }
 
-unsafe extern "C" fn reset(
+unsafe extern "C" fn resume_game_time(
     data: *mut c_void,
     _: obs_hotkey_id,
     _: *mut obs_hotkey_t,
@@ -269,11 +1579,11 @@ unsafe extern "C" fn reset(
 ) {
     if pressed {
         let state: &mut State = &mut *data.cast();
-        state.timer.write().unwrap().reset(true);
+        let _ = state.execute(Command::ResumeGameTime);
     }
 }
 
-unsafe extern "C" fn undo(
+unsafe extern "C" fn save_comparison(
     data: *mut c_void,
     _: obs_hotkey_id,
     _: *mut obs_hotkey_t,
@@ -281,11 +1591,11 @@ unsafe extern "C" fn undo(
 ) {
     if pressed {
         let state: &mut State = &mut *data.cast();
-        state.timer.write().unwrap().undo_split();
+        state.save_comparison();
     }
 }
 
-unsafe extern "C" fn skip(
+unsafe extern "C" fn save_splits_hotkey(
     data: *mut c_void,
     _: obs_hotkey_id,
     _: *mut obs_hotkey_t,
@@ -293,11 +1603,12 @@ unsafe extern "C" fn skip(
 ) {
     if pressed {
         let state: &mut State = &mut *data.cast();
-        state.timer.write().unwrap().skip_split();
+        state.save_splits();
     }
 }
 
-unsafe extern "C" fn pause(
+#[cfg(feature = "auto-splitting")]
+unsafe extern "C" fn toggle_auto_splitter(
     data: *mut c_void,
     _: obs_hotkey_id,
     _: *mut obs_hotkey_t,
@@ -305,11 +1616,11 @@ unsafe extern "C" fn pause(
 ) {
     if pressed {
         let state: &mut State = &mut *data.cast();
-        state.timer.write().unwrap().toggle_pause_or_start();
+        state.toggle_auto_splitter();
     }
 }
 
-unsafe extern "C" fn undo_all_pauses(
+unsafe extern "C" fn next_run(
     data: *mut c_void,
     _: obs_hotkey_id,
     _: *mut obs_hotkey_t,
@@ -317,11 +1628,11 @@ unsafe extern "C" fn undo_all_pauses(
 ) {
     if pressed {
         let state: &mut State = &mut *data.cast();
-        state.timer.write().unwrap().undo_all_pauses();
+        state.switch_run(1);
     }
 }
 
-unsafe extern "C" fn previous_comparison(
+unsafe extern "C" fn previous_run(
     data: *mut c_void,
     _: obs_hotkey_id,
     _: *mut obs_hotkey_t,
@@ -329,11 +1640,11 @@ unsafe extern "C" fn previous_comparison(
 ) {
     if pressed {
         let state: &mut State = &mut *data.cast();
-        state.timer.write().unwrap().switch_to_previous_comparison();
+        state.switch_run(-1);
     }
 }
 
-unsafe extern "C" fn next_comparison(
+unsafe extern "C" fn hand_off(
     data: *mut c_void,
     _: obs_hotkey_id,
     _: *mut obs_hotkey_t,
@@ -341,11 +1652,13 @@ unsafe extern "C" fn next_comparison(
 ) {
     if pressed {
         let state: &mut State = &mut *data.cast();
-        state.timer.write().unwrap().switch_to_next_comparison();
+        if let Some(relay) = &mut state.relay {
+            relay.hand_off(&mut state.timer.write().unwrap());
+        }
     }
 }
 
-unsafe extern "C" fn toggle_timing_method(
+unsafe extern "C" fn start_practice(
     data: *mut c_void,
     _: obs_hotkey_id,
     _: *mut obs_hotkey_t,
@@ -353,90 +1666,386 @@ unsafe extern "C" fn toggle_timing_method(
 ) {
     if pressed {
         let state: &mut State = &mut *data.cast();
-        state.timer.write().unwrap().toggle_timing_method();
+        state.start_practice();
     }
 }
 
-unsafe extern "C" fn create(settings: *mut obs_data_t, source: *mut obs_source_t) -> *mut c_void {
-    let data = Box::into_raw(Box::new(State::new(parse_settings(settings)))).cast();
+unsafe extern "C" fn increment_deaths(
+    data: *mut c_void,
+    _: obs_hotkey_id,
+    _: *mut obs_hotkey_t,
+    pressed: bool,
+) {
+    if pressed {
+        let state: &mut State = &mut *data.cast();
+        state
+            .death_counter
+            .increment(&mut state.timer.write().unwrap());
+    }
+}
 
-    obs_hotkey_register_source(
-        source,
-        cstr!("hotkey_split"),
-        cstr!("Split"),
-        Some(split),
-        data,
-    );
+unsafe extern "C" fn decrement_deaths(
+    data: *mut c_void,
+    _: obs_hotkey_id,
+    _: *mut obs_hotkey_t,
+    pressed: bool,
+) {
+    if pressed {
+        let state: &mut State = &mut *data.cast();
+        state
+            .death_counter
+            .decrement(&mut state.timer.write().unwrap());
+    }
+}
 
-    obs_hotkey_register_source(
-        source,
-        cstr!("hotkey_reset"),
-        cstr!("Reset"),
-        Some(reset),
-        data,
-    );
+unsafe extern "C" fn reset_deaths(
+    data: *mut c_void,
+    _: obs_hotkey_id,
+    _: *mut obs_hotkey_t,
+    pressed: bool,
+) {
+    if pressed {
+        let state: &mut State = &mut *data.cast();
+        state.death_counter.reset(&mut state.timer.write().unwrap());
+    }
+}
 
-    obs_hotkey_register_source(
-        source,
-        cstr!("hotkey_undo"),
-        cstr!("Undo Split"),
-        Some(undo),
-        data,
-    );
+unsafe extern "C" fn increase_offset(
+    data: *mut c_void,
+    _: obs_hotkey_id,
+    _: *mut obs_hotkey_t,
+    pressed: bool,
+) {
+    if pressed {
+        let state: &mut State = &mut *data.cast();
+        run_editor::shift_offset(state, 1.0);
+    }
+}
 
-    obs_hotkey_register_source(
-        source,
-        cstr!("hotkey_skip"),
-        cstr!("Skip Split"),
-        Some(skip),
-        data,
-    );
+unsafe extern "C" fn decrease_offset(
+    data: *mut c_void,
+    _: obs_hotkey_id,
+    _: *mut obs_hotkey_t,
+    pressed: bool,
+) {
+    if pressed {
+        let state: &mut State = &mut *data.cast();
+        run_editor::shift_offset(state, -1.0);
+    }
+}
 
-    obs_hotkey_register_source(
-        source,
-        cstr!("hotkey_pause"),
-        cstr!("Pause"),
-        Some(pause),
-        data,
-    );
+unsafe extern "C" fn toggle_layout(
+    data: *mut c_void,
+    _: obs_hotkey_id,
+    _: *mut obs_hotkey_t,
+    pressed: bool,
+) {
+    if pressed {
+        let state: &mut State = &mut *data.cast();
+        state.toggle_layout();
+    }
+}
 
-    obs_hotkey_register_source(
-        source,
+unsafe extern "C" fn scroll_up(
+    data: *mut c_void,
+    _: obs_hotkey_id,
+    _: *mut obs_hotkey_t,
+    pressed: bool,
+) {
+    if pressed {
+        let state: &mut State = &mut *data.cast();
+        state.layout.scroll_up();
+    }
+}
+
+unsafe extern "C" fn scroll_down(
+    data: *mut c_void,
+    _: obs_hotkey_id,
+    _: *mut obs_hotkey_t,
+    pressed: bool,
+) {
+    if pressed {
+        let state: &mut State = &mut *data.cast();
+        state.layout.scroll_down();
+    }
+}
+
+unsafe extern "C" fn reset_scroll(
+    data: *mut c_void,
+    _: obs_hotkey_id,
+    _: *mut obs_hotkey_t,
+    pressed: bool,
+) {
+    if pressed {
+        let state: &mut State = &mut *data.cast();
+        state.reset_scroll();
+    }
+}
+
+unsafe extern "C" fn jump_to_split(
+    data: *mut c_void,
+    id: obs_hotkey_id,
+    _: *mut obs_hotkey_t,
+    pressed: bool,
+) {
+    // The global hotkeys have no equivalent, so these stay active like the
+    // comparison hotkeys.
+    let state: &mut State = &mut *data.cast();
+    if pressed {
+        if let Some(index) = state.jump_hotkeys.iter().position(|&hotkey| hotkey == id) {
+            let _ = state.execute(Command::JumpToSplit { index });
+        }
+    }
+}
+
+unsafe extern "C" fn switch_comparison(
+    data: *mut c_void,
+    id: obs_hotkey_id,
+    _: *mut obs_hotkey_t,
+    pressed: bool,
+) {
+    if pressed {
+        let state: &mut State = &mut *data.cast();
+        if let Some((_, comparison)) = state
+            .comparison_hotkeys
+            .iter()
+            .find(|(hotkey, _)| *hotkey == id)
+        {
+            let comparison = comparison.clone();
+            let command = Command::SetCurrentComparison {
+                comparison: comparison.clone(),
+            };
+            if state.execute(command).is_err() {
+                log::warn!("The comparison {comparison} doesn't exist.");
+            }
+        }
+    }
+}
+
+type HotkeyFunc = unsafe extern "C" fn(*mut c_void, obs_hotkey_id, *mut obs_hotkey_t, bool);
+
+const HOTKEYS: [(*const c_char, *const c_char, HotkeyFunc); 26] = [
+    (cstr!("hotkey_split"), cstr!("Split"), split),
+    (cstr!("hotkey_reset"), cstr!("Reset"), reset),
+    (cstr!("hotkey_undo"), cstr!("Undo Split"), undo),
+    (cstr!("hotkey_skip"), cstr!("Skip Split"), skip),
+    (cstr!("hotkey_pause"), cstr!("Pause"), pause),
+    (
         cstr!("hotkey_undo_all_pauses"),
         cstr!("Undo All Pauses"),
-        Some(undo_all_pauses),
-        data,
-    );
-
-    obs_hotkey_register_source(
-        source,
+        undo_all_pauses,
+    ),
+    (
         cstr!("hotkey_previous_comparison"),
         cstr!("Previous Comparison"),
-        Some(previous_comparison),
-        data,
-    );
-
-    obs_hotkey_register_source(
-        source,
+        previous_comparison,
+    ),
+    (
         cstr!("hotkey_next_comparison"),
         cstr!("Next Comparison"),
-        Some(next_comparison),
-        data,
-    );
-
-    obs_hotkey_register_source(
-        source,
+        next_comparison,
+    ),
+    (
         cstr!("hotkey_toggle_timing_method"),
         cstr!("Toggle Timing Method"),
-        Some(toggle_timing_method),
+        toggle_timing_method,
+    ),
+    (
+        cstr!("hotkey_pause_game_time"),
+        cstr!("Pause Game Time"),
+        pause_game_time,
+    ),
+    (
+        cstr!("hotkey_resume_game_time"),
+        cstr!("Resume Game Time"),
+        resume_game_time,
+    ),
+    (
+        cstr!("hotkey_save_splits"),
+        cstr!("Save Splits"),
+        save_splits_hotkey,
+    ),
+    (
+        cstr!("hotkey_save_comparison"),
+        cstr!("Save Attempt as Comparison"),
+        save_comparison,
+    ),
+    (cstr!("hotkey_next_run"), cstr!("Next Run"), next_run),
+    (
+        cstr!("hotkey_previous_run"),
+        cstr!("Previous Run"),
+        previous_run,
+    ),
+    (cstr!("hotkey_hand_off"), cstr!("Hand Off"), hand_off),
+    (
+        cstr!("hotkey_start_practice"),
+        cstr!("Start Practice"),
+        start_practice,
+    ),
+    (
+        cstr!("hotkey_increase_offset"),
+        cstr!("Increase Offset"),
+        increase_offset,
+    ),
+    (
+        cstr!("hotkey_decrease_offset"),
+        cstr!("Decrease Offset"),
+        decrease_offset,
+    ),
+    (
+        cstr!("hotkey_toggle_layout"),
+        cstr!("Switch Layout"),
+        toggle_layout,
+    ),
+    (cstr!("hotkey_scroll_up"), cstr!("Scroll Up"), scroll_up),
+    (
+        cstr!("hotkey_scroll_down"),
+        cstr!("Scroll Down"),
+        scroll_down,
+    ),
+    (
+        cstr!("hotkey_reset_scroll"),
+        cstr!("Reset Scrolling"),
+        reset_scroll,
+    ),
+    (
+        cstr!("hotkey_increment_deaths"),
+        cstr!("Increment Death Counter"),
+        increment_deaths,
+    ),
+    (
+        cstr!("hotkey_decrement_deaths"),
+        cstr!("Decrement Death Counter"),
+        decrement_deaths,
+    ),
+    (
+        cstr!("hotkey_reset_deaths"),
+        cstr!("Reset Death Counter"),
+        reset_deaths,
+    ),
+];
+
+unsafe fn register_hotkeys(source: *mut obs_source_t, data: *mut c_void) -> Vec<obs_hotkey_id> {
+    #[allow(unused_mut)]
+    let mut hotkeys: Vec<_> = HOTKEYS
+        .iter()
+        .map(|&(name, description, func)| {
+            obs_hotkey_register_source(source, name, description, Some(func), data)
+        })
+        .collect();
+
+    #[cfg(feature = "auto-splitting")]
+    hotkeys.push(obs_hotkey_register_source(
+        source,
+        cstr!("hotkey_toggle_auto_splitter"),
+        cstr!("Toggle Auto Splitter"),
+        Some(toggle_auto_splitter),
         data,
-    );
+    ));
+
+    hotkeys
+}
+
+/// Registers a hotkey for jumping to each of the first `count` splits. They
+/// all share a callback, which tells them apart by their position.
+unsafe fn register_jump_hotkeys(
+    source: *mut obs_source_t,
+    data: *mut c_void,
+    count: usize,
+) -> Vec<obs_hotkey_id> {
+    (1..=count)
+        .map(|number| {
+            // OBS copies the name and the description.
+            let name = CString::new(format!("hotkey_jump_to_split_{number}")).unwrap();
+            let description = CString::new(format!("Jump to Split {number}")).unwrap();
+            obs_hotkey_register_source(
+                source,
+                name.as_ptr(),
+                description.as_ptr(),
+                Some(jump_to_split),
+                data,
+            )
+        })
+        .collect()
+}
+
+/// Registers a hotkey for switching to each of the comparisons. The hotkeys
+/// are named after their comparison, so they keep their keys when the list is
+/// reordered.
+unsafe fn register_comparison_hotkeys(
+    source: *mut obs_source_t,
+    data: *mut c_void,
+    comparisons: Vec<String>,
+) -> Vec<(obs_hotkey_id, String)> {
+    comparisons
+        .into_iter()
+        .filter_map(|comparison| {
+            let name = CString::new(format!("hotkey_comparison_{comparison}")).ok()?;
+            let description = CString::new(format!("Switch to {comparison}")).ok()?;
+            let id = obs_hotkey_register_source(
+                source,
+                name.as_ptr(),
+                description.as_ptr(),
+                Some(switch_comparison),
+                data,
+            );
+            Some((id, comparison))
+        })
+        .collect()
+}
+
+unsafe fn get_hotkey_comparisons(settings: *mut obs_data_t) -> Vec<String> {
+    if !obs_data_get_bool(settings, SETTINGS_REGISTER_HOTKEYS) {
+        return Vec::new();
+    }
+    let mut comparisons = Vec::<String>::new();
+    for comparison in get_string_list(settings, SETTINGS_COMPARISON_HOTKEYS) {
+        let comparison = comparison.trim();
+        if !comparison.is_empty() && !comparisons.iter().any(|c| c == comparison) {
+            comparisons.push(comparison.to_owned());
+        }
+    }
+    comparisons
+}
+
+unsafe fn get_jump_hotkey_count(settings: *mut obs_data_t) -> usize {
+    if obs_data_get_bool(settings, SETTINGS_REGISTER_HOTKEYS) {
+        obs_data_get_int(settings, SETTINGS_JUMP_HOTKEYS).clamp(0, 100) as usize
+    } else {
+        0
+    }
+}
+
+unsafe fn unregister_hotkeys(hotkeys: &mut Vec<obs_hotkey_id>) {
+    for id in hotkeys.drain(..) {
+        obs_hotkey_unregister(id);
+    }
+}
+
+unsafe extern "C" fn create(settings: *mut obs_data_t, source: *mut obs_source_t) -> *mut c_void {
+    let data = Box::into_raw(Box::new(State::new(source, parse_settings(settings)))).cast();
+
+    procs::register(source, data);
+    signals::register(source);
+
+    let state: &mut State = &mut *data.cast();
+    if obs_data_get_bool(settings, SETTINGS_REGISTER_HOTKEYS) {
+        state.hotkeys = register_hotkeys(source, data);
+    }
+    state.jump_hotkeys = register_jump_hotkeys(source, data, get_jump_hotkey_count(settings));
+    state.comparison_hotkeys =
+        register_comparison_hotkeys(source, data, get_hotkey_comparisons(settings));
+    // Every source has audio, but it only shows up in the mixer while it
+    // plays audio cues.
+    obs_source_set_audio_active(source, state.audio_cues.is_some());
 
     data
 }
 
 unsafe extern "C" fn destroy(data: *mut c_void) {
     let state: Box<State> = Box::from_raw(data.cast());
+    #[cfg(feature = "auto-splitting")]
+    auto_splitter_log::remove(state.source);
     obs_enter_graphics();
     gs_texture_destroy(state.texture);
     obs_leave_graphics();
@@ -452,24 +2061,188 @@ unsafe extern "C" fn get_height(data: *mut c_void) -> u32 {
     state.height
 }
 
+unsafe extern "C" fn video_tick(data: *mut c_void, _: f32) {
+    let state: &mut State = &mut *data.cast();
+    state.schedule_update();
+    state.finish_unconfirmed_reset();
+    state.save_pending_comparison();
+    state.reload_splits_if_changed();
+    state.reload_layout_if_changed();
+    #[cfg(feature = "auto-splitting")]
+    state.reload_auto_splitter_if_changed();
+    #[cfg(feature = "auto-splitting")]
+    state.finish_loading_auto_splitter();
+    #[cfg(feature = "auto-splitting")]
+    state.auto_splitter_downloads.poll(state.source);
+    #[cfg(feature = "auto-splitting")]
+    state.apply_auto_splitter_settings();
+    #[cfg(feature = "auto-splitting")]
+    state.switch_game();
+    run_editor::finish_look_up(state);
+    if let Some(mirror) = &state.mirror {
+        let offset = mirror.poll(&mut state.timer.write().unwrap());
+        if let Some(offset) = offset {
+            state.restored_offset.get_or_insert(offset);
+        }
+    }
+    if let Some(therun) = &mut state.therun {
+        therun.poll(&state.timer.read().unwrap());
+    }
+    if let Some(stream_markers) = &mut state.stream_markers {
+        stream_markers.poll(&state.timer.read().unwrap());
+    }
+    if let Some(webhook) = &mut state.webhook {
+        webhook.poll(&state.timer.read().unwrap());
+    }
+    if let Some(mqtt) = &mut state.mqtt {
+        mqtt.poll(&state.timer.read().unwrap());
+    }
+    let osc_commands = state.osc.as_mut().map(Osc::receive).unwrap_or_default();
+    for command in osc_commands {
+        let _ = state.execute(command);
+    }
+    if let Some(osc) = &mut state.osc {
+        osc.send_feedback(&state.timer.read().unwrap());
+    }
+    state.death_counter.poll(&state.timer);
+    #[cfg(feature = "midi")]
+    {
+        let midi_commands = state.midi.as_ref().map(Midi::poll).unwrap_or_default();
+        for command in midi_commands {
+            let _ = state.execute(command);
+        }
+    }
+    #[cfg(feature = "gamepad")]
+    {
+        let gamepad_commands = state
+            .gamepad
+            .as_mut()
+            .map(Gamepad::poll)
+            .unwrap_or_default();
+        for command in gamepad_commands {
+            let _ = state.execute(command);
+        }
+    }
+    if let Some(rich_presence) = &mut state.rich_presence {
+        rich_presence.poll(&state.timer.read().unwrap());
+    }
+    if let Some(scene_switcher) = &mut state.scene_switcher {
+        scene_switcher.poll(&state.timer.read().unwrap());
+    }
+    if let Some(recorder) = &mut state.recorder {
+        recorder.poll(&state.timer.read().unwrap());
+    }
+    if let Some(state_export) = &mut state.state_export {
+        state_export.poll(&state.timer.read().unwrap(), &state.state);
+    }
+    if let Some(summary_image) = &mut state.summary_image {
+        summary_image.poll(&state.timer.read().unwrap());
+    }
+    if let Some(audio_cues) = &mut state.audio_cues {
+        audio_cues.poll(state.source, &state.timer.read().unwrap());
+    }
+    if let Some(relay) = &mut state.relay {
+        relay.poll(&mut state.timer.write().unwrap());
+    }
+    if let Some(level) = &mut state.individual_level {
+        level.poll();
+    }
+    let finished_run = match &mut state.playlist {
+        Some(playlist) => playlist.poll(&state.timer.read().unwrap()),
+        None => false,
+    };
+    if finished_run {
+        state.switch_run(1);
+    }
+    if let Some(recovery) = &mut state.recovery {
+        recovery.poll(&state.timer.read().unwrap());
+    }
+    state
+        .signals
+        .poll(state.source, &state.timer.read().unwrap());
+    {
+        let policy = command::policy(&state.timer);
+        let mut policy = policy.lock().unwrap();
+        if policy.practicing
+            && state.timer.read().unwrap().current_phase() == TimerPhase::NotRunning
+        {
+            policy.practicing = false;
+        }
+    }
+    if state.restored_offset.is_some() {
+        saved_attempt::restore_offset(
+            &mut state.timer.write().unwrap(),
+            &mut state.restored_offset,
+        );
+    }
+}
+
+unsafe extern "C" fn save(data: *mut c_void, settings: *mut obs_data_t) {
+    let state: &mut State = &mut *data.cast();
+    saved_attempt::save(&state.timer.read().unwrap(), settings);
+}
+
+unsafe extern "C" fn load(data: *mut c_void, settings: *mut obs_data_t) {
+    let state: &mut State = &mut *data.cast();
+    let offset = saved_attempt::load(&mut state.timer.write().unwrap(), settings);
+    if offset.is_some() {
+        state.restored_offset = offset;
+    }
+}
+
 unsafe extern "C" fn video_render(data: *mut c_void, _: *mut gs_effect_t) {
     let state: &mut State = &mut *data.cast();
-    state.update();
+    if mem::take(&mut state.update_pending) {
+        state.update();
+    }
+    state.upload_frame();
+    draw_texture(state.texture, [state.width, state.height], state.srgb);
+}
+
+/// Hashes the state of the layout, so that rendering it again can be skipped
+/// when it didn't change.
+fn hash_layout_state(state: &LayoutState) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    if let Ok(state) = serde_json::to_vec(state) {
+        state.hash(&mut hasher);
+    }
+    hasher.finish()
+}
 
+/// Draws the rendered image. The image is in sRGB, so with `srgb` it is
+/// converted properly into the color space of the canvas, which OBS 28 and
+/// newer expect. Without it, the image is drawn as is, like older versions of
+/// OBS did.
+unsafe fn draw_texture(texture: *mut gs_texture_t, [width, height]: [u32; 2], srgb: bool) {
     let effect = obs_get_base_effect(OBS_EFFECT_PREMULTIPLIED_ALPHA);
     let tech = gs_effect_get_technique(effect, cstr!("Draw"));
+    let image = gs_effect_get_param_by_name(effect, cstr!("image"));
+
+    let previous_srgb = gs_framebuffer_srgb_enabled();
+    gs_enable_framebuffer_srgb(srgb);
 
     gs_technique_begin(tech);
     gs_technique_begin_pass(tech, 0);
 
-    gs_effect_set_texture(
-        gs_effect_get_param_by_name(effect, cstr!("image")),
-        state.texture,
-    );
-    gs_draw_sprite(state.texture, 0, 0, 0);
+    if srgb {
+        gs_effect_set_texture_srgb(image, texture);
+    } else {
+        gs_effect_set_texture(image, texture);
+    }
+    gs_draw_sprite(texture, 0, width, height);
 
     gs_technique_end_pass(tech);
     gs_technique_end(tech);
+
+    gs_enable_framebuffer_srgb(previous_srgb);
+}
+
+unsafe extern "C" fn video_get_color_space(
+    _: *mut c_void,
+    _: size_t,
+    _: *const gs_color_space,
+) -> gs_color_space {
+    GS_CS_SRGB
 }
 
 unsafe extern "C" fn mouse_wheel(
@@ -479,10 +2252,92 @@ unsafe extern "C" fn mouse_wheel(
     y_delta: c_int,
 ) {
     let state: &mut State = &mut *data.cast();
-    match y_delta.cmp(&0) {
-        Ordering::Less => state.layout.scroll_down(),
-        Ordering::Equal => {}
-        Ordering::Greater => state.layout.scroll_up(),
+    let steps = state
+        .interaction
+        .wheel_steps(&mut state.wheel_delta, y_delta);
+    match state.interaction.wheel {
+        WheelAction::Scroll => scroll(&mut state.layout, steps),
+        WheelAction::PageSplits => {
+            let rows = visible_split_rows(&state.state).max(1) as i32;
+            scroll(&mut state.layout, steps * rows);
+        }
+        WheelAction::SwitchComparison => {
+            for _ in 0..steps.abs() {
+                let command = if steps > 0 {
+                    Command::SwitchToPreviousComparison
+                } else {
+                    Command::SwitchToNextComparison
+                };
+                let _ = state.execute(command);
+            }
+        }
+    }
+}
+
+/// Scrolls the layout up for positive steps and down for negative ones.
+fn scroll(layout: &mut Layout, steps: i32) {
+    for _ in 0..steps.abs() {
+        if steps > 0 {
+            layout.scroll_up();
+        } else {
+            layout.scroll_down();
+        }
+    }
+}
+
+/// Returns how many rows of splits the layout shows.
+fn visible_split_rows(state: &LayoutState) -> usize {
+    state
+        .components
+        .iter()
+        .map(|component| match component {
+            ComponentState::Splits(splits) => splits.splits.len(),
+            _ => 0,
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+unsafe extern "C" fn mouse_click(
+    data: *mut c_void,
+    _: *const obs_mouse_event,
+    button: i32,
+    mouse_up: bool,
+    _: u32,
+) {
+    let state: &mut State = &mut *data.cast();
+    // Acting when the button is pressed rather than released keeps the split
+    // as close as possible to the moment of the click.
+    if mouse_up {
+        return;
+    }
+    if let Some(command) = state.interaction.click(button).command() {
+        let _ = state.execute(command);
+    }
+}
+
+unsafe extern "C" fn key_click(data: *mut c_void, event: *const obs_key_event, key_up: bool) {
+    let state: &mut State = &mut *data.cast();
+    let key = obs_key_from_virtual_key((*event).native_vkey as c_int);
+    // Holding a key down repeats it, which mustn't split over and over.
+    if key_up {
+        state.held_keys.retain(|&held| held != key);
+        return;
+    }
+    if state.held_keys.contains(&key) {
+        return;
+    }
+    state.held_keys.push(key);
+    if let Some(command) = state.interaction.key(key).command() {
+        let _ = state.execute(command);
+    }
+}
+
+unsafe extern "C" fn focus(data: *mut c_void, focus: bool) {
+    let state: &mut State = &mut *data.cast();
+    // The keys may be released while the window isn't focused.
+    if !focus {
+        state.held_keys.clear();
     }
 }
 
@@ -492,15 +2347,36 @@ unsafe extern "C" fn save_splits(
     data: *mut c_void,
 ) -> bool {
     let state: &mut State = &mut *data.cast();
-    if state.can_save_splits {
-        let timer = state.timer.read().unwrap();
-        if let Ok(file) = File::create(&state.splits_path) {
-            let _ = save_timer(&timer, IoWrite(BufWriter::new(file)));
-        }
-    }
+    state.save_splits();
     false
 }
 
+#[cfg(feature = "auto-splitting")]
+unsafe extern "C" fn reload_auto_splitter(
+    _: *mut obs_properties_t,
+    _: *mut obs_property_t,
+    data: *mut c_void,
+) -> bool {
+    let state: &mut State = &mut *data.cast();
+    state.auto_splitter_enabled = true;
+    state.load_auto_splitter();
+    true
+}
+
+#[cfg(feature = "auto-splitting")]
+unsafe extern "C" fn unload_auto_splitter(
+    _: *mut obs_properties_t,
+    _: *mut obs_property_t,
+    data: *mut c_void,
+) -> bool {
+    let state: &mut State = &mut *data.cast();
+    // It stays unloaded until it's reloaded, even if its file changes.
+    state.auto_splitter_enabled = false;
+    state.unload_auto_splitter();
+    log::info!("Unloaded auto splitter.");
+    true
+}
+
 unsafe extern "C" fn media_get_state(data: *mut c_void) -> obs_media_state {
     let state: &mut State = &mut *data.cast();
     let phase = state.timer.read().unwrap().current_phase();
@@ -514,47 +2390,36 @@ unsafe extern "C" fn media_get_state(data: *mut c_void) -> obs_media_state {
 
 unsafe extern "C" fn media_play_pause(data: *mut c_void, pause: bool) {
     let state: &mut State = &mut *data.cast();
-    let mut timer = state.timer.write().unwrap();
-    match timer.current_phase() {
-        TimerPhase::NotRunning => {
-            if !pause {
-                timer.start()
-            }
-        }
-        TimerPhase::Running => {
-            if pause {
-                timer.pause()
-            }
-        }
-        TimerPhase::Ended => {}
-        TimerPhase::Paused => {
-            if !pause {
-                timer.resume()
-            }
-        }
-    }
+    let phase = state.timer.read().unwrap().current_phase();
+    let command = match (phase, pause) {
+        (TimerPhase::NotRunning, false) => Command::Start,
+        (TimerPhase::Running, true) => Command::Pause,
+        (TimerPhase::Paused, false) => Command::Resume,
+        _ => return,
+    };
+    let _ = state.execute(command);
 }
 
 unsafe extern "C" fn media_restart(data: *mut c_void) {
     let state: &mut State = &mut *data.cast();
-    let mut timer = state.timer.write().unwrap();
-    timer.reset(true);
-    timer.start();
+    let policy = command::policy(&state.timer);
+    let mut policy = policy.lock().unwrap();
+    policy.restart(&mut state.timer.write().unwrap());
 }
 
 unsafe extern "C" fn media_stop(data: *mut c_void) {
     let state: &mut State = &mut *data.cast();
-    state.timer.write().unwrap().reset(true);
+    let _ = state.execute(state.media_controls.stop());
 }
 
 unsafe extern "C" fn media_next(data: *mut c_void) {
     let state: &mut State = &mut *data.cast();
-    state.timer.write().unwrap().split();
+    let _ = state.execute(state.media_controls.next());
 }
 
 unsafe extern "C" fn media_previous(data: *mut c_void) {
     let state: &mut State = &mut *data.cast();
-    state.timer.write().unwrap().undo_split();
+    let _ = state.execute(state.media_controls.previous());
 }
 
 unsafe extern "C" fn media_get_time(data: *mut c_void) -> i64 {
@@ -565,32 +2430,144 @@ unsafe extern "C" fn media_get_time(data: *mut c_void) -> i64 {
     secs * 1000 + (nanos / 1_000_000) as i64
 }
 
+unsafe extern "C" fn media_set_time(data: *mut c_void, milliseconds: i64) {
+    let state: &mut State = &mut *data.cast();
+    state.seek(TimeSpan::from_milliseconds(milliseconds as f64));
+}
+
 unsafe extern "C" fn media_get_duration(data: *mut c_void) -> i64 {
     let state: &mut State = &mut *data.cast();
-    let timer = state.timer.read().unwrap();
-    let time = timer
-        .run()
-        .segments()
-        .last()
-        .unwrap()
-        .personal_best_split_time()[timer.current_timing_method()]
-    .unwrap_or_default();
+    let time = state.media_controls.duration(&state.timer.read().unwrap());
     let (secs, nanos) = time.to_seconds_and_subsec_nanoseconds();
     secs * 1000 + (nanos / 1_000_000) as i64
 }
 
 const SETTINGS_WIDTH: *const c_char = cstr!("width");
 const SETTINGS_HEIGHT: *const c_char = cstr!("height");
+const SETTINGS_UPDATE_FPS: *const c_char = cstr!("update_fps");
+const SETTINGS_SRGB: *const c_char = cstr!("srgb");
+const SETTINGS_RENDER_SCALE: *const c_char = cstr!("render_scale");
+const SETTINGS_AUTO_SIZE: *const c_char = cstr!("auto_size");
+const SETTINGS_OPACITY: *const c_char = cstr!("opacity");
 const SETTINGS_SPLITS_PATH: *const c_char = cstr!("splits_path");
+const SETTINGS_TIMER_GROUP: *const c_char = cstr!("timer_group");
+const SETTINGS_TRANSFER_ATTEMPT: *const c_char = cstr!("transfer_attempt");
 const SETTINGS_LAYOUT_PATH: *const c_char = cstr!("layout_path");
+const SETTINGS_ALTERNATE_LAYOUT_PATH: *const c_char = cstr!("alternate_layout_path");
 #[cfg(feature = "auto-splitting")]
 const SETTINGS_AUTO_SPLITTER_PATH: *const c_char = cstr!("auto_splitter_path");
+#[cfg(feature = "auto-splitting")]
+const SETTINGS_RELOAD_AUTO_SPLITTER: *const c_char = cstr!("reload_auto_splitter");
+#[cfg(feature = "auto-splitting")]
+const SETTINGS_UNLOAD_AUTO_SPLITTER: *const c_char = cstr!("unload_auto_splitter");
+const SETTINGS_REGISTER_HOTKEYS: *const c_char = cstr!("register_hotkeys");
+const SETTINGS_JUMP_HOTKEYS: *const c_char = cstr!("jump_hotkeys");
+const SETTINGS_COMPARISON_HOTKEYS: *const c_char = cstr!("comparison_hotkeys");
 const SETTINGS_SAVE_SPLITS: *const c_char = cstr!("save_splits");
-
-unsafe extern "C" fn get_properties(_: *mut c_void) -> *mut obs_properties_t {
+const SETTINGS_COMPARISON: *const c_char = cstr!("comparison");
+const SETTINGS_TIMING_METHOD: *const c_char = cstr!("timing_method");
+const SETTINGS_RESET_POLICY: *const c_char = cstr!("reset_policy");
+const SETTINGS_SERVER_ENABLED: *const c_char = cstr!("server_enabled");
+const SETTINGS_SERVER_PORT: *const c_char = cstr!("server_port");
+const SETTINGS_SERVER_LAN: *const c_char = cstr!("server_lan");
+const SETTINGS_SERVER_TOKEN: *const c_char = cstr!("server_token");
+const SETTINGS_SERVER_ALLOWED_ORIGINS: *const c_char = cstr!("server_allowed_origins");
+const SETTINGS_GLOBAL_HOTKEYS: *const c_char = cstr!("global_hotkeys");
+const SETTINGS_THERUN_KEY: *const c_char = cstr!("therun_upload_key");
+const SETTINGS_TWITCH_CLIENT_ID: *const c_char = cstr!("twitch_client_id");
+const SETTINGS_TWITCH_TOKEN: *const c_char = cstr!("twitch_token");
+const SETTINGS_WEBHOOK_URL: *const c_char = cstr!("webhook_url");
+const SETTINGS_WEBHOOK_TEMPLATE: *const c_char = cstr!("webhook_template");
+const SETTINGS_DISCORD_APPLICATION_ID: *const c_char = cstr!("discord_application_id");
+const SETTINGS_GLOBAL_HOTKEY_SPLIT: *const c_char = cstr!("global_hotkey_split");
+const SETTINGS_GLOBAL_HOTKEY_RESET: *const c_char = cstr!("global_hotkey_reset");
+const SETTINGS_GLOBAL_HOTKEY_UNDO: *const c_char = cstr!("global_hotkey_undo");
+const SETTINGS_GLOBAL_HOTKEY_SKIP: *const c_char = cstr!("global_hotkey_skip");
+const SETTINGS_GLOBAL_HOTKEY_PAUSE: *const c_char = cstr!("global_hotkey_pause");
+const SETTINGS_GLOBAL_HOTKEY_UNDO_ALL_PAUSES: *const c_char =
+    cstr!("global_hotkey_undo_all_pauses");
+const SETTINGS_GLOBAL_HOTKEY_PREVIOUS_COMPARISON: *const c_char =
+    cstr!("global_hotkey_previous_comparison");
+const SETTINGS_GLOBAL_HOTKEY_NEXT_COMPARISON: *const c_char =
+    cstr!("global_hotkey_next_comparison");
+const SETTINGS_GLOBAL_HOTKEY_TOGGLE_TIMING_METHOD: *const c_char =
+    cstr!("global_hotkey_toggle_timing_method");
+
+const GLOBAL_HOTKEYS: [(*const c_char, *const c_char, *const c_char); 9] = [
+    (
+        SETTINGS_GLOBAL_HOTKEY_SPLIT,
+        cstr!("Global Split Hotkey"),
+        cstr!("Numpad1"),
+    ),
+    (
+        SETTINGS_GLOBAL_HOTKEY_RESET,
+        cstr!("Global Reset Hotkey"),
+        cstr!("Numpad3"),
+    ),
+    (
+        SETTINGS_GLOBAL_HOTKEY_UNDO,
+        cstr!("Global Undo Split Hotkey"),
+        cstr!("Numpad8"),
+    ),
+    (
+        SETTINGS_GLOBAL_HOTKEY_SKIP,
+        cstr!("Global Skip Split Hotkey"),
+        cstr!("Numpad2"),
+    ),
+    (
+        SETTINGS_GLOBAL_HOTKEY_PAUSE,
+        cstr!("Global Pause Hotkey"),
+        cstr!(""),
+    ),
+    (
+        SETTINGS_GLOBAL_HOTKEY_UNDO_ALL_PAUSES,
+        cstr!("Global Undo All Pauses Hotkey"),
+        cstr!(""),
+    ),
+    (
+        SETTINGS_GLOBAL_HOTKEY_PREVIOUS_COMPARISON,
+        cstr!("Global Previous Comparison Hotkey"),
+        cstr!("Numpad4"),
+    ),
+    (
+        SETTINGS_GLOBAL_HOTKEY_NEXT_COMPARISON,
+        cstr!("Global Next Comparison Hotkey"),
+        cstr!("Numpad6"),
+    ),
+    (
+        SETTINGS_GLOBAL_HOTKEY_TOGGLE_TIMING_METHOD,
+        cstr!("Global Toggle Timing Method Hotkey"),
+        cstr!(""),
+    ),
+];
+
+unsafe extern "C" fn get_properties(data: *mut c_void) -> *mut obs_properties_t {
     let props = obs_properties_create();
     obs_properties_add_int(props, SETTINGS_WIDTH, cstr!("Width"), 10, 8200, 10);
     obs_properties_add_int(props, SETTINGS_HEIGHT, cstr!("Height"), 10, 8200, 10);
+    let auto_size = obs_properties_add_bool(props, SETTINGS_AUTO_SIZE, cstr!("Auto Size"));
+    obs_property_set_long_description(
+        auto_size,
+        cstr!("Resizes the source to fit the layout, like when splits are added to it. The width and height are where it starts out."),
+    );
+    let update_fps =
+        obs_properties_add_int(props, SETTINGS_UPDATE_FPS, cstr!("Update FPS"), 0, 240, 1);
+    obs_property_set_long_description(
+        update_fps,
+        cstr!("How often the layout is rendered per second. 0 renders it every frame."),
+    );
+    let render_scale =
+        obs_properties_add_int(props, SETTINGS_RENDER_SCALE, cstr!("Render Scale"), 1, 4, 1);
+    obs_property_set_long_description(
+        render_scale,
+        cstr!("Renders the layout at a multiple of its size, so it stays sharp when the source is scaled up in the scene."),
+    );
+    obs_properties_add_int_slider(props, SETTINGS_OPACITY, cstr!("Opacity"), 0, 100, 1);
+    let srgb = obs_properties_add_bool(props, SETTINGS_SRGB, cstr!("sRGB Color Handling"));
+    obs_property_set_long_description(
+        srgb,
+        cstr!("Converts the colors into the color space of the canvas. Turn this off if the colors look wrong on an older version of OBS."),
+    );
     obs_properties_add_path(
         props,
         SETTINGS_SPLITS_PATH,
@@ -599,6 +2576,18 @@ unsafe extern "C" fn get_properties(_: *mut c_void) -> *mut obs_properties_t {
         cstr!("LiveSplit Splits (*.lss)"),
         ptr::null(),
     );
+    remote_splits::add_properties(props);
+    new_splits::add_properties(props);
+    add_timer_group_property(props);
+    let transfer_attempt = obs_properties_add_bool(
+        props,
+        SETTINGS_TRANSFER_ATTEMPT,
+        cstr!("Keep the Attempt When Changing the Splits"),
+    );
+    obs_property_set_long_description(
+        transfer_attempt,
+        cstr!("Continues the attempt in progress with the new splits. The times of the splits that were already done don't carry over."),
+    );
     obs_properties_add_path(
         props,
         SETTINGS_LAYOUT_PATH,
@@ -607,6 +2596,19 @@ unsafe extern "C" fn get_properties(_: *mut c_void) -> *mut obs_properties_t {
         cstr!("LiveSplit Layouts (*.lsl *.ls1l)"),
         ptr::null(),
     );
+    let alternate_layout = obs_properties_add_path(
+        props,
+        SETTINGS_ALTERNATE_LAYOUT_PATH,
+        cstr!("Alternate Layout"),
+        OBS_PATH_FILE,
+        cstr!("LiveSplit Layouts (*.lsl *.ls1l)"),
+        ptr::null(),
+    );
+    obs_property_set_long_description(
+        alternate_layout,
+        cstr!("A second layout that the Switch Layout hotkey switches to and back, e.g. a more detailed one for between the games of a marathon."),
+    );
+    fonts::add_properties(props);
     #[cfg(feature = "auto-splitting")]
     obs_properties_add_path(
         props,
@@ -616,18 +2618,271 @@ unsafe extern "C" fn get_properties(_: *mut c_void) -> *mut obs_properties_t {
         cstr!("LiveSplit One Auto Splitter (*.wasm)"),
         ptr::null(),
     );
+    #[cfg(feature = "auto-splitting")]
+    {
+        obs_properties_add_button(
+            props,
+            SETTINGS_RELOAD_AUTO_SPLITTER,
+            cstr!("Reload Auto Splitter"),
+            Some(reload_auto_splitter),
+        );
+        obs_properties_add_button(
+            props,
+            SETTINGS_UNLOAD_AUTO_SPLITTER,
+            cstr!("Unload Auto Splitter"),
+            Some(unload_auto_splitter),
+        );
+        auto_splitter_log::add_properties(props);
+    }
+    #[cfg(feature = "auto-splitting")]
+    auto_splitter_list::add_properties(props);
+    #[cfg(feature = "auto-splitting")]
+    game_detection::add_properties(props);
     obs_properties_add_button(
         props,
         SETTINGS_SAVE_SPLITS,
         cstr!("Save Splits"),
         Some(save_splits),
     );
+    backup::add_properties(props);
+    let comparison = obs_properties_add_list(
+        props,
+        SETTINGS_COMPARISON,
+        cstr!("Comparison"),
+        OBS_COMBO_TYPE_LIST,
+        OBS_COMBO_FORMAT_STRING,
+    );
+    obs_property_list_add_string(comparison, cstr!("Keep Current"), cstr!(""));
+    if let Some(state) = data.cast::<State>().as_ref() {
+        let timer = state.timer.read().unwrap();
+        for name in timer.run().comparisons() {
+            if let Ok(name) = CString::new(name) {
+                obs_property_list_add_string(comparison, name.as_ptr(), name.as_ptr());
+            }
+        }
+    }
+    let timing_method = obs_properties_add_list(
+        props,
+        SETTINGS_TIMING_METHOD,
+        cstr!("Timing Method"),
+        OBS_COMBO_TYPE_LIST,
+        OBS_COMBO_FORMAT_STRING,
+    );
+    obs_property_list_add_string(timing_method, cstr!("Real Time"), cstr!("real_time"));
+    obs_property_list_add_string(timing_method, cstr!("Game Time"), cstr!("game_time"));
+    let reset_policy = obs_properties_add_list(
+        props,
+        SETTINGS_RESET_POLICY,
+        cstr!("Update Splits on Reset"),
+        OBS_COMBO_TYPE_LIST,
+        OBS_COMBO_FORMAT_STRING,
+    );
+    obs_property_list_add_string(reset_policy, cstr!("Always"), cstr!("always"));
+    obs_property_list_add_string(reset_policy, cstr!("Never"), cstr!("never"));
+    obs_property_list_add_string(
+        reset_policy,
+        cstr!("Only When Resetting Twice"),
+        cstr!("ask"),
+    );
+    obs_properties_add_bool(
+        props,
+        SETTINGS_SERVER_ENABLED,
+        cstr!("Enable WebSocket Server"),
+    );
+    obs_properties_add_int(
+        props,
+        SETTINGS_SERVER_PORT,
+        cstr!("WebSocket Server Port"),
+        1,
+        65535,
+        1,
+    );
+    let server_lan = obs_properties_add_bool(
+        props,
+        SETTINGS_SERVER_LAN,
+        cstr!("Allow Connections From Other Devices"),
+    );
+    obs_property_set_long_description(
+        server_lan,
+        cstr!("Without this, only programs on this computer can connect to the server."),
+    );
+    let server_token = obs_properties_add_text(
+        props,
+        SETTINGS_SERVER_TOKEN,
+        cstr!("WebSocket Server Token"),
+        OBS_TEXT_PASSWORD,
+    );
+    obs_property_set_long_description(
+        server_token,
+        cstr!("If set, clients need to add it to the address they connect to, like ws://localhost:16834/?token=... The dock needs it in its address as well."),
+    );
+    let server_allowed_origins = obs_properties_add_editable_list(
+        props,
+        SETTINGS_SERVER_ALLOWED_ORIGINS,
+        cstr!("Web Pages Allowed to Connect"),
+        OBS_EDITABLE_LIST_TYPE_STRINGS,
+        ptr::null(),
+        ptr::null(),
+    );
+    obs_property_set_long_description(
+        server_allowed_origins,
+        cstr!("Web pages can only connect to the server if they're listed here, like https://one.livesplit.org. The server's own control panel can always connect."),
+    );
+    sync::add_properties(props);
+    let register_hotkeys = obs_properties_add_bool(
+        props,
+        SETTINGS_REGISTER_HOTKEYS,
+        cstr!("Register Hotkeys for This Source"),
+    );
+    obs_property_set_long_description(
+        register_hotkeys,
+        cstr!("When multiple sources share a timer, turn this off for all but one of them, so a single key press doesn't trigger the same action multiple times."),
+    );
+    let jump_hotkeys = obs_properties_add_int(
+        props,
+        SETTINGS_JUMP_HOTKEYS,
+        cstr!("Jump to Split Hotkeys"),
+        0,
+        100,
+        1,
+    );
+    obs_property_set_long_description(
+        jump_hotkeys,
+        cstr!("Adds hotkeys that skip or undo splits until the timer is at the first, the second, ... split. Useful for practicing and for catching up when the auto splitter missed a split."),
+    );
+    let comparison_hotkeys = obs_properties_add_editable_list(
+        props,
+        SETTINGS_COMPARISON_HOTKEYS,
+        cstr!("Comparison Hotkeys"),
+        OBS_EDITABLE_LIST_TYPE_STRINGS,
+        ptr::null(),
+        ptr::null(),
+    );
+    obs_property_set_long_description(
+        comparison_hotkeys,
+        cstr!("Adds a hotkey for switching to each of these comparisons, e.g. Personal Best or Best Segments."),
+    );
+    obs_properties_add_bool(
+        props,
+        SETTINGS_GLOBAL_HOTKEYS,
+        cstr!("Use Global Hotkeys (disables the OBS hotkeys of this source)"),
+    );
+    for (name, description, _) in GLOBAL_HOTKEYS {
+        obs_properties_add_text(props, name, description, OBS_TEXT_DEFAULT);
+    }
+    obs_properties_add_text(
+        props,
+        SETTINGS_THERUN_KEY,
+        cstr!("therun.gg Upload Key"),
+        OBS_TEXT_PASSWORD,
+    );
+    obs_properties_add_text(
+        props,
+        SETTINGS_TWITCH_CLIENT_ID,
+        cstr!("Twitch Client ID"),
+        OBS_TEXT_DEFAULT,
+    );
+    obs_properties_add_text(
+        props,
+        SETTINGS_TWITCH_TOKEN,
+        cstr!("Twitch Token (creates stream markers)"),
+        OBS_TEXT_PASSWORD,
+    );
+    obs_properties_add_text(
+        props,
+        SETTINGS_WEBHOOK_URL,
+        cstr!("Webhook URL"),
+        OBS_TEXT_DEFAULT,
+    );
+    obs_properties_add_text(
+        props,
+        SETTINGS_WEBHOOK_TEMPLATE,
+        cstr!("Webhook Template"),
+        OBS_TEXT_MULTILINE,
+    );
+    obs_properties_add_text(
+        props,
+        SETTINGS_DISCORD_APPLICATION_ID,
+        cstr!("Discord Application ID (shows Rich Presence)"),
+        OBS_TEXT_DEFAULT,
+    );
+    mqtt::add_properties(props);
+    osc::add_properties(props);
+    #[cfg(feature = "midi")]
+    midi::add_properties(props);
+    #[cfg(feature = "gamepad")]
+    gamepad::add_properties(props);
+    scene_switcher::add_properties(props);
+    recording::add_properties(props);
+    state_export::add_properties(props);
+    history_export::add_properties(props);
+    summary_image::add_properties(props);
+    run_summary::add_properties(props);
+    custom_comparison::add_properties(props);
+    generated_comparisons::add_properties(props);
+    audio_cues::add_properties(props);
+    playlist::add_properties(props);
+    relay::add_properties(props);
+    custom_variables::add_properties(props);
+    individual_level::add_properties(props, data.cast::<State>().as_ref().map(State::main_timer));
+    practice::add_properties(props, data.cast::<State>().as_ref().map(State::main_timer));
+    run_maintenance::add_properties(props, data.cast::<State>().as_ref().map(State::main_timer));
+    attempt_history::add_properties(props, data.cast::<State>().as_ref().map(State::main_timer));
+    splits_statistics::add_properties(props, data.cast::<State>().as_ref().map(State::main_timer));
+    death_counter::add_properties(props);
+    media_controls::add_properties(props);
+    interaction::add_properties(props);
+    layout_builder::add_properties(props);
+    if let Some(state) = data.cast::<State>().as_mut() {
+        #[cfg(feature = "auto-splitting")]
+        auto_splitter_status::add_properties(props, state);
+        #[cfg(feature = "auto-splitting")]
+        auto_splitter_settings::add_properties(props, state);
+        run_editor::add_properties(props, state);
+        recovery::add_properties(props, state);
+    }
     props
 }
 
 unsafe extern "C" fn get_defaults(settings: *mut obs_data_t) {
     obs_data_set_default_int(settings, SETTINGS_WIDTH, 300);
     obs_data_set_default_int(settings, SETTINGS_HEIGHT, 500);
+    obs_data_set_default_bool(settings, SETTINGS_SRGB, true);
+    obs_data_set_default_int(settings, SETTINGS_RENDER_SCALE, 1);
+    obs_data_set_default_int(settings, SETTINGS_OPACITY, 100);
+    obs_data_set_default_string(settings, SETTINGS_TIMING_METHOD, cstr!("real_time"));
+    obs_data_set_default_string(settings, SETTINGS_RESET_POLICY, cstr!("always"));
+    obs_data_set_default_bool(settings, SETTINGS_SERVER_ENABLED, false);
+    obs_data_set_default_int(settings, SETTINGS_SERVER_PORT, 16834);
+    obs_data_set_default_bool(settings, SETTINGS_SERVER_LAN, false);
+    obs_data_set_default_bool(settings, SETTINGS_REGISTER_HOTKEYS, true);
+    obs_data_set_default_bool(settings, SETTINGS_GLOBAL_HOTKEYS, false);
+    obs_data_set_default_string(
+        settings,
+        SETTINGS_WEBHOOK_TEMPLATE,
+        cstr!(r#"{"content": "{event}: {segment} {time}"}"#),
+    );
+    for (name, _, default) in GLOBAL_HOTKEYS {
+        obs_data_set_default_string(settings, name, default);
+    }
+    #[cfg(feature = "auto-splitting")]
+    auto_splitter_log::set_defaults(settings);
+    mqtt::set_defaults(settings);
+    recording::set_defaults(settings);
+    state_export::set_defaults(settings);
+    summary_image::set_defaults(settings);
+    run_summary::set_defaults(settings);
+    custom_comparison::set_defaults(settings);
+    generated_comparisons::set_defaults(settings);
+    audio_cues::set_defaults(settings);
+    media_controls::set_defaults(settings);
+    interaction::set_defaults(settings);
+    practice::set_defaults(settings);
+    death_counter::set_defaults(settings);
+    individual_level::set_defaults(settings);
+    recovery::set_defaults(settings);
+    backup::set_defaults(settings);
+    layout_builder::set_defaults(settings);
 }
 
 fn default_run() -> (Run, bool) {
@@ -640,56 +2895,286 @@ unsafe extern "C" fn update(data: *mut c_void, settings: *mut obs_data_t) {
     log::info!("Reloading settings.");
 
     let state: &mut State = &mut *data.cast();
+    let transfer_attempt = obs_data_get_bool(settings, SETTINGS_TRANSFER_ATTEMPT);
+
+    let register_hotkeys_setting = obs_data_get_bool(settings, SETTINGS_REGISTER_HOTKEYS);
+    if register_hotkeys_setting && state.hotkeys.is_empty() {
+        state.hotkeys = register_hotkeys(state.source, data);
+    } else if !register_hotkeys_setting {
+        unregister_hotkeys(&mut state.hotkeys);
+    }
+    let jump_hotkey_count = get_jump_hotkey_count(settings);
+    if state.jump_hotkeys.len() != jump_hotkey_count {
+        unregister_hotkeys(&mut state.jump_hotkeys);
+        state.jump_hotkeys = register_jump_hotkeys(state.source, data, jump_hotkey_count);
+    }
+    let hotkey_comparisons = get_hotkey_comparisons(settings);
+    if !state
+        .comparison_hotkeys
+        .iter()
+        .map(|(_, comparison)| comparison)
+        .eq(&hotkey_comparisons)
+    {
+        let mut ids = state
+            .comparison_hotkeys
+            .drain(..)
+            .map(|(id, _)| id)
+            .collect();
+        unregister_hotkeys(&mut ids);
+        state.comparison_hotkeys =
+            register_comparison_hotkeys(state.source, data, hotkey_comparisons);
+    }
+
     let settings = parse_settings(settings);
+    let old_timer = state.timer.clone();
+
+    // The splits only need to be parsed when there's no timer for them yet,
+    // or when the source switched to different splits.
+    let mut can_save = None;
+    let (timer, splits_path) = shared_timer(&settings.splits_path, &settings.timer_group, || {
+        let (run, can_save_splits) = parse_run(&settings.splits_path).unwrap_or_else(default_run);
+        can_save = Some(can_save_splits);
+        run
+    });
+    let splits_changed = state.splits_path != splits_path;
 
-    let timer = {
-        let mut timers = TIMERS.lock().unwrap();
-        timers.retain(|(_, timer)| timer.strong_count() > 0);
-        if let Some(timer) = timers.iter().find_map(|(path, timer)| {
-            if path == &settings.splits_path {
-                timer.upgrade()
-            } else {
-                None
+    if splits_changed {
+        state.can_save_splits = can_save.unwrap_or_else(|| can_save_splits(&splits_path));
+        state.splits_watcher = FileWatcher::new(&splits_path);
+        state.splits_reload_pending = false;
+
+        if transfer_attempt && !Arc::ptr_eq(state.main_timer(), &timer) {
+            if let Some(offset) = saved_attempt::transfer(state.main_timer(), &timer) {
+                state.restored_offset = Some(offset);
             }
-        }) {
-            log::debug!("Found timer to reuse.");
-            timer
-        } else {
-            log::debug!("Storing timer for reuse.");
-            let timer = Timer::new(settings.run).unwrap().into_shared();
-            timers.push((settings.splits_path.clone(), Arc::downgrade(&timer)));
-            timer
         }
+    }
+    state.splits_path = splits_path;
+    state.backup_count = settings.backup_count;
+    state.individual_level = match (state.individual_level.take(), settings.individual_level) {
+        (Some(level), Some(segment)) if level.is_of(&timer, segment) => Some(level),
+        (_, segment) => segment.and_then(|segment| IndividualLevel::new(timer.clone(), segment)),
     };
-
-    state.splits_path = settings.splits_path;
-    state.can_save_splits = settings.can_save_splits;
-    state.timer = timer;
-    state.layout = settings.layout;
+    state.timer = state
+        .individual_level
+        .as_ref()
+        .map_or(timer, |level| level.timer().clone());
+    let timer_changed = !Arc::ptr_eq(&old_timer, &state.timer);
+    configure_timer(&state.timer, &settings.comparison, settings.timing_method);
+    command::policy(&state.timer)
+        .lock()
+        .unwrap()
+        .configure(settings.reset_policy, settings.hidden_comparisons);
+    state.main_layout_path = settings.layout_path;
+    state.alternate_layout_path = settings.alternate_layout_path;
+    state.show_layout(state.chosen_layout_path().to_owned(), settings.built_layout);
+    // The renderer only gets the fonts that were loaded when it was created,
+    // so it needs to be recreated for the fonts of a new folder.
+    if fonts::load_folder(&settings.fonts_folder) {
+        state.render_thread = RenderThread::new();
+        state.rendered_state = None;
+        state.update_pending = true;
+    }
 
     #[cfg(feature = "auto-splitting")]
-    if !settings.auto_splitter_path.is_empty() {
-        state
-            .auto_splitter
-            .load_script_blocking(PathBuf::from(settings.auto_splitter_path))
-            .ok();
+    {
+        // Reloading the auto splitter loses its state, so it only happens
+        // when a different one was chosen.
+        let auto_splitter_path = settings.auto_splitter_path;
+        let auto_splitter_changed = state.auto_splitter_path != auto_splitter_path;
+        if auto_splitter_changed {
+            state.auto_splitter_watcher = FileWatcher::new(&auto_splitter_path);
+            state.auto_splitter_path = auto_splitter_path;
+        }
+        // The auto splitter needs to control the timer that the source shows,
+        // which is a different one for other splits, another timer group or
+        // an individual level. Moving on in the playlist unloads it, so it's bound again for any
+        // other splits, even if they share the timer. Binding it loads the
+        // chosen one, so a detected game's auto splitter and splits, which
+        // are switched together, only load it once.
+        if splits_changed || timer_changed {
+            state.bind_auto_splitter();
+        } else if auto_splitter_changed && state.auto_splitter_enabled {
+            // Without a path this only unloads the auto splitter.
+            state.load_auto_splitter();
+        }
+        // Its own settings may have changed too. They're applied in the next
+        // frame, or once the auto splitter finished loading.
+        state.auto_splitter_settings_pending = true;
+
+        auto_splitter_log::set_level(state.source, settings.auto_splitter_log_level);
+
+        // The games that are already running are only reported once, so the
+        // detection is kept as long as the same games are listed.
+        if state.game_detection.as_ref().map(GameDetection::games)
+            != settings.game_detection.as_deref()
+        {
+            state.game_detection = settings.game_detection.map(GameDetection::new);
+        }
     }
 
-    if state.width != settings.width || state.height != settings.height {
+    if state.server.as_ref().map(Server::config) != settings.server.as_ref() {
+        state.server = None;
+        state.server = settings
+            .server
+            .and_then(|config| start_server(config, state.timer.clone()));
+    } else if let Some(server) = &state.server {
+        server.set_timer(state.timer.clone());
+    }
+
+    if state.mirror.as_ref().map(Mirror::address) != settings.sync_address.as_deref() {
+        state.mirror = None;
+        state.mirror = settings.sync_address.and_then(start_mirror);
+    }
+
+    // The hotkey system holds on to the timer, so it always needs to be
+    // recreated in case the timer changed.
+    state.global_hotkeys = None;
+    state.global_hotkeys = settings
+        .global_hotkeys
+        .and_then(|config| start_global_hotkeys(state.timer.clone(), config));
+
+    // The integrations keep running while their settings stay the same, so
+    // tweaking something else doesn't reconnect them. The ones that follow
+    // the timer's events need to start over with a different timer though.
+    if timer_changed
+        || state.therun.as_ref().map(TheRun::upload_key) != settings.therun_upload_key.as_deref()
+    {
+        state.therun = settings
+            .therun_upload_key
+            .and_then(|key| TheRun::new(key, &state.timer.read().unwrap()));
+    }
+    if timer_changed
+        || state
+            .stream_markers
+            .as_ref()
+            .map(StreamMarkers::credentials)
+            != settings.twitch_credentials.as_ref()
+    {
+        state.stream_markers = settings
+            .twitch_credentials
+            .and_then(|credentials| StreamMarkers::new(credentials, &state.timer.read().unwrap()));
+    }
+    if timer_changed || state.webhook.as_ref().map(Webhook::config) != settings.webhook.as_ref() {
+        state.webhook = settings
+            .webhook
+            .and_then(|config| Webhook::new(config, &state.timer.read().unwrap()));
+    }
+    if timer_changed || state.mqtt.as_ref().map(Mqtt::config) != settings.mqtt.as_ref() {
+        state.mqtt = settings
+            .mqtt
+            .and_then(|config| Mqtt::new(config, &state.timer.read().unwrap()));
+    }
+    if timer_changed || state.osc.as_ref().map(Osc::config) != settings.osc.as_ref() {
+        // The old socket needs to be closed before its port can be bound again.
+        state.osc = None;
+        state.osc = settings
+            .osc
+            .and_then(|config| start_osc(config, &state.timer.read().unwrap()));
+    }
+    #[cfg(feature = "midi")]
+    if state.midi.as_ref().map(Midi::config) != settings.midi.as_ref() {
+        // Some backends only allow a single connection to a device.
+        state.midi = None;
+        state.midi = settings.midi.and_then(Midi::new);
+    }
+    #[cfg(feature = "gamepad")]
+    if state.gamepad.as_ref().map(Gamepad::config) != settings.gamepad.as_ref() {
+        state.gamepad = settings.gamepad.and_then(Gamepad::new);
+    }
+    if timer_changed
+        || state
+            .rich_presence
+            .as_ref()
+            .map(RichPresence::application_id)
+            != settings.discord_application_id.as_deref()
+    {
+        state.rich_presence = settings
+            .discord_application_id
+            .and_then(|id| RichPresence::new(id, &state.timer.read().unwrap()));
+    }
+    if timer_changed
+        || state.scene_switcher.as_ref().map(SceneSwitcher::config)
+            != settings.scene_switcher.as_ref()
+    {
+        state.scene_switcher = settings
+            .scene_switcher
+            .map(|config| SceneSwitcher::new(config, &state.timer.read().unwrap()));
+    }
+    if state.state_export.as_ref().map(StateExport::config) != settings.state_export.as_ref() {
+        state.state_export = settings.state_export.map(StateExport::new);
+    }
+    state.summary_image = settings
+        .summary_image
+        .map(|config| SummaryImage::new(config, &state.timer.read().unwrap()));
+    if timer_changed || state.recorder.as_ref().map(Recorder::config) != settings.recording.as_ref()
+    {
+        state.recorder = settings
+            .recording
+            .map(|config| Recorder::new(config, &state.timer.read().unwrap()));
+    }
+    if timer_changed
+        || state.audio_cues.as_ref().map(AudioCues::config) != settings.audio_cues.as_ref()
+    {
+        state.audio_cues = settings
+            .audio_cues
+            .map(|config| AudioCues::new(config, &state.timer.read().unwrap()));
+        obs_source_set_audio_active(state.source, state.audio_cues.is_some());
+    }
+    if timer_changed || state.playlist.as_ref().map(Playlist::config) != settings.playlist.as_ref()
+    {
+        state.playlist = settings
+            .playlist
+            .map(|config| Playlist::new(config, &state.timer.read().unwrap()));
+    }
+    state.relay = match (state.relay.take(), settings.relay) {
+        (Some(mut relay), Some(config)) => {
+            relay.reconfigure(config, &mut state.timer.write().unwrap());
+            Some(relay)
+        }
+        (_, config) => config.map(|config| Relay::new(config, &mut state.timer.write().unwrap())),
+    };
+    custom_variables::apply(
+        &mut state.timer.write().unwrap(),
+        &state.custom_variables,
+        &settings.custom_variables,
+    );
+    state.custom_variables = settings.custom_variables;
+    if splits_changed
+        || state.recovery.as_ref().map(Recovery::interval) != settings.recovery_interval
+    {
+        state.recovery = settings
+            .recovery_interval
+            .and_then(|interval| Recovery::new(interval, &state.splits_path));
+    }
+    if timer_changed {
+        state.signals = Signals::new(&state.timer.read().unwrap());
+    }
+    state.practice = settings.practice;
+    if timer_changed || state.death_counter.config() != &settings.death_counter {
+        state.death_counter =
+            DeathCounter::new(settings.death_counter, &state.timer.read().unwrap());
+    }
+    state.media_controls = settings.media_controls;
+    state.interaction = settings.interaction;
+    state.update_interval = settings.update_interval;
+    state.srgb = settings.srgb;
+
+    state.auto_size = settings.auto_size;
+    if state.opacity != settings.opacity {
+        state.opacity = settings.opacity;
+        state.rendered_state = None;
+    }
+    if state.width != settings.width
+        || state.height != settings.height
+        || state.render_scale != settings.render_scale
+    {
         state.width = settings.width;
         state.height = settings.height;
+        state.render_scale = settings.render_scale;
 
         obs_enter_graphics();
-        let mut texture = gs_texture_create(
-            state.width,
-            state.height,
-            GS_RGBA,
-            1,
-            ptr::null_mut(),
-            GS_DYNAMIC,
-        );
-        mem::swap(&mut state.texture, &mut texture);
-        gs_texture_destroy(texture);
+        state.recreate_texture();
         obs_leave_graphics();
     }
 }
@@ -703,6 +3188,14 @@ impl Log for ObsLog {
 
     fn log(&self, record: &Record) {
         if self.enabled(record.metadata()) {
+            #[cfg(feature = "auto-splitting")]
+            if auto_splitter_status::is_auto_splitter(record.target()) {
+                auto_splitter_status::record(record);
+                if let Some(level) = auto_splitter_log::obs_level(record.level()) {
+                    log(level, record.target(), record.args());
+                }
+                return;
+            }
             log(record.level(), record.target(), record.args());
         }
     }
@@ -710,12 +3203,29 @@ impl Log for ObsLog {
     fn flush(&self) {}
 }
 
+#[no_mangle]
+pub extern "C" fn obs_module_post_load() {
+    unsafe {
+        obs_websocket::register();
+        run_manager::add_tools_menu_item();
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn obs_module_unload() {
+    unsafe {
+        obs_websocket::unregister();
+        run_manager::unload();
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn obs_module_load() -> bool {
     static SOURCE_INFO: UnsafeMultiThread<obs_source_info> = UnsafeMultiThread(obs_source_info {
         id: cstr!("livesplit-one"),
         type_: OBS_SOURCE_TYPE_INPUT,
         output_flags: OBS_SOURCE_VIDEO
+            | OBS_SOURCE_AUDIO
             | OBS_SOURCE_CUSTOM_DRAW
             | OBS_SOURCE_INTERACTION
             | OBS_SOURCE_CONTROLLABLE_MEDIA,
@@ -734,16 +3244,16 @@ pub extern "C" fn obs_module_load() -> bool {
         deactivate: None,
         show: None,
         hide: None,
-        video_tick: None,
+        video_tick: Some(video_tick),
         filter_video: None,
         filter_audio: None,
         enum_active_sources: None,
-        save: None,
-        load: None,
-        mouse_click: None,
+        save: Some(save),
+        load: Some(load),
+        mouse_click: Some(mouse_click),
         mouse_move: None,
-        focus: None,
-        key_click: None,
+        focus: Some(focus),
+        key_click: Some(key_click),
         filter_remove: None,
         type_data: ptr::null_mut(),
         free_type_data: None,
@@ -761,19 +3271,22 @@ pub extern "C" fn obs_module_load() -> bool {
         media_previous: Some(media_previous),
         media_get_duration: Some(media_get_duration),
         media_get_time: Some(media_get_time),
-        media_set_time: None,
+        media_set_time: Some(media_set_time),
         media_get_state: Some(media_get_state),
         version: 0,
         unversioned_id: ptr::null(),
+        missing_files: Some(missing_files::missing_files),
+        video_get_color_space: Some(video_get_color_space),
     });
 
     let _ = log::set_logger(&ObsLog);
     log::set_max_level(LevelFilter::Debug);
 
-    let source_info: &obs_source_info = &SOURCE_INFO.0;
-
     unsafe {
-        obs_register_source_s(source_info, mem::size_of_val(source_info) as _);
+        register_source(&SOURCE_INFO.0);
     }
+    controller::register();
+    component::register();
+    run_manager::register();
     true
 }
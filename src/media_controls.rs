@@ -0,0 +1,137 @@
+use std::{ffi::CStr, os::raw::c_char};
+
+use livesplit_core::{analysis::sum_of_segments, TimeSpan, Timer};
+
+use crate::{
+    command::Command,
+    ffi::{
+        obs_data_get_string, obs_data_set_default_string, obs_data_t, obs_properties_add_group,
+        obs_properties_add_list, obs_properties_create, obs_properties_t,
+        obs_property_list_add_string, OBS_COMBO_FORMAT_STRING, OBS_COMBO_TYPE_LIST,
+        OBS_GROUP_NORMAL,
+    },
+};
+
+const SETTINGS_MEDIA_CONTROLS: *const c_char = cstr!("media_controls");
+const SETTINGS_MEDIA_NEXT_PREVIOUS: *const c_char = cstr!("media_next_previous");
+const SETTINGS_MEDIA_STOP: *const c_char = cstr!("media_stop");
+const SETTINGS_MEDIA_DURATION: *const c_char = cstr!("media_duration");
+
+/// What OBS' media controls, which Stream Decks and media keys use as well, do
+/// to the timer.
+pub struct Config {
+    comparisons: bool,
+    stop_pauses: bool,
+    best_possible_duration: bool,
+}
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let group = obs_properties_create();
+    let next_previous = obs_properties_add_list(
+        group,
+        SETTINGS_MEDIA_NEXT_PREVIOUS,
+        cstr!("Next and Previous"),
+        OBS_COMBO_TYPE_LIST,
+        OBS_COMBO_FORMAT_STRING,
+    );
+    obs_property_list_add_string(
+        next_previous,
+        cstr!("Split and Undo Split"),
+        cstr!("splits"),
+    );
+    obs_property_list_add_string(
+        next_previous,
+        cstr!("Switch Comparisons"),
+        cstr!("comparisons"),
+    );
+    let stop = obs_properties_add_list(
+        group,
+        SETTINGS_MEDIA_STOP,
+        cstr!("Stop"),
+        OBS_COMBO_TYPE_LIST,
+        OBS_COMBO_FORMAT_STRING,
+    );
+    obs_property_list_add_string(stop, cstr!("Reset"), cstr!("reset"));
+    obs_property_list_add_string(stop, cstr!("Pause"), cstr!("pause"));
+    let duration = obs_properties_add_list(
+        group,
+        SETTINGS_MEDIA_DURATION,
+        cstr!("Duration Without a Personal Best"),
+        OBS_COMBO_TYPE_LIST,
+        OBS_COMBO_FORMAT_STRING,
+    );
+    obs_property_list_add_string(duration, cstr!("Sum of Best"), cstr!("sum_of_best"));
+    obs_property_list_add_string(
+        duration,
+        cstr!("Best Possible Time"),
+        cstr!("best_possible"),
+    );
+
+    obs_properties_add_group(
+        props,
+        SETTINGS_MEDIA_CONTROLS,
+        cstr!("Media Controls"),
+        OBS_GROUP_NORMAL,
+        group,
+    );
+}
+
+pub unsafe fn set_defaults(settings: *mut obs_data_t) {
+    obs_data_set_default_string(settings, SETTINGS_MEDIA_NEXT_PREVIOUS, cstr!("splits"));
+    obs_data_set_default_string(settings, SETTINGS_MEDIA_STOP, cstr!("reset"));
+    obs_data_set_default_string(settings, SETTINGS_MEDIA_DURATION, cstr!("sum_of_best"));
+}
+
+pub unsafe fn parse_settings(settings: *mut obs_data_t) -> Config {
+    let get = |name| CStr::from_ptr(obs_data_get_string(settings, name)).to_bytes();
+    Config {
+        comparisons: get(SETTINGS_MEDIA_NEXT_PREVIOUS) == b"comparisons",
+        stop_pauses: get(SETTINGS_MEDIA_STOP) == b"pause",
+        best_possible_duration: get(SETTINGS_MEDIA_DURATION) == b"best_possible",
+    }
+}
+
+impl Config {
+    pub fn next(&self) -> Command {
+        if self.comparisons {
+            Command::SwitchToNextComparison
+        } else {
+            Command::Split
+        }
+    }
+
+    pub fn previous(&self) -> Command {
+        if self.comparisons {
+            Command::SwitchToPreviousComparison
+        } else {
+            Command::UndoSplit
+        }
+    }
+
+    pub fn stop(&self) -> Command {
+        if self.stop_pauses {
+            Command::Pause
+        } else {
+            Command::Reset { save_attempt: None }
+        }
+    }
+
+    /// The length of the run that the media progress bar shows. Without a
+    /// personal best, the best segments give an idea of it instead.
+    pub fn duration(&self, timer: &Timer) -> TimeSpan {
+        let method = timer.current_timing_method();
+        let segments = timer.run().segments();
+        segments
+            .last()
+            .and_then(|segment| segment.personal_best_split_time()[method])
+            .or_else(|| {
+                sum_of_segments::calculate_best(
+                    segments,
+                    false,
+                    self.best_possible_duration,
+                    method,
+                )
+            })
+            .unwrap_or_default()
+    }
+}
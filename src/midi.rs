@@ -0,0 +1,233 @@
+use std::{
+    ffi::{CStr, CString},
+    mem,
+    os::raw::c_char,
+    sync::mpsc,
+};
+
+use midir::{Ignore, MidiInput, MidiInputConnection};
+
+use crate::{
+    command::Command,
+    ffi::{
+        obs_data_get_string, obs_data_t, obs_properties_add_group, obs_properties_add_list,
+        obs_properties_add_text, obs_properties_create, obs_properties_t,
+        obs_property_list_add_string, obs_property_set_long_description, OBS_COMBO_FORMAT_STRING,
+        OBS_COMBO_TYPE_LIST, OBS_GROUP_NORMAL, OBS_TEXT_DEFAULT,
+    },
+};
+
+const SETTINGS_MIDI: *const c_char = cstr!("midi");
+const SETTINGS_MIDI_DEVICE: *const c_char = cstr!("midi_device");
+
+const CLIENT_NAME: &str = "LiveSplit One";
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Action {
+    Split,
+    Undo,
+    Skip,
+    Reset,
+    Pause,
+}
+
+/// The settings of the actions, along with their names in the properties.
+const ACTIONS: [(*const c_char, *const c_char, Action); 5] = [
+    (cstr!("midi_split"), cstr!("Start / Split"), Action::Split),
+    (cstr!("midi_undo"), cstr!("Undo Split"), Action::Undo),
+    (cstr!("midi_skip"), cstr!("Skip Split"), Action::Skip),
+    (cstr!("midi_reset"), cstr!("Reset"), Action::Reset),
+    (cstr!("midi_pause"), cstr!("Pause"), Action::Pause),
+];
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Control {
+    Note(u8),
+    ControlChange(u8),
+}
+
+#[derive(PartialEq, Eq)]
+pub struct Config {
+    device: String,
+    bindings: Vec<(Control, Action)>,
+}
+
+/// Triggers timer actions with the notes and control changes of a MIDI
+/// controller, so pads can be used without fighting the game over hotkeys.
+/// The messages arrive on a thread of the MIDI backend and are handled when
+/// polled.
+pub struct Midi {
+    config: Config,
+    _connection: MidiInputConnection<()>,
+    receiver: mpsc::Receiver<Control>,
+}
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let group = obs_properties_create();
+    let device = obs_properties_add_list(
+        group,
+        SETTINGS_MIDI_DEVICE,
+        cstr!("Device"),
+        OBS_COMBO_TYPE_LIST,
+        OBS_COMBO_FORMAT_STRING,
+    );
+    obs_property_list_add_string(device, cstr!("Off"), cstr!(""));
+    if let Ok(input) = MidiInput::new(CLIENT_NAME) {
+        for port in input.ports() {
+            if let Ok(Ok(name)) = input.port_name(&port).map(CString::new) {
+                obs_property_list_add_string(device, name.as_ptr(), name.as_ptr());
+            }
+        }
+    }
+
+    for (setting, name, _) in ACTIONS {
+        let action = obs_properties_add_text(group, setting, name, OBS_TEXT_DEFAULT);
+        obs_property_set_long_description(
+            action,
+            cstr!("The note or control change that triggers this, like \"Note 36\" or \"CC 64\". Leave it empty to not use it."),
+        );
+    }
+
+    obs_properties_add_group(props, SETTINGS_MIDI, cstr!("MIDI"), OBS_GROUP_NORMAL, group);
+}
+
+/// Parses a control like `Note 36` or `CC 64`. A plain number is a note.
+fn parse_control(control: &str) -> Option<Control> {
+    let control = control.trim().to_ascii_lowercase();
+    let mut parts = control.split_whitespace();
+    let (kind, number) = match (parts.next()?, parts.next()) {
+        (kind, Some(number)) => (kind, number),
+        (number, None) => ("note", number),
+    };
+    let number = number.parse::<u8>().ok().filter(|&number| number < 128)?;
+    match kind {
+        "note" => Some(Control::Note(number)),
+        "cc" => Some(Control::ControlChange(number)),
+        _ => None,
+    }
+}
+
+pub unsafe fn parse_settings(settings: *mut obs_data_t) -> Option<Config> {
+    let get = |name| {
+        CStr::from_ptr(obs_data_get_string(settings, name))
+            .to_string_lossy()
+            .into_owned()
+    };
+    let device = get(SETTINGS_MIDI_DEVICE);
+    if device.is_empty() {
+        return None;
+    }
+    let bindings = ACTIONS
+        .iter()
+        .filter_map(|&(setting, _, action)| {
+            let control = get(setting);
+            if control.trim().is_empty() {
+                return None;
+            }
+            let parsed = parse_control(&control);
+            if parsed.is_none() {
+                log::warn!("{control:?} is not a MIDI note or control change.");
+            }
+            Some((parsed?, action))
+        })
+        .collect();
+    Some(Config { device, bindings })
+}
+
+/// Turns a MIDI message into the control that was pressed, if any. Releases,
+/// like notes with a velocity of 0 and control changes below the middle,
+/// aren't presses.
+fn pressed(message: &[u8]) -> Option<Control> {
+    match *message {
+        [status, note, velocity] if status & 0xF0 == 0x90 && velocity > 0 => {
+            Some(Control::Note(note))
+        }
+        [status, controller, value] if status & 0xF0 == 0xB0 && value >= 64 => {
+            Some(Control::ControlChange(controller))
+        }
+        _ => None,
+    }
+}
+
+impl Action {
+    fn command(self) -> Command {
+        match self {
+            Action::Split => Command::SplitOrStart,
+            Action::Undo => Command::UndoSplit,
+            Action::Skip => Command::SkipSplit,
+            Action::Reset => Command::Reset { save_attempt: None },
+            Action::Pause => Command::TogglePauseOrStart,
+        }
+    }
+}
+
+impl Midi {
+    pub fn new(config: Config) -> Option<Self> {
+        let device = &config.device;
+        let mut input = MidiInput::new(CLIENT_NAME)
+            .map_err(|e| log::warn!("Failed to access MIDI: {e}"))
+            .ok()?;
+        input.ignore(Ignore::All);
+        let Some(port) = input
+            .ports()
+            .into_iter()
+            .find(|port| input.port_name(port).map_or(false, |name| name == *device))
+        else {
+            log::warn!("The MIDI device {device} isn't connected.");
+            return None;
+        };
+
+        let (sender, receiver) = mpsc::channel();
+        // Pedals and faders send lots of control changes while they are held,
+        // so only the moment they cross the middle counts as a press.
+        let mut held = [false; 128];
+        let connection = input
+            .connect(
+                &port,
+                "LiveSplit One Input",
+                move |_, message, _| {
+                    if let [status, controller, value] = *message {
+                        if status & 0xF0 == 0xB0 {
+                            let controller = controller as usize & 0x7F;
+                            let was_held = mem::replace(&mut held[controller], value >= 64);
+                            if was_held {
+                                return;
+                            }
+                        }
+                    }
+                    if let Some(control) = pressed(message) {
+                        let _ = sender.send(control);
+                    }
+                },
+                (),
+            )
+            .map_err(|e| log::warn!("Failed to connect to the MIDI device {device}: {e}"))
+            .ok()?;
+        log::info!("Listening to the MIDI device {device}.");
+
+        Some(Self {
+            config,
+            _connection: connection,
+            receiver,
+        })
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Returns the commands of the controls that were pressed since the last
+    /// call.
+    pub fn poll(&self) -> Vec<Command> {
+        self.receiver
+            .try_iter()
+            .flat_map(|control| {
+                self.config
+                    .bindings
+                    .iter()
+                    .filter(move |&&(bound, _)| bound == control)
+                    .map(|&(_, action)| action.command())
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,68 @@
+use std::{ffi::c_void, os::raw::c_char};
+
+use crate::{
+    ffi::{
+        obs_data_create, obs_data_get_string, obs_data_release, obs_data_set_string,
+        obs_missing_file_create, obs_missing_files_add_file, obs_missing_files_create,
+        obs_missing_files_t, obs_source_get_settings, obs_source_t, obs_source_update,
+        OBS_MISSING_FILE_SOURCE,
+    },
+    path, remote_splits, State, SETTINGS_ALTERNATE_LAYOUT_PATH, SETTINGS_LAYOUT_PATH,
+    SETTINGS_SPLITS_PATH,
+};
+
+/// The settings holding the files that the source loads.
+#[cfg(feature = "auto-splitting")]
+const FILES: [*const c_char; 4] = [
+    SETTINGS_SPLITS_PATH,
+    SETTINGS_LAYOUT_PATH,
+    SETTINGS_ALTERNATE_LAYOUT_PATH,
+    crate::SETTINGS_AUTO_SPLITTER_PATH,
+];
+#[cfg(not(feature = "auto-splitting"))]
+const FILES: [*const c_char; 3] = [
+    SETTINGS_SPLITS_PATH,
+    SETTINGS_LAYOUT_PATH,
+    SETTINGS_ALTERNATE_LAYOUT_PATH,
+];
+
+/// Stores the path that the user chose in OBS' Missing Files dialog.
+unsafe extern "C" fn relocate(src: *mut c_void, new_path: *const c_char, data: *mut c_void) {
+    let source: *mut obs_source_t = src.cast();
+    let settings = obs_data_create();
+    obs_data_set_string(settings, data.cast(), new_path);
+    obs_source_update(source, settings);
+    obs_data_release(settings);
+}
+
+/// Reports the files of the source that don't exist anymore, so that OBS can
+/// ask the user where they went.
+pub unsafe extern "C" fn missing_files(data: *mut c_void) -> *mut obs_missing_files_t {
+    let state: &mut State = &mut *data.cast();
+    let files = obs_missing_files_create();
+
+    let settings = obs_source_get_settings(state.source);
+    // Downloaded splits don't come from the path in the settings.
+    let uses_remote_splits = !remote_splits::get_url(settings).is_empty();
+    for name in FILES {
+        if name == SETTINGS_SPLITS_PATH && uses_remote_splits {
+            continue;
+        }
+        let path = obs_data_get_string(settings, name);
+        let path_buf = path::from_obs(path);
+        if path_buf.as_os_str().is_empty() || path_buf.exists() {
+            continue;
+        }
+        let file = obs_missing_file_create(
+            path,
+            Some(relocate),
+            OBS_MISSING_FILE_SOURCE,
+            state.source.cast(),
+            name as *mut c_void,
+        );
+        obs_missing_files_add_file(files, file);
+    }
+    obs_data_release(settings);
+
+    files
+}
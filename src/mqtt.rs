@@ -0,0 +1,262 @@
+use std::{
+    ffi::CStr,
+    mem,
+    os::raw::c_char,
+    process,
+    sync::{
+        atomic::{self, AtomicBool},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use livesplit_core::{
+    timing::formatter::{Complete, TimeFormatter},
+    Timer,
+};
+use rumqttc::{Client, Connection, Event, MqttOptions, Packet, QoS, Transport};
+use serde_json::json;
+
+use crate::{
+    events::{self, EventTracker, TimerEvent},
+    ffi::{
+        obs_data_get_bool, obs_data_get_string, obs_data_set_default_string, obs_data_t,
+        obs_properties_add_bool, obs_properties_add_group, obs_properties_add_text,
+        obs_properties_create, obs_properties_t, obs_property_set_long_description,
+        OBS_GROUP_NORMAL, OBS_TEXT_DEFAULT, OBS_TEXT_PASSWORD,
+    },
+};
+
+const SETTINGS_MQTT: *const c_char = cstr!("mqtt");
+const SETTINGS_MQTT_BROKER: *const c_char = cstr!("mqtt_broker");
+const SETTINGS_MQTT_TOPIC: *const c_char = cstr!("mqtt_topic");
+const SETTINGS_MQTT_USERNAME: *const c_char = cstr!("mqtt_username");
+const SETTINGS_MQTT_PASSWORD: *const c_char = cstr!("mqtt_password");
+const SETTINGS_MQTT_TLS: *const c_char = cstr!("mqtt_tls");
+
+const DEFAULT_PORT: u16 = 1883;
+const DEFAULT_TLS_PORT: u16 = 8883;
+/// The broker closes connections that are quiet for one and a half times this
+/// long, so a ping is sent whenever nothing was published for that long.
+const KEEP_ALIVE: Duration = Duration::from_secs(60);
+/// How many messages may wait for the connection before new ones are dropped.
+const QUEUE_CAPACITY: usize = 64;
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+#[derive(PartialEq, Eq)]
+pub struct Config {
+    broker: String,
+    topic: String,
+    username: String,
+    password: String,
+    tls: bool,
+}
+
+/// Publishes the events of the timer to an MQTT broker, so home automation
+/// and other subscribers can react to them. Every event is published to a
+/// topic of its own below the configured one. The connection is kept on a
+/// separate thread, so it doesn't block rendering.
+pub struct Mqtt {
+    config: Config,
+    tracker: EventTracker,
+    client: Client,
+    closed: Arc<AtomicBool>,
+}
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let group = obs_properties_create();
+    let broker = obs_properties_add_text(
+        group,
+        SETTINGS_MQTT_BROKER,
+        cstr!("Broker"),
+        OBS_TEXT_DEFAULT,
+    );
+    obs_property_set_long_description(
+        broker,
+        cstr!("The address of the MQTT broker, e.g. 192.168.0.2:1883. Leave it empty to not publish anything."),
+    );
+    obs_properties_add_text(group, SETTINGS_MQTT_TOPIC, cstr!("Topic"), OBS_TEXT_DEFAULT);
+    obs_properties_add_text(
+        group,
+        SETTINGS_MQTT_USERNAME,
+        cstr!("Username"),
+        OBS_TEXT_DEFAULT,
+    );
+    obs_properties_add_text(
+        group,
+        SETTINGS_MQTT_PASSWORD,
+        cstr!("Password"),
+        OBS_TEXT_PASSWORD,
+    );
+    let tls = obs_properties_add_bool(group, SETTINGS_MQTT_TLS, cstr!("Use TLS"));
+    obs_property_set_long_description(
+        tls,
+        cstr!("Encrypts the connection to the broker. The broker's certificate is checked against the certificates the system trusts. Without a port, 8883 is used instead of 1883."),
+    );
+
+    obs_properties_add_group(props, SETTINGS_MQTT, cstr!("MQTT"), OBS_GROUP_NORMAL, group);
+}
+
+pub unsafe fn set_defaults(settings: *mut obs_data_t) {
+    obs_data_set_default_string(settings, SETTINGS_MQTT_TOPIC, cstr!("livesplit"));
+}
+
+pub unsafe fn parse_settings(settings: *mut obs_data_t) -> Option<Config> {
+    let get = |name| {
+        CStr::from_ptr(obs_data_get_string(settings, name))
+            .to_string_lossy()
+            .trim()
+            .to_owned()
+    };
+    let broker = get(SETTINGS_MQTT_BROKER);
+    if broker.is_empty() {
+        return None;
+    }
+    Some(Config {
+        broker,
+        topic: get(SETTINGS_MQTT_TOPIC).trim_end_matches('/').to_owned(),
+        username: get(SETTINGS_MQTT_USERNAME),
+        password: get(SETTINGS_MQTT_PASSWORD),
+        tls: obs_data_get_bool(settings, SETTINGS_MQTT_TLS),
+    })
+}
+
+/// Sets up the connection to the broker. The address may leave out the port,
+/// in which case the usual one is used.
+fn options(config: &Config) -> Option<MqttOptions> {
+    let (host, port) = match config.broker.rsplit_once(':') {
+        Some((host, port)) => match port.parse() {
+            Ok(port) => (host, port),
+            Err(_) => {
+                log::warn!("Invalid MQTT broker: {}", config.broker);
+                return None;
+            }
+        },
+        None if config.tls => (&*config.broker, DEFAULT_TLS_PORT),
+        None => (&*config.broker, DEFAULT_PORT),
+    };
+    let mut options = MqttOptions::new(format!("obs-livesplit-one-{}", process::id()), host, port);
+    options.set_keep_alive(KEEP_ALIVE);
+    if !config.username.is_empty() {
+        options.set_credentials(&config.username, &config.password);
+    }
+    if config.tls {
+        options.set_transport(Transport::tls_with_default_config());
+    }
+    Some(options)
+}
+
+impl Mqtt {
+    pub fn new(config: Config, timer: &Timer) -> Option<Self> {
+        let (client, connection) = Client::new(options(&config)?, QUEUE_CAPACITY);
+        let closed = Arc::new(AtomicBool::new(false));
+
+        thread::Builder::new()
+            .name("MQTT".into())
+            .spawn({
+                let broker = config.broker.clone();
+                let closed = closed.clone();
+                move || run(&broker, connection, &closed)
+            })
+            .map_err(|e| log::warn!("Failed to start MQTT: {e}"))
+            .ok()?;
+
+        Some(Self {
+            config,
+            tracker: EventTracker::new(timer),
+            client,
+            closed,
+        })
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn poll(&mut self, timer: &Timer) {
+        for event in self.tracker.poll(timer) {
+            // Unlike with the webhook, golds are published in addition to the
+            // split, as subscribers pick the topics they care about anyway.
+            match event {
+                TimerEvent::Started => self.publish(timer, "start", None),
+                TimerEvent::Splitted { index } => {
+                    self.publish(timer, "split", Some(index));
+                    if events::is_gold(timer, index) {
+                        self.publish(timer, "gold", Some(index));
+                    }
+                }
+                TimerEvent::Finished => {
+                    self.publish(timer, "finish", None);
+                    let time = timer.current_time()[timer.current_timing_method()];
+                    self.send("final_time", Complete.format(time).to_string(), true);
+                }
+                TimerEvent::Reset => self.publish(timer, "reset", None),
+                _ => {}
+            }
+        }
+    }
+
+    fn publish(&self, timer: &Timer, event: &str, segment: Option<usize>) {
+        let run = timer.run();
+        let method = timer.current_timing_method();
+        let split = segment.map(|index| run.segment(index));
+        let payload = json!({
+            "event": event,
+            "game": run.game_name(),
+            "category": run.category_name(),
+            "segmentIndex": segment,
+            "segment": split.map(|segment| segment.name()),
+            "time": Complete.format(timer.current_time()[method]).to_string(),
+            "splitTime": split.map(|segment| {
+                Complete.format(segment.split_time()[method]).to_string()
+            }),
+            "personalBest": event == "finish" && events::is_personal_best(timer),
+        });
+        self.send(event, payload.to_string(), false);
+    }
+
+    fn send(&self, event: &str, payload: String, retain: bool) {
+        let topic = if self.config.topic.is_empty() {
+            event.to_owned()
+        } else {
+            format!("{}/{event}", self.config.topic)
+        };
+        // Publishing never waits for the connection, so messages are dropped
+        // if the broker can't be reached for a while.
+        let _ = self
+            .client
+            .try_publish(topic, QoS::AtMostOnce, retain, payload);
+    }
+}
+
+impl Drop for Mqtt {
+    fn drop(&mut self) {
+        self.closed.store(true, atomic::Ordering::Relaxed);
+        let _ = self.client.try_disconnect();
+    }
+}
+
+/// Drives the connection until the client is dropped. Polling the connection
+/// again after an error connects to the broker again.
+fn run(broker: &str, mut connection: Connection, closed: &AtomicBool) {
+    let mut warned = false;
+    for notification in connection.iter() {
+        match notification {
+            Ok(Event::Incoming(Packet::ConnAck(_))) => warned = false,
+            Ok(_) => {}
+            Err(e) => {
+                // While the broker can't be reached, connecting is all the
+                // connection does, so it never notices that the client is
+                // gone.
+                if closed.load(atomic::Ordering::Relaxed) {
+                    break;
+                }
+                if !mem::replace(&mut warned, true) {
+                    log::warn!("Failed to connect to the MQTT broker {broker}: {e}");
+                }
+                thread::sleep(RECONNECT_DELAY);
+            }
+        }
+    }
+}
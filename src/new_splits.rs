@@ -0,0 +1,136 @@
+use std::{
+    ffi::{c_void, CStr},
+    fs::File,
+    io::BufWriter,
+    os::raw::c_char,
+    path::Path,
+    ptr,
+};
+
+use livesplit_core::{
+    run::saver::livesplit::{save_run, IoWrite},
+    Run, Segment,
+};
+
+use crate::{
+    ffi::{
+        obs_data_get_string, obs_data_release, obs_data_t, obs_properties_add_button,
+        obs_properties_add_group, obs_properties_add_path, obs_properties_add_text,
+        obs_properties_create, obs_properties_t, obs_property_t, obs_source_get_settings,
+        OBS_GROUP_NORMAL, OBS_PATH_FILE_SAVE, OBS_TEXT_DEFAULT, OBS_TEXT_MULTILINE,
+    },
+    path, playlist, State,
+};
+
+const SETTINGS_NEW_SPLITS: *const c_char = cstr!("new_splits");
+const SETTINGS_NEW_SPLITS_GAME: *const c_char = cstr!("new_splits_game");
+const SETTINGS_NEW_SPLITS_CATEGORY: *const c_char = cstr!("new_splits_category");
+const SETTINGS_NEW_SPLITS_SEGMENTS: *const c_char = cstr!("new_splits_segments");
+const SETTINGS_NEW_SPLITS_PATH: *const c_char = cstr!("new_splits_path");
+const SETTINGS_NEW_SPLITS_CREATE: *const c_char = cstr!("new_splits_create");
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let group = obs_properties_create();
+    obs_properties_add_text(
+        group,
+        SETTINGS_NEW_SPLITS_GAME,
+        cstr!("Game"),
+        OBS_TEXT_DEFAULT,
+    );
+    obs_properties_add_text(
+        group,
+        SETTINGS_NEW_SPLITS_CATEGORY,
+        cstr!("Category"),
+        OBS_TEXT_DEFAULT,
+    );
+    obs_properties_add_text(
+        group,
+        SETTINGS_NEW_SPLITS_SEGMENTS,
+        cstr!("Segments (one per line)"),
+        OBS_TEXT_MULTILINE,
+    );
+    obs_properties_add_path(
+        group,
+        SETTINGS_NEW_SPLITS_PATH,
+        cstr!("Save to"),
+        OBS_PATH_FILE_SAVE,
+        cstr!("LiveSplit Splits (*.lss)"),
+        ptr::null(),
+    );
+    obs_properties_add_button(
+        group,
+        SETTINGS_NEW_SPLITS_CREATE,
+        cstr!("Create and Use"),
+        Some(create),
+    );
+
+    obs_properties_add_group(
+        props,
+        SETTINGS_NEW_SPLITS,
+        cstr!("Create New Splits"),
+        OBS_GROUP_NORMAL,
+        group,
+    );
+}
+
+unsafe fn get_string(settings: *mut obs_data_t, name: *const c_char) -> String {
+    CStr::from_ptr(obs_data_get_string(settings, name))
+        .to_string_lossy()
+        .trim()
+        .to_owned()
+}
+
+/// Builds the run from the game, category and segments in the settings.
+unsafe fn build_run(settings: *mut obs_data_t) -> Run {
+    let mut run = Run::new();
+    run.set_game_name(get_string(settings, SETTINGS_NEW_SPLITS_GAME));
+    run.set_category_name(get_string(settings, SETTINGS_NEW_SPLITS_CATEGORY));
+    for name in get_string(settings, SETTINGS_NEW_SPLITS_SEGMENTS)
+        .lines()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+    {
+        run.push_segment(Segment::new(name));
+    }
+    if run.is_empty() {
+        run.push_segment(Segment::new("Time"));
+    }
+    run
+}
+
+fn write_run(run: &Run, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let file = File::create(path)?;
+    save_run(run, IoWrite(BufWriter::new(file)))?;
+    Ok(())
+}
+
+unsafe extern "C" fn create(
+    _: *mut obs_properties_t,
+    _: *mut obs_property_t,
+    data: *mut c_void,
+) -> bool {
+    let state: &mut State = &mut *data.cast();
+    let settings = obs_source_get_settings(state.source);
+    let run = build_run(settings);
+    let path = path::from_obs(obs_data_get_string(settings, SETTINGS_NEW_SPLITS_PATH));
+    obs_data_release(settings);
+
+    if path.as_os_str().is_empty() {
+        log::warn!("Choose where to save the new splits first.");
+        return false;
+    }
+    // Overwriting existing splits would lose their history.
+    if path.exists() {
+        log::warn!("The splits {} already exist.", path.display());
+        return false;
+    }
+    if let Err(e) = write_run(&run, &path) {
+        log::warn!("Failed to create the splits: {e}");
+        return false;
+    }
+    log::info!("Created the splits {}.", path.display());
+
+    state.save_splits();
+    playlist::switch_to(state.source, &path);
+    true
+}
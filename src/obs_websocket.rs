@@ -0,0 +1,267 @@
+// A port of the inline functions from obs-websocket's obs-websocket-api.h,
+// which talks to obs-websocket through its proc handler.
+
+#![allow(non_camel_case_types)]
+
+use std::{
+    ffi::{c_void, CStr, CString},
+    path::{Path, PathBuf},
+    ptr,
+    sync::{
+        atomic::{self, AtomicPtr},
+        Mutex, RwLock, Weak,
+    },
+};
+
+use livesplit_core::{
+    timing::formatter::{Complete, TimeFormatter},
+    SharedTimer, Timer,
+};
+use serde_json::{json, Value};
+
+use crate::{
+    calldata::{self, CallData},
+    canonical_path,
+    command::{self, Command},
+    events::{EventTracker, TimerEvent},
+    ffi::{
+        obs_add_tick_callback, obs_data_apply, obs_data_create_from_json, obs_data_get_json,
+        obs_data_release, obs_data_t, obs_get_proc_handler, obs_remove_tick_callback,
+        proc_handler_call, proc_handler_t,
+    },
+    TIMERS,
+};
+
+type obs_websocket_request_callback_function = unsafe extern "C" fn(
+    request_data: *mut obs_data_t,
+    response_data: *mut obs_data_t,
+    priv_data: *mut c_void,
+);
+
+#[repr(C)]
+struct obs_websocket_request_callback {
+    callback: obs_websocket_request_callback_function,
+    priv_data: *mut c_void,
+}
+
+const VENDOR_NAME: &CStr = unsafe { CStr::from_bytes_with_nul_unchecked(b"livesplit-one\0") };
+
+/// The requests that are available through the vendor, and the commands they
+/// map to.
+static REQUESTS: [(&[u8], &str); 18] = [
+    (b"Split\0", "split"),
+    (b"SplitOrStart\0", "splitOrStart"),
+    (b"Start\0", "start"),
+    (b"Reset\0", "reset"),
+    (b"UndoSplit\0", "undoSplit"),
+    (b"SkipSplit\0", "skipSplit"),
+    (b"TogglePause\0", "togglePauseOrStart"),
+    (b"Pause\0", "pause"),
+    (b"Resume\0", "resume"),
+    (b"UndoAllPauses\0", "undoAllPauses"),
+    (b"PreviousComparison\0", "switchToPreviousComparison"),
+    (b"NextComparison\0", "switchToNextComparison"),
+    (b"SetCurrentComparison\0", "setCurrentComparison"),
+    (b"ToggleTimingMethod\0", "toggleTimingMethod"),
+    (b"SetGameTime\0", "setGameTime"),
+    (b"GetCurrentTime\0", "getCurrentTime"),
+    (b"GetCurrentSplitName\0", "getCurrentSplitName"),
+    (b"GetCurrentState\0", "getCurrentState"),
+];
+
+static PROC_HANDLER: AtomicPtr<proc_handler_t> = AtomicPtr::new(ptr::null_mut());
+static VENDOR: AtomicPtr<c_void> = AtomicPtr::new(ptr::null_mut());
+
+#[allow(clippy::type_complexity)]
+static TRACKERS: Mutex<Vec<(Weak<RwLock<Timer>>, EventTracker)>> = Mutex::new(Vec::new());
+
+/// Registers the vendor with obs-websocket. This needs to happen after all
+/// modules are loaded, as obs-websocket may not be loaded yet otherwise.
+pub unsafe fn register() {
+    let mut cd = CallData::default();
+    if !proc_handler_call(
+        obs_get_proc_handler(),
+        cstr!("obs_websocket_api_get_ph"),
+        cd.as_mut_ptr(),
+    ) {
+        log::debug!("obs-websocket is not installed, skipping vendor registration.");
+        return;
+    }
+    let ph: *mut proc_handler_t = calldata::get_ptr(cd.as_mut_ptr(), cstr!("ph")).cast();
+    if ph.is_null() {
+        return;
+    }
+    PROC_HANDLER.store(ph, atomic::Ordering::Relaxed);
+
+    let mut cd = CallData::default();
+    calldata::set_string(cd.as_mut_ptr(), cstr!("name"), VENDOR_NAME);
+    proc_handler_call(ph, cstr!("vendor_register"), cd.as_mut_ptr());
+    let vendor = calldata::get_ptr(cd.as_mut_ptr(), cstr!("vendor"));
+    if vendor.is_null() {
+        log::warn!("Failed to register the obs-websocket vendor.");
+        return;
+    }
+    VENDOR.store(vendor, atomic::Ordering::Relaxed);
+
+    for request in &REQUESTS {
+        let callback = obs_websocket_request_callback {
+            callback: handle_request,
+            priv_data: (request as *const (&[u8], &str)) as *mut c_void,
+        };
+        let mut cd = CallData::default();
+        calldata::set_string(
+            cd.as_mut_ptr(),
+            cstr!("type"),
+            CStr::from_bytes_with_nul_unchecked(request.0),
+        );
+        calldata::set_ptr(
+            cd.as_mut_ptr(),
+            cstr!("callback"),
+            (&callback as *const obs_websocket_request_callback) as *mut c_void,
+        );
+        calldata::set_ptr(cd.as_mut_ptr(), cstr!("vendor"), vendor);
+        proc_handler_call(ph, cstr!("vendor_request_register"), cd.as_mut_ptr());
+        if !calldata::get_bool(cd.as_mut_ptr(), cstr!("success")) {
+            log::warn!(
+                "Failed to register the obs-websocket request {}.",
+                request.1
+            );
+        }
+    }
+
+    obs_add_tick_callback(Some(tick), ptr::null_mut());
+
+    log::info!("Registered the obs-websocket vendor.");
+}
+
+pub unsafe fn unregister() {
+    if !VENDOR.load(atomic::Ordering::Relaxed).is_null() {
+        obs_remove_tick_callback(Some(tick), ptr::null_mut());
+    }
+}
+
+fn find_timer(splits_path: Option<&Path>) -> Option<SharedTimer> {
+    let splits_path = splits_path.map(canonical_path);
+    TIMERS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(path, _, _)| splits_path.as_ref().map_or(true, |p| p == path))
+        .find_map(|(_, _, timer)| timer.upgrade())
+}
+
+unsafe extern "C" fn handle_request(
+    request_data: *mut obs_data_t,
+    response_data: *mut obs_data_t,
+    priv_data: *mut c_void,
+) {
+    let (_, command): &(&[u8], &str) = &*priv_data.cast();
+
+    let mut request: Value =
+        serde_json::from_slice(CStr::from_ptr(obs_data_get_json(request_data)).to_bytes())
+            .unwrap_or_default();
+    if !request.is_object() {
+        request = json!({});
+    }
+    let splits_path = request
+        .get("splitsPath")
+        .and_then(Value::as_str)
+        .map(PathBuf::from);
+    request["command"] = json!(command);
+
+    let response = match Command::from_value(request) {
+        Ok(command) => match find_timer(splits_path.as_deref()) {
+            Some(timer) => match command::execute(command, &timer) {
+                Ok(result) => json!({ "success": true, "result": result }),
+                Err(e) => json!({ "success": false, "error": e }),
+            },
+            None => json!({ "success": false, "error": { "code": "NoTimer" } }),
+        },
+        Err(e) => json!({ "success": false, "error": e }),
+    };
+
+    let Ok(response) = CString::new(response.to_string()) else {
+        return;
+    };
+    let response = obs_data_create_from_json(response.as_ptr());
+    obs_data_apply(response_data, response);
+    obs_data_release(response);
+}
+
+fn event_name(event: TimerEvent) -> &'static [u8] {
+    match event {
+        TimerEvent::Started => b"RunStarted\0",
+        TimerEvent::Splitted { .. } => b"Split\0",
+        TimerEvent::SkippedSplit { .. } => b"SplitSkipped\0",
+        TimerEvent::UndoneSplit { .. } => b"SplitUndone\0",
+        TimerEvent::Finished => b"RunFinished\0",
+        TimerEvent::Reset => b"RunReset\0",
+        TimerEvent::Paused => b"RunPaused\0",
+        TimerEvent::Resumed => b"RunResumed\0",
+    }
+}
+
+fn event_data(event: TimerEvent, timer: &Timer, splits_path: &Path) -> Value {
+    let method = timer.current_timing_method();
+    let mut data = json!({
+        "splitsPath": splits_path.to_string_lossy(),
+        "time": Complete.format(timer.snapshot().current_time()[method]).to_string(),
+    });
+    match event {
+        TimerEvent::Splitted { index }
+        | TimerEvent::SkippedSplit { index }
+        | TimerEvent::UndoneSplit { index } => {
+            let segment = timer.run().segment(index);
+            data["segmentIndex"] = json!(index);
+            data["segmentName"] = json!(segment.name());
+            data["splitTime"] = json!(Complete.format(segment.split_time()[method]).to_string());
+        }
+        _ => {}
+    }
+    data
+}
+
+unsafe extern "C" fn tick(_: *mut c_void, _: f32) {
+    let mut emitted = Vec::new();
+    {
+        let timers = TIMERS.lock().unwrap();
+        let mut trackers = TRACKERS.lock().unwrap();
+        trackers.retain(|(timer, _)| timer.strong_count() > 0);
+
+        for (path, _, weak) in timers.iter() {
+            let Some(timer) = weak.upgrade() else {
+                continue;
+            };
+            let timer = timer.read().unwrap();
+            let tracker = match trackers.iter().position(|(t, _)| t.ptr_eq(weak)) {
+                Some(index) => &mut trackers[index].1,
+                None => {
+                    trackers.push((weak.clone(), EventTracker::new(&timer)));
+                    continue;
+                }
+            };
+            for event in tracker.poll(&timer) {
+                emitted.push((event_name(event), event_data(event, &timer, path)));
+            }
+        }
+    }
+
+    let ph = PROC_HANDLER.load(atomic::Ordering::Relaxed);
+    let vendor = VENDOR.load(atomic::Ordering::Relaxed);
+    for (name, data) in emitted {
+        let Ok(data) = CString::new(data.to_string()) else {
+            continue;
+        };
+        let data = obs_data_create_from_json(data.as_ptr());
+        let mut cd = CallData::default();
+        calldata::set_string(
+            cd.as_mut_ptr(),
+            cstr!("type"),
+            CStr::from_bytes_with_nul_unchecked(name),
+        );
+        calldata::set_ptr(cd.as_mut_ptr(), cstr!("data"), data.cast());
+        calldata::set_ptr(cd.as_mut_ptr(), cstr!("vendor"), vendor);
+        proc_handler_call(ph, cstr!("vendor_event_emit"), cd.as_mut_ptr());
+        obs_data_release(data);
+    }
+}
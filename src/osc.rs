@@ -0,0 +1,352 @@
+use std::{
+    ffi::CStr,
+    io,
+    net::{Ipv4Addr, SocketAddr, ToSocketAddrs, UdpSocket},
+    os::raw::c_char,
+    time::{Duration, Instant},
+};
+
+use livesplit_core::{
+    timing::formatter::{Complete, Delta, TimeFormatter},
+    Timer,
+};
+use serde_json::{json, Map, Value};
+
+use crate::{
+    command::{phase_name, Command},
+    events::{self, EventTracker, TimerEvent},
+    ffi::{
+        obs_data_get_int, obs_data_get_string, obs_data_t, obs_properties_add_group,
+        obs_properties_add_int, obs_properties_add_text, obs_properties_create, obs_properties_t,
+        obs_property_set_long_description, OBS_GROUP_NORMAL, OBS_TEXT_DEFAULT,
+    },
+};
+
+const SETTINGS_OSC: *const c_char = cstr!("osc");
+const SETTINGS_OSC_PORT: *const c_char = cstr!("osc_port");
+const SETTINGS_OSC_TARGET: *const c_char = cstr!("osc_target");
+
+const PREFIX: &str = "/livesplit/";
+/// How often the state of the timer is sent at most. Events are sent right
+/// away.
+const FEEDBACK_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(PartialEq, Eq)]
+pub struct Config {
+    port: Option<u16>,
+    target: Option<String>,
+}
+
+#[derive(Default)]
+struct Feedback {
+    time: String,
+    split: String,
+    split_index: i32,
+    phase: String,
+    delta: String,
+}
+
+/// Controls the timer with OSC messages, as lighting desks, TouchOSC layouts
+/// and show control software send them, and sends the state of the timer
+/// back. Everything happens on a non-blocking UDP socket that is polled every
+/// frame, so no thread is needed.
+pub struct Osc {
+    config: Config,
+    socket: UdpSocket,
+    target: Option<SocketAddr>,
+    tracker: EventTracker,
+    feedback: Option<Feedback>,
+    last_feedback: Instant,
+}
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let group = obs_properties_create();
+    let port = obs_properties_add_int(group, SETTINGS_OSC_PORT, cstr!("Port"), 0, 65535, 1);
+    obs_property_set_long_description(
+        port,
+        cstr!("The UDP port that OSC messages such as /livesplit/split are received on. 0 turns receiving them off."),
+    );
+    let target = obs_properties_add_text(
+        group,
+        SETTINGS_OSC_TARGET,
+        cstr!("Send Feedback to"),
+        OBS_TEXT_DEFAULT,
+    );
+    obs_property_set_long_description(
+        target,
+        cstr!("The address and port that the time, split and events are sent to, e.g. 192.168.0.2:9000."),
+    );
+
+    obs_properties_add_group(props, SETTINGS_OSC, cstr!("OSC"), OBS_GROUP_NORMAL, group);
+}
+
+pub unsafe fn parse_settings(settings: *mut obs_data_t) -> Option<Config> {
+    let port = obs_data_get_int(settings, SETTINGS_OSC_PORT);
+    let port = Some(port)
+        .filter(|&port| port > 0 && port <= u16::MAX as i64)
+        .map(|port| port as u16);
+    let target = CStr::from_ptr(obs_data_get_string(settings, SETTINGS_OSC_TARGET))
+        .to_string_lossy()
+        .trim()
+        .to_owned();
+    let target = Some(target).filter(|target| !target.is_empty());
+    if port.is_none() && target.is_none() {
+        return None;
+    }
+    Some(Config { port, target })
+}
+
+impl Osc {
+    pub fn new(config: Config, timer: &Timer) -> io::Result<Self> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, config.port.unwrap_or(0)))?;
+        socket.set_nonblocking(true)?;
+        let target = match &config.target {
+            Some(target) => Some(target.to_socket_addrs()?.next().ok_or_else(|| {
+                io::Error::new(io::ErrorKind::NotFound, "the address couldn't be resolved")
+            })?),
+            None => None,
+        };
+        if let Some(port) = config.port {
+            log::info!("Receiving OSC messages on port {port}.");
+        }
+        Ok(Self {
+            config,
+            socket,
+            target,
+            tracker: EventTracker::new(timer),
+            feedback: None,
+            last_feedback: Instant::now(),
+        })
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Returns the commands of the messages that were received since the last
+    /// call.
+    pub fn receive(&mut self) -> Vec<Command> {
+        let mut commands = Vec::new();
+        if self.config.port.is_none() {
+            return commands;
+        }
+        let mut buf = [0; 4096];
+        while let Ok(len) = self.socket.recv(&mut buf) {
+            parse_packet(&buf[..len], &mut commands);
+        }
+        commands
+    }
+
+    /// Sends the events and the state of the timer to the target.
+    pub fn send_feedback(&mut self, timer: &Timer) {
+        let Some(target) = self.target else {
+            return;
+        };
+
+        for event in self.tracker.poll(timer) {
+            let name = match event {
+                TimerEvent::Started => "start",
+                TimerEvent::Splitted { index } if events::is_gold(timer, index) => "gold",
+                TimerEvent::Splitted { .. } => "split",
+                TimerEvent::SkippedSplit { .. } => "skip",
+                TimerEvent::UndoneSplit { .. } => "undo",
+                TimerEvent::Finished if events::is_personal_best(timer) => "personal_best",
+                TimerEvent::Finished => "finish",
+                TimerEvent::Reset => "reset",
+                TimerEvent::Paused => "pause",
+                TimerEvent::Resumed => "resume",
+            };
+            self.send(target, "event", &[Argument::String(name)]);
+        }
+
+        if self.last_feedback.elapsed() < FEEDBACK_INTERVAL {
+            return;
+        }
+        self.last_feedback = Instant::now();
+
+        let method = timer.current_timing_method();
+        let feedback = Feedback {
+            time: Complete
+                .format(timer.snapshot().current_time()[method])
+                .to_string(),
+            split: timer
+                .current_split()
+                .map_or("", |segment| segment.name())
+                .to_owned(),
+            split_index: timer.current_split_index().map_or(-1, |index| index as i32),
+            phase: phase_name(timer.current_phase()).to_owned(),
+            delta: events::last_delta(timer)
+                .map(|delta| Delta::new().format(delta).to_string())
+                .unwrap_or_default(),
+        };
+        // Only what changed is sent, so receivers aren't flooded.
+        let first = self.feedback.is_none();
+        let previous = self.feedback.take().unwrap_or_default();
+        if first || feedback.time != previous.time {
+            self.send(target, "time", &[Argument::String(&feedback.time)]);
+        }
+        if first || feedback.split != previous.split {
+            self.send(target, "split", &[Argument::String(&feedback.split)]);
+        }
+        if first || feedback.split_index != previous.split_index {
+            self.send(target, "splitIndex", &[Argument::Int(feedback.split_index)]);
+        }
+        if first || feedback.phase != previous.phase {
+            self.send(target, "phase", &[Argument::String(&feedback.phase)]);
+        }
+        if first || feedback.delta != previous.delta {
+            self.send(target, "delta", &[Argument::String(&feedback.delta)]);
+        }
+        self.feedback = Some(feedback);
+    }
+
+    fn send(&self, target: SocketAddr, name: &str, arguments: &[Argument<'_>]) {
+        let message = encode_message(&format!("{PREFIX}{name}"), arguments);
+        if let Err(e) = self.socket.send_to(&message, target) {
+            log::debug!("Failed to send the OSC message: {e}");
+        }
+    }
+}
+
+enum Argument<'a> {
+    String(&'a str),
+    Int(i32),
+}
+
+/// Appends the string with its null terminator, padded to a multiple of four
+/// bytes, as all parts of OSC messages are.
+fn push_string(message: &mut Vec<u8>, string: &str) {
+    message.extend_from_slice(string.as_bytes());
+    message.push(0);
+    while message.len() % 4 != 0 {
+        message.push(0);
+    }
+}
+
+fn encode_message(address: &str, arguments: &[Argument<'_>]) -> Vec<u8> {
+    let mut message = Vec::new();
+    push_string(&mut message, address);
+    let mut tags = String::from(",");
+    for argument in arguments {
+        tags.push(match argument {
+            Argument::String(_) => 's',
+            Argument::Int(_) => 'i',
+        });
+    }
+    push_string(&mut message, &tags);
+    for argument in arguments {
+        match argument {
+            Argument::String(value) => push_string(&mut message, value),
+            Argument::Int(value) => message.extend_from_slice(&value.to_be_bytes()),
+        }
+    }
+    message
+}
+
+/// Reads a padded string and returns it along with the rest of the data.
+fn read_string(data: &[u8]) -> Option<(&str, &[u8])> {
+    let len = data.iter().position(|&b| b == 0)?;
+    let string = std::str::from_utf8(&data[..len]).ok()?;
+    let padded = (len + 4) & !3;
+    Some((string, data.get(padded..)?))
+}
+
+fn read_u32(data: &[u8]) -> Option<(u32, &[u8])> {
+    let bytes = data.get(..4)?;
+    Some((
+        u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        &data[4..],
+    ))
+}
+
+/// Parses a message or a bundle of them into the commands that they ask for.
+fn parse_packet(data: &[u8], commands: &mut Vec<Command>) {
+    if let Some(mut elements) = data.strip_prefix(b"#bundle\0") {
+        // The time tag is ignored, everything happens right away.
+        elements = elements.get(8..).unwrap_or_default();
+        while let Some((len, rest)) = read_u32(elements) {
+            let Some(element) = rest.get(..len as usize) else {
+                return;
+            };
+            parse_packet(element, commands);
+            elements = &rest[len as usize..];
+        }
+        return;
+    }
+    if let Some(command) = parse_message(data) {
+        commands.push(command);
+    }
+}
+
+/// The keys of the command's parameters, in the order that the arguments of
+/// the message are assigned to them.
+fn parameters(command: &str) -> &'static [&'static str] {
+    match command {
+        "reset" => &["saveAttempt"],
+        "setCurrentComparison" => &["comparison"],
+        "setCurrentTimingMethod" => &["timingMethod"],
+        "setGameTime" | "setLoadingTimes" => &["time"],
+        "setCustomVariable" => &["key", "value"],
+        _ => &[],
+    }
+}
+
+/// Parses a message like `/livesplit/split` into the command of the same name
+/// as the WebSocket server's.
+fn parse_message(data: &[u8]) -> Option<Command> {
+    let (address, rest) = read_string(data)?;
+    let name = address.strip_prefix(PREFIX)?;
+    let (tags, mut rest) = read_string(rest).unwrap_or((",", &[]));
+
+    let mut arguments = Vec::new();
+    for tag in tags.strip_prefix(',')?.chars() {
+        let value = match tag {
+            's' => {
+                let (value, remaining) = read_string(rest)?;
+                rest = remaining;
+                json!(value)
+            }
+            'i' => {
+                let (value, remaining) = read_u32(rest)?;
+                rest = remaining;
+                json!(value as i32)
+            }
+            'f' => {
+                let (value, remaining) = read_u32(rest)?;
+                rest = remaining;
+                json!(f32::from_bits(value))
+            }
+            'T' => json!(true),
+            'F' => json!(false),
+            _ => return None,
+        };
+        arguments.push(value);
+    }
+
+    let parameters = parameters(name);
+    if parameters.is_empty() {
+        // Buttons send 1 when they are pressed and 0 when they are released,
+        // which shouldn't trigger the command a second time.
+        if arguments.first().and_then(Value::as_f64) == Some(0.0) {
+            return None;
+        }
+    }
+
+    let mut command = Map::new();
+    command.insert("command".into(), json!(name));
+    for (&key, value) in parameters.iter().zip(arguments) {
+        let value = match (key, value) {
+            ("saveAttempt", Value::Number(number)) => json!(number.as_f64() != Some(0.0)),
+            (_, Value::Number(number)) => json!(number.to_string()),
+            (_, value) => value,
+        };
+        command.insert(key.into(), value);
+    }
+    match Command::from_value(Value::Object(command)) {
+        Ok(command) => Some(command),
+        Err(_) => {
+            log::debug!("Received the unknown OSC message {address}.");
+            None
+        }
+    }
+}
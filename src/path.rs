@@ -0,0 +1,62 @@
+//! OBS stores paths as UTF-8 strings. Converting them with `to_string_lossy`
+//! breaks paths that aren't valid Unicode, so they are converted into the
+//! platform's native representation instead. On Windows that is UTF-16, which
+//! OBS converts to the same way it does when it opens files itself.
+
+use std::{
+    ffi::CString,
+    os::raw::c_char,
+    path::{Path, PathBuf},
+};
+
+#[cfg(windows)]
+pub unsafe fn from_obs(path: *const c_char) -> PathBuf {
+    use crate::ffi::{bfree, os_utf8_to_wcs_ptr};
+    use std::{ffi::OsString, os::windows::ffi::OsStringExt, ptr, slice};
+
+    let mut wide = ptr::null_mut();
+    let len = os_utf8_to_wcs_ptr(path, 0, &mut wide);
+    if wide.is_null() {
+        return PathBuf::new();
+    }
+    let path = OsString::from_wide(slice::from_raw_parts(wide, len));
+    bfree(wide.cast());
+    PathBuf::from(path)
+}
+
+#[cfg(not(windows))]
+pub unsafe fn from_obs(path: *const c_char) -> PathBuf {
+    use std::{
+        ffi::{CStr, OsStr},
+        os::unix::ffi::OsStrExt,
+    };
+
+    PathBuf::from(OsStr::from_bytes(CStr::from_ptr(path).to_bytes()))
+}
+
+#[cfg(windows)]
+pub unsafe fn to_obs(path: &Path) -> Option<CString> {
+    use crate::ffi::{bfree, os_wcs_to_utf8_ptr};
+    use std::{ffi::CStr, iter, os::windows::ffi::OsStrExt, ptr};
+
+    let wide: Vec<u16> = path
+        .as_os_str()
+        .encode_wide()
+        .chain(iter::once(0))
+        .collect();
+    let mut utf8 = ptr::null_mut();
+    os_wcs_to_utf8_ptr(wide.as_ptr(), wide.len() - 1, &mut utf8);
+    if utf8.is_null() {
+        return None;
+    }
+    let path = CStr::from_ptr(utf8).to_owned();
+    bfree(utf8.cast());
+    Some(path)
+}
+
+#[cfg(not(windows))]
+pub unsafe fn to_obs(path: &Path) -> Option<CString> {
+    use std::os::unix::ffi::OsStrExt;
+
+    CString::new(path.as_os_str().as_bytes()).ok()
+}
@@ -0,0 +1,135 @@
+use std::{
+    mem,
+    os::raw::c_char,
+    path::{Path, PathBuf},
+    ptr,
+};
+
+use livesplit_core::Timer;
+
+use crate::{
+    canonical_path,
+    events::{EventTracker, TimerEvent},
+    ffi::{
+        obs_data_create, obs_data_get_bool, obs_data_release, obs_data_set_string, obs_data_t,
+        obs_properties_add_bool, obs_properties_add_editable_list, obs_properties_add_group,
+        obs_properties_create, obs_properties_t, obs_source_t, obs_source_update,
+        OBS_EDITABLE_LIST_TYPE_FILES, OBS_GROUP_NORMAL,
+    },
+    get_path_list, path, SETTINGS_SPLITS_PATH,
+};
+
+const SETTINGS_PLAYLIST: *const c_char = cstr!("playlist");
+const SETTINGS_PLAYLIST_RUNS: *const c_char = cstr!("playlist_runs");
+const SETTINGS_PLAYLIST_AUTO_ADVANCE: *const c_char = cstr!("playlist_auto_advance");
+
+#[derive(PartialEq, Eq)]
+pub struct Config {
+    runs: Vec<PathBuf>,
+    auto_advance: bool,
+}
+
+/// The splits of the runs of a marathon, in the order they are done in.
+pub struct Playlist {
+    config: Config,
+    tracker: EventTracker,
+    finished: bool,
+}
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let group = obs_properties_create();
+    obs_properties_add_editable_list(
+        group,
+        SETTINGS_PLAYLIST_RUNS,
+        cstr!("Splits"),
+        OBS_EDITABLE_LIST_TYPE_FILES,
+        cstr!("LiveSplit Splits (*.lss)"),
+        ptr::null(),
+    );
+    obs_properties_add_bool(
+        group,
+        SETTINGS_PLAYLIST_AUTO_ADVANCE,
+        cstr!("Go to the Next Run After Finishing"),
+    );
+
+    obs_properties_add_group(
+        props,
+        SETTINGS_PLAYLIST,
+        cstr!("Playlist"),
+        OBS_GROUP_NORMAL,
+        group,
+    );
+}
+
+pub unsafe fn parse_settings(settings: *mut obs_data_t) -> Option<Config> {
+    let runs: Vec<PathBuf> = get_path_list(settings, SETTINGS_PLAYLIST_RUNS)
+        .into_iter()
+        .filter(|run| !run.as_os_str().is_empty())
+        .collect();
+    if runs.is_empty() {
+        return None;
+    }
+    Some(Config {
+        runs,
+        auto_advance: obs_data_get_bool(settings, SETTINGS_PLAYLIST_AUTO_ADVANCE),
+    })
+}
+
+impl Playlist {
+    pub fn new(config: Config, timer: &Timer) -> Self {
+        Self {
+            config,
+            tracker: EventTracker::new(timer),
+            finished: false,
+        }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Returns whether the run was finished and then reset, which is when the
+    /// playlist moves on to the next run if it's supposed to.
+    pub fn poll(&mut self, timer: &Timer) -> bool {
+        let mut advance = false;
+        for event in self.tracker.poll(timer) {
+            match event {
+                TimerEvent::Finished => self.finished = true,
+                TimerEvent::UndoneSplit { .. } => self.finished = false,
+                TimerEvent::Reset => advance = mem::take(&mut self.finished),
+                _ => {}
+            }
+        }
+        advance && self.config.auto_advance
+    }
+
+    /// Returns the splits of the run that is `offset` runs away from the
+    /// current splits. If the current splits aren't in the playlist, it starts
+    /// from the first run.
+    pub fn neighbor(&self, splits_path: &Path, offset: isize) -> Option<&Path> {
+        let splits_path = canonical_path(splits_path);
+        let index = match self
+            .config
+            .runs
+            .iter()
+            .position(|run| canonical_path(run) == splits_path)
+        {
+            Some(index) => index.checked_add_signed(offset)?,
+            None => 0,
+        };
+        self.config.runs.get(index).map(PathBuf::as_path)
+    }
+}
+
+/// Chooses the splits for the source. This goes through the settings, so that
+/// everything that depends on the splits is updated and the choice is stored.
+pub unsafe fn switch_to(source: *mut obs_source_t, splits_path: &Path) {
+    let Some(splits_path) = path::to_obs(splits_path) else {
+        return;
+    };
+    log::info!("Switching to the splits {}.", splits_path.to_string_lossy());
+    let changes = obs_data_create();
+    obs_data_set_string(changes, SETTINGS_SPLITS_PATH, splits_path.as_ptr());
+    obs_source_update(source, changes);
+    obs_data_release(changes);
+}
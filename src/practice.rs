@@ -0,0 +1,138 @@
+use std::{
+    ffi::{CStr, CString},
+    os::raw::c_char,
+};
+
+use livesplit_core::{comparison::best_segments, SharedTimer, TimeSpan, Timer, TimerPhase};
+
+use crate::ffi::{
+    obs_data_get_int, obs_data_get_string, obs_data_set_default_string, obs_data_t,
+    obs_properties_add_group, obs_properties_add_list, obs_properties_create, obs_properties_t,
+    obs_property_list_add_int, obs_property_list_add_string, obs_property_set_long_description,
+    OBS_COMBO_FORMAT_INT, OBS_COMBO_FORMAT_STRING, OBS_COMBO_TYPE_LIST, OBS_GROUP_NORMAL,
+};
+
+const SETTINGS_PRACTICE: *const c_char = cstr!("practice");
+const SETTINGS_PRACTICE_SEGMENT: *const c_char = cstr!("practice_segment");
+const SETTINGS_PRACTICE_TIMES: *const c_char = cstr!("practice_times");
+
+/// Where the times of the splits before the practiced segment come from.
+#[derive(Copy, Clone)]
+enum Times {
+    PersonalBest,
+    BestSegments,
+}
+
+pub struct Config {
+    segment: usize,
+    times: Times,
+}
+
+pub unsafe fn add_properties(props: *mut obs_properties_t, timer: Option<&SharedTimer>) {
+    let group = obs_properties_create();
+    let segments = obs_properties_add_list(
+        group,
+        SETTINGS_PRACTICE_SEGMENT,
+        cstr!("Segment"),
+        OBS_COMBO_TYPE_LIST,
+        OBS_COMBO_FORMAT_INT,
+    );
+    obs_property_set_long_description(
+        segments,
+        cstr!("The segment that the Start Practice hotkey starts the timer at."),
+    );
+    if let Some(timer) = timer {
+        let timer = timer.read().unwrap();
+        for (index, segment) in timer.run().segments().iter().enumerate() {
+            if let Ok(name) = CString::new(format!("{}. {}", index + 1, segment.name())) {
+                obs_property_list_add_int(segments, name.as_ptr(), index as _);
+            }
+        }
+    }
+    let times = obs_properties_add_list(
+        group,
+        SETTINGS_PRACTICE_TIMES,
+        cstr!("Earlier Splits"),
+        OBS_COMBO_TYPE_LIST,
+        OBS_COMBO_FORMAT_STRING,
+    );
+    obs_property_list_add_string(times, cstr!("Personal Best"), cstr!("personal_best"));
+    obs_property_list_add_string(times, cstr!("Best Segments"), cstr!("best_segments"));
+    obs_property_set_long_description(
+        times,
+        cstr!("The times that the splits before the segment are assumed to have, so the deltas of the segment are the ones of a full run."),
+    );
+
+    obs_properties_add_group(
+        props,
+        SETTINGS_PRACTICE,
+        cstr!("Practice"),
+        OBS_GROUP_NORMAL,
+        group,
+    );
+}
+
+pub unsafe fn set_defaults(settings: *mut obs_data_t) {
+    obs_data_set_default_string(settings, SETTINGS_PRACTICE_TIMES, cstr!("personal_best"));
+}
+
+pub unsafe fn parse_settings(settings: *mut obs_data_t) -> Config {
+    Config {
+        segment: obs_data_get_int(settings, SETTINGS_PRACTICE_SEGMENT).max(0) as usize,
+        times: match CStr::from_ptr(obs_data_get_string(settings, SETTINGS_PRACTICE_TIMES))
+            .to_bytes()
+        {
+            b"best_segments" => Times::BestSegments,
+            _ => Times::PersonalBest,
+        },
+    }
+}
+
+/// Starts the timer at the segment, as if the run got there with the times
+/// of the comparison. The splits before it can't be given times, so they are
+/// skipped, but the run's offset makes up for them, so the deltas are still
+/// accurate. The original offset is returned, so that it can be put back once
+/// the practice attempt is over.
+pub fn start(timer: &mut Timer, config: &Config) -> Option<TimeSpan> {
+    if timer.current_phase() != TimerPhase::NotRunning {
+        return None;
+    }
+    let index = config.segment.min(timer.run().len() - 1);
+    let time = match index.checked_sub(1) {
+        Some(previous) => {
+            let segment = timer.run().segment(previous);
+            let time = match config.times {
+                Times::PersonalBest => segment.personal_best_split_time(),
+                Times::BestSegments => segment.comparison(best_segments::NAME),
+            };
+            if time.real_time.is_none() {
+                log::warn!("{} has no time to practice from.", segment.name());
+                return None;
+            }
+            time
+        }
+        None => Default::default(),
+    };
+
+    let offset = timer.run().offset();
+    let mut run = timer.run().clone();
+    run.set_offset(time.real_time.unwrap_or_default());
+    timer.set_run(run).ok()?;
+    timer.start();
+
+    for _ in 0..index {
+        timer.skip_split();
+    }
+
+    if let Some(game_time) = time.game_time {
+        timer.initialize_game_time();
+        timer.set_game_time(game_time);
+    }
+
+    log::info!(
+        "Practicing {}.",
+        timer.current_split().map_or("", |segment| segment.name())
+    );
+
+    Some(offset)
+}
@@ -0,0 +1,142 @@
+// Procedures that are registered on the proc handler of every source, so that
+// scripts and other plugins can control and query the timer through
+// `obs_source_get_proc_handler`.
+
+use std::{
+    ffi::{c_void, CString},
+    os::raw::c_char,
+};
+
+use livesplit_core::{
+    analysis::sum_of_segments,
+    timing::formatter::{Complete, Delta, TimeFormatter},
+};
+
+use crate::{
+    calldata,
+    command::{phase_name, Command},
+    events,
+    ffi::{calldata_t, obs_source_get_proc_handler, obs_source_t, proc_handler_add},
+    State,
+};
+
+unsafe fn set_string(cd: *mut calldata_t, name: *const c_char, value: &str) {
+    if let Ok(value) = CString::new(value) {
+        calldata::set_string(cd, name, &value);
+    }
+}
+
+fn run(state: &mut State, command: Command) {
+    let _ = state.execute(command);
+}
+
+macro_rules! procs {
+    ($($name:ident: $decl:literal => $action:expr,)*) => {
+        $(
+            unsafe extern "C" fn $name(data: *mut c_void, cd: *mut calldata_t) {
+                let state: &mut State = &mut *data.cast();
+                let action: fn(&mut State, *mut calldata_t) = $action;
+                action(state, cd);
+            }
+        )*
+
+        pub unsafe fn register(source: *mut obs_source_t, data: *mut c_void) {
+            let ph = obs_source_get_proc_handler(source);
+            $(
+                proc_handler_add(ph, cstr!($decl), Some($name), data);
+            )*
+        }
+    };
+}
+
+procs! {
+    split: "void livesplit_split()" => |state, _| run(state, Command::Split),
+    split_or_start: "void livesplit_split_or_start()" => |state, _| {
+        run(state, Command::SplitOrStart)
+    },
+    start: "void livesplit_start()" => |state, _| run(state, Command::Start),
+    reset: "void livesplit_reset()" => |state, _| {
+        run(state, Command::Reset { save_attempt: None })
+    },
+    undo_split: "void livesplit_undo_split()" => |state, _| run(state, Command::UndoSplit),
+    skip_split: "void livesplit_skip_split()" => |state, _| run(state, Command::SkipSplit),
+    toggle_pause: "void livesplit_toggle_pause()" => |state, _| {
+        run(state, Command::TogglePauseOrStart)
+    },
+    pause: "void livesplit_pause()" => |state, _| run(state, Command::Pause),
+    resume: "void livesplit_resume()" => |state, _| run(state, Command::Resume),
+    undo_all_pauses: "void livesplit_undo_all_pauses()" => |state, _| {
+        run(state, Command::UndoAllPauses)
+    },
+    previous_comparison: "void livesplit_previous_comparison()" => |state, _| {
+        run(state, Command::SwitchToPreviousComparison)
+    },
+    next_comparison: "void livesplit_next_comparison()" => |state, _| {
+        run(state, Command::SwitchToNextComparison)
+    },
+    toggle_timing_method: "void livesplit_toggle_timing_method()" => |state, _| {
+        run(state, Command::ToggleTimingMethod)
+    },
+    pause_game_time: "void livesplit_pause_game_time()" => |state, _| state.pause_game_time(),
+    resume_game_time: "void livesplit_resume_game_time()" => |state, _| {
+        run(state, Command::ResumeGameTime)
+    },
+    set_game_time: "void livesplit_set_game_time(in string time)" => |state, cd| {
+        let time = unsafe { calldata::get_string(cd, cstr!("time")) };
+        let time = time.and_then(|time| time.to_str().ok()).unwrap_or_default();
+        if state.execute(Command::SetGameTime { time: time.to_owned() }).is_err() {
+            log::warn!("livesplit_set_game_time was called with an invalid time.");
+        }
+    },
+    set_custom_variable: "void livesplit_set_custom_variable(in string key, in string value)"
+        => |state, cd| {
+        let get = |name| unsafe { calldata::get_string(cd, name) }.map(|s| s.to_string_lossy());
+        let (Some(key), Some(value)) = (get(cstr!("key")), get(cstr!("value"))) else {
+            log::warn!("livesplit_set_custom_variable needs a key and a value.");
+            return;
+        };
+        state.timer.write().unwrap().set_custom_variable(&key, &value);
+    },
+    get_custom_variable: "void livesplit_get_custom_variable(in string key, out string value)"
+        => |state, cd| {
+        let key = unsafe { calldata::get_string(cd, cstr!("key")) };
+        let timer = state.timer.read().unwrap();
+        let value = key
+            .and_then(|key| timer.run().metadata().custom_variable(&key.to_string_lossy()))
+            .map_or("", |variable| &variable.value);
+        unsafe { set_string(cd, cstr!("value"), value) };
+    },
+    get_time: "void livesplit_get_time(out string time)" => |state, cd| {
+        let timer = state.timer.read().unwrap();
+        let time = timer.snapshot().current_time()[timer.current_timing_method()];
+        unsafe { set_string(cd, cstr!("time"), &Complete.format(time).to_string()) };
+    },
+    get_split_name: "void livesplit_get_split_name(out string name)" => |state, cd| {
+        let timer = state.timer.read().unwrap();
+        let name = timer.current_split().map_or("", |segment| segment.name());
+        unsafe { set_string(cd, cstr!("name"), name) };
+    },
+    get_delta: "void livesplit_get_delta(out string delta)" => |state, cd| {
+        let timer = state.timer.read().unwrap();
+        let delta = events::last_delta(&timer).map(|delta| Delta::new().format(delta).to_string());
+        unsafe { set_string(cd, cstr!("delta"), &delta.unwrap_or_default()) };
+    },
+    best_possible_time: "void livesplit_get_best_possible_time(out string time)" => |state, cd| {
+        let timer = state.timer.read().unwrap();
+        let time = sum_of_segments::calculate_best(
+            timer.run().segments(),
+            false,
+            true,
+            timer.current_timing_method(),
+        );
+        unsafe { set_string(cd, cstr!("time"), &Complete.format(time).to_string()) };
+    },
+    get_state: "void livesplit_get_state(out string phase, out int split_index)" => |state, cd| {
+        let timer = state.timer.read().unwrap();
+        let split_index = timer.current_split_index().map_or(-1, |index| index as i64);
+        unsafe {
+            set_string(cd, cstr!("phase"), phase_name(timer.current_phase()));
+            calldata::set_int(cd, cstr!("split_index"), split_index);
+        }
+    },
+}
@@ -0,0 +1,142 @@
+use std::{ffi::CStr, os::raw::c_char};
+
+use livesplit_core::Timer;
+
+use crate::{
+    events::{self, EventTracker, TimerEvent},
+    ffi::{
+        obs_data_get_bool, obs_data_get_string, obs_data_set_default_string, obs_data_t,
+        obs_properties_add_bool, obs_properties_add_group, obs_properties_add_list,
+        obs_properties_create, obs_properties_t, obs_property_list_add_string,
+        OBS_COMBO_FORMAT_STRING, OBS_COMBO_TYPE_LIST, OBS_GROUP_NORMAL,
+    },
+    frontend,
+};
+
+const SETTINGS_RECORDING: *const c_char = cstr!("recording");
+const SETTINGS_START_RECORDING: *const c_char = cstr!("recording_start");
+const SETTINGS_ON_FINISH: *const c_char = cstr!("recording_on_finish");
+const SETTINGS_ONLY_PERSONAL_BESTS: *const c_char = cstr!("recording_only_personal_bests");
+const SETTINGS_STOP_ON_RESET: *const c_char = cstr!("recording_stop_on_reset");
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum FinishAction {
+    Nothing,
+    StopRecording,
+    SaveReplayBuffer,
+}
+
+#[derive(PartialEq, Eq)]
+pub struct Config {
+    start_recording: bool,
+    on_finish: FinishAction,
+    only_personal_bests: bool,
+    stop_on_reset: bool,
+}
+
+/// Starts and stops the recording of the frontend, or saves its replay buffer,
+/// depending on what happens to the timer.
+pub struct Recorder {
+    config: Config,
+    tracker: EventTracker,
+}
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let group = obs_properties_create();
+    obs_properties_add_bool(
+        group,
+        SETTINGS_START_RECORDING,
+        cstr!("Start Recording When the Run Starts"),
+    );
+    let on_finish = obs_properties_add_list(
+        group,
+        SETTINGS_ON_FINISH,
+        cstr!("When the Run Finishes"),
+        OBS_COMBO_TYPE_LIST,
+        OBS_COMBO_FORMAT_STRING,
+    );
+    obs_property_list_add_string(on_finish, cstr!("Do Nothing"), cstr!("nothing"));
+    obs_property_list_add_string(on_finish, cstr!("Stop Recording"), cstr!("stop_recording"));
+    obs_property_list_add_string(
+        on_finish,
+        cstr!("Save Replay Buffer"),
+        cstr!("save_replay_buffer"),
+    );
+    obs_properties_add_bool(
+        group,
+        SETTINGS_ONLY_PERSONAL_BESTS,
+        cstr!("Only When It's a Personal Best"),
+    );
+    obs_properties_add_bool(
+        group,
+        SETTINGS_STOP_ON_RESET,
+        cstr!("Stop Recording When the Run Is Reset"),
+    );
+
+    obs_properties_add_group(
+        props,
+        SETTINGS_RECORDING,
+        cstr!("Recording"),
+        OBS_GROUP_NORMAL,
+        group,
+    );
+}
+
+pub unsafe fn set_defaults(settings: *mut obs_data_t) {
+    obs_data_set_default_string(settings, SETTINGS_ON_FINISH, cstr!("nothing"));
+}
+
+pub unsafe fn parse_settings(settings: *mut obs_data_t) -> Option<Config> {
+    let config = Config {
+        start_recording: obs_data_get_bool(settings, SETTINGS_START_RECORDING),
+        on_finish: match CStr::from_ptr(obs_data_get_string(settings, SETTINGS_ON_FINISH))
+            .to_bytes()
+        {
+            b"stop_recording" => FinishAction::StopRecording,
+            b"save_replay_buffer" => FinishAction::SaveReplayBuffer,
+            _ => FinishAction::Nothing,
+        },
+        only_personal_bests: obs_data_get_bool(settings, SETTINGS_ONLY_PERSONAL_BESTS),
+        stop_on_reset: obs_data_get_bool(settings, SETTINGS_STOP_ON_RESET),
+    };
+    if config.start_recording || config.on_finish != FinishAction::Nothing || config.stop_on_reset {
+        Some(config)
+    } else {
+        None
+    }
+}
+
+impl Recorder {
+    pub fn new(config: Config, timer: &Timer) -> Self {
+        Self {
+            config,
+            tracker: EventTracker::new(timer),
+        }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn poll(&mut self, timer: &Timer) {
+        for event in self.tracker.poll(timer) {
+            let action: unsafe fn() = match event {
+                TimerEvent::Started if self.config.start_recording => frontend::recording_start,
+                TimerEvent::Finished
+                    if !self.config.only_personal_bests || events::is_personal_best(timer) =>
+                {
+                    match self.config.on_finish {
+                        FinishAction::Nothing => continue,
+                        FinishAction::StopRecording => frontend::recording_stop,
+                        FinishAction::SaveReplayBuffer => frontend::replay_buffer_save,
+                    }
+                }
+                TimerEvent::Reset if self.config.stop_on_reset => frontend::recording_stop,
+                _ => continue,
+            };
+            // The graphics thread that polls the timer must not use the
+            // frontend.
+            frontend::queue_ui_task(move || unsafe { action() });
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    ffi::c_void,
+    fs,
+    hash::{Hash, Hasher},
+    os::raw::c_char,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use livesplit_core::Timer;
+
+use crate::{
+    ffi::{
+        obs_data_get_int, obs_data_set_default_int, obs_data_t, obs_properties_add_button,
+        obs_properties_add_int, obs_properties_t, obs_property_set_long_description,
+        obs_property_t,
+    },
+    module_config_path,
+    saved_attempt::SavedAttempt,
+    State,
+};
+
+const SETTINGS_RECOVERY_INTERVAL: *const c_char = cstr!("recovery_interval");
+const SETTINGS_RECOVERY_RESTORE: *const c_char = cstr!("recovery_restore");
+
+/// Regularly writes the attempt that is in progress to a recovery file, so it
+/// can be restored after OBS crashed.
+pub struct Recovery {
+    path: PathBuf,
+    interval: Duration,
+    last_save: Instant,
+    saved: bool,
+}
+
+/// The recovery file for the splits.
+unsafe fn recovery_path(splits_path: &Path) -> Option<PathBuf> {
+    let mut hasher = DefaultHasher::new();
+    splits_path.hash(&mut hasher);
+    module_config_path(&format!("recovery/{:016x}.json", hasher.finish()))
+}
+
+pub unsafe fn add_properties(props: *mut obs_properties_t, state: &mut State) {
+    let interval = obs_properties_add_int(
+        props,
+        SETTINGS_RECOVERY_INTERVAL,
+        cstr!("Recovery Autosave Interval (Seconds)"),
+        0,
+        3600,
+        5,
+    );
+    obs_property_set_long_description(
+        interval,
+        cstr!("How often the attempt in progress is saved for recovering it after a crash. 0 turns this off."),
+    );
+
+    if recovery_path(&state.splits_path).map_or(false, |path| path.exists()) {
+        obs_properties_add_button(
+            props,
+            SETTINGS_RECOVERY_RESTORE,
+            cstr!("Restore Attempt from Before the Crash"),
+            Some(restore),
+        );
+    }
+}
+
+pub unsafe fn set_defaults(settings: *mut obs_data_t) {
+    obs_data_set_default_int(settings, SETTINGS_RECOVERY_INTERVAL, 30);
+}
+
+pub unsafe fn parse_settings(settings: *mut obs_data_t) -> Option<Duration> {
+    let interval = obs_data_get_int(settings, SETTINGS_RECOVERY_INTERVAL);
+    if interval > 0 {
+        Some(Duration::from_secs(interval as u64))
+    } else {
+        None
+    }
+}
+
+unsafe extern "C" fn restore(
+    _: *mut obs_properties_t,
+    _: *mut obs_property_t,
+    data: *mut c_void,
+) -> bool {
+    let state: &mut State = &mut *data.cast();
+    let Some(path) = recovery_path(&state.splits_path) else {
+        return false;
+    };
+    let attempt = fs::read(&path)
+        .ok()
+        .and_then(|file| serde_json::from_slice::<SavedAttempt>(&file).ok());
+    if let Some(attempt) = attempt {
+        if let Some(offset) = attempt.restore(&mut state.timer.write().unwrap()) {
+            state.restored_offset = Some(offset);
+        } else {
+            log::warn!("The attempt can't be restored while the timer is running.");
+            return false;
+        }
+    }
+    let _ = fs::remove_file(&path);
+    true
+}
+
+impl Recovery {
+    pub unsafe fn new(interval: Duration, splits_path: &Path) -> Option<Self> {
+        let path = recovery_path(splits_path)?;
+        if path.exists() {
+            log::info!(
+                "There is an attempt from before a crash that can be restored in the source's \
+                 properties."
+            );
+        }
+        Some(Self {
+            path,
+            interval,
+            last_save: Instant::now(),
+            saved: false,
+        })
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    pub fn poll(&mut self, timer: &Timer) {
+        if self.last_save.elapsed() < self.interval {
+            return;
+        }
+        self.last_save = Instant::now();
+
+        match SavedAttempt::capture(timer) {
+            Some(attempt) => {
+                let Ok(attempt) = serde_json::to_vec(&attempt) else {
+                    return;
+                };
+                if let Some(parent) = self.path.parent() {
+                    let _ = fs::create_dir_all(parent);
+                }
+                match fs::write(&self.path, attempt) {
+                    Ok(()) => self.saved = true,
+                    Err(e) => log::warn!("Failed to write the recovery file: {e}"),
+                }
+            }
+            // The attempt is over, so there's nothing to recover anymore.
+            None if self.saved => {
+                let _ = fs::remove_file(&self.path);
+                self.saved = false;
+            }
+            None => {}
+        }
+    }
+}
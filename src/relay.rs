@@ -0,0 +1,233 @@
+use std::{os::raw::c_char, ptr};
+
+use livesplit_core::Timer;
+
+use crate::{
+    events::{EventTracker, TimerEvent},
+    ffi::{
+        obs_data_t, obs_properties_add_editable_list, obs_properties_add_group,
+        obs_properties_create, obs_properties_t, obs_property_set_long_description,
+        OBS_EDITABLE_LIST_TYPE_STRINGS, OBS_GROUP_NORMAL,
+    },
+    get_string_list,
+};
+
+const SETTINGS_RELAY: *const c_char = cstr!("relay");
+const SETTINGS_RELAY_RUNNERS: *const c_char = cstr!("relay_runners");
+
+/// The custom variables that the layout can show, e.g. with a Text component.
+const VARIABLE_RUNNER: &str = "Runner";
+const VARIABLE_NEXT_RUNNER: &str = "Next Runner";
+const VARIABLE_LEG: &str = "Leg";
+
+pub struct Config {
+    runners: Vec<String>,
+}
+
+/// Keeps track of which runner of a relay is running. Each hand off starts a
+/// new leg at the current split.
+pub struct Relay {
+    config: Config,
+    tracker: EventTracker,
+    /// The split index that each leg after the first one started at.
+    hand_offs: Vec<usize>,
+}
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let group = obs_properties_create();
+    let runners = obs_properties_add_editable_list(
+        group,
+        SETTINGS_RELAY_RUNNERS,
+        cstr!("Runners"),
+        OBS_EDITABLE_LIST_TYPE_STRINGS,
+        ptr::null(),
+        ptr::null(),
+    );
+    obs_property_set_long_description(
+        runners,
+        cstr!("The runners in the order they run their legs. The Hand Off hotkey passes the run on to the next one."),
+    );
+
+    obs_properties_add_group(
+        props,
+        SETTINGS_RELAY,
+        cstr!("Relay"),
+        OBS_GROUP_NORMAL,
+        group,
+    );
+}
+
+pub unsafe fn parse_settings(settings: *mut obs_data_t) -> Option<Config> {
+    let runners: Vec<String> = get_string_list(settings, SETTINGS_RELAY_RUNNERS)
+        .into_iter()
+        .map(|runner| runner.trim().to_owned())
+        .filter(|runner| !runner.is_empty())
+        .collect();
+    if runners.is_empty() {
+        None
+    } else {
+        Some(Config { runners })
+    }
+}
+
+impl Relay {
+    pub fn new(config: Config, timer: &mut Timer) -> Self {
+        let relay = Self {
+            config,
+            tracker: EventTracker::new(timer),
+            hand_offs: Vec::new(),
+        };
+        relay.update_variables(timer);
+        relay
+    }
+
+    /// Changes the runners without losing track of the leg that is being run,
+    /// so the settings can be changed during the relay.
+    pub fn reconfigure(&mut self, config: Config, timer: &mut Timer) {
+        self.hand_offs.truncate(config.runners.len() - 1);
+        self.config = config;
+        self.tracker = EventTracker::new(timer);
+        self.update_variables(timer);
+    }
+
+    pub fn poll(&mut self, timer: &mut Timer) {
+        let mut changed = false;
+        for event in self.tracker.poll(timer) {
+            match event {
+                TimerEvent::Reset => {
+                    self.hand_offs.clear();
+                    changed = true;
+                }
+                // Undoing the split that a leg started at goes back to the
+                // runner before.
+                TimerEvent::UndoneSplit { index } => {
+                    while self.hand_offs.last().map_or(false, |&start| start > index) {
+                        self.hand_offs.pop();
+                        changed = true;
+                    }
+                }
+                _ => {}
+            }
+        }
+        if changed {
+            self.update_variables(timer);
+        }
+    }
+
+    /// Passes the run on to the next runner, starting their leg at the
+    /// current split.
+    pub fn hand_off(&mut self, timer: &mut Timer) {
+        let Some(index) = timer.current_split_index() else {
+            return;
+        };
+        if self.hand_offs.len() + 1 >= self.config.runners.len() {
+            log::warn!("The last runner can't hand off the run.");
+            return;
+        }
+        if self.hand_offs.last().map_or(false, |&start| start >= index) {
+            return;
+        }
+        self.hand_offs.push(index);
+        let runner = &self.config.runners[self.hand_offs.len()];
+        let segment = timer
+            .run()
+            .segments()
+            .get(index)
+            .map_or("", |segment| segment.name());
+        log::info!("{runner} took over at {segment}.");
+        self.update_variables(timer);
+    }
+
+    fn update_variables(&self, timer: &mut Timer) {
+        let leg = self.hand_offs.len();
+        let runners = &self.config.runners;
+        timer.set_custom_variable(VARIABLE_RUNNER, &runners[leg]);
+        timer.set_custom_variable(
+            VARIABLE_NEXT_RUNNER,
+            runners.get(leg + 1).map_or("", String::as_str),
+        );
+        timer.set_custom_variable(VARIABLE_LEG, &format!("{}/{}", leg + 1, runners.len()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use livesplit_core::{Run, Segment};
+
+    use super::*;
+
+    fn relay() -> (Relay, Timer) {
+        let mut run = Run::new();
+        for name in ["One", "Two", "Three"] {
+            run.push_segment(Segment::new(name));
+        }
+        let mut timer = Timer::new(run).unwrap();
+        let config = Config {
+            runners: vec!["A".into(), "B".into(), "C".into()],
+        };
+        let relay = Relay::new(config, &mut timer);
+        (relay, timer)
+    }
+
+    fn variables(timer: &Timer) -> [&str; 3] {
+        let metadata = timer.run().metadata();
+        [VARIABLE_RUNNER, VARIABLE_NEXT_RUNNER, VARIABLE_LEG].map(|name| {
+            metadata
+                .custom_variable(name)
+                .map_or("", |variable| variable.value.as_str())
+        })
+    }
+
+    #[test]
+    fn hands_off_the_run() {
+        let (mut relay, mut timer) = relay();
+        assert_eq!(variables(&timer), ["A", "B", "1/3"]);
+
+        timer.start();
+        timer.split();
+        relay.poll(&mut timer);
+        relay.hand_off(&mut timer);
+        assert_eq!(variables(&timer), ["B", "C", "2/3"]);
+
+        // Another leg can't start at the same split.
+        relay.hand_off(&mut timer);
+        assert_eq!(variables(&timer), ["B", "C", "2/3"]);
+
+        timer.split();
+        relay.poll(&mut timer);
+        relay.hand_off(&mut timer);
+        assert_eq!(variables(&timer), ["C", "", "3/3"]);
+
+        // There's nobody left to take over.
+        timer.split();
+        relay.poll(&mut timer);
+        relay.hand_off(&mut timer);
+        assert_eq!(variables(&timer), ["C", "", "3/3"]);
+    }
+
+    #[test]
+    fn goes_back_to_the_previous_runner() {
+        let (mut relay, mut timer) = relay();
+        timer.start();
+        timer.split();
+        relay.poll(&mut timer);
+        relay.hand_off(&mut timer);
+
+        timer.undo_split();
+        relay.poll(&mut timer);
+        assert_eq!(variables(&timer), ["A", "B", "1/3"]);
+    }
+
+    #[test]
+    fn starts_over_after_a_reset() {
+        let (mut relay, mut timer) = relay();
+        timer.start();
+        timer.split();
+        relay.poll(&mut timer);
+        relay.hand_off(&mut timer);
+
+        timer.reset(false);
+        relay.poll(&mut timer);
+        assert_eq!(variables(&timer), ["A", "B", "1/3"]);
+    }
+}
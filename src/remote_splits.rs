@@ -0,0 +1,197 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    ffi::{c_void, CStr},
+    fs,
+    hash::{Hash, Hasher},
+    io::Read,
+    os::raw::c_char,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    thread,
+};
+
+use crate::{
+    ffi::{
+        obs_data_get_string, obs_data_release, obs_data_t, obs_properties_add_button,
+        obs_properties_add_text, obs_properties_t, obs_property_t, obs_source_get_settings,
+        OBS_TEXT_DEFAULT,
+    },
+    http, module_config_path, State,
+};
+
+const SETTINGS_SPLITS_URL: *const c_char = cstr!("splits_url");
+const SETTINGS_DOWNLOAD_SPLITS: *const c_char = cstr!("splits_url_download");
+
+/// The splits that are being downloaded, so each of them is only downloaded
+/// once at a time.
+static DOWNLOADS: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    obs_properties_add_text(
+        props,
+        SETTINGS_SPLITS_URL,
+        cstr!("Splits URL (used instead of the file)"),
+        OBS_TEXT_DEFAULT,
+    );
+    obs_properties_add_button(
+        props,
+        SETTINGS_DOWNLOAD_SPLITS,
+        cstr!("Download Splits Again"),
+        Some(download_again),
+    );
+}
+
+pub unsafe fn get_url(settings: *mut obs_data_t) -> String {
+    CStr::from_ptr(obs_data_get_string(settings, SETTINGS_SPLITS_URL))
+        .to_string_lossy()
+        .trim()
+        .to_owned()
+}
+
+/// The splits of each URL are cached in OBS' config directory for this
+/// plugin, so they don't need to be downloaded every time OBS starts.
+unsafe fn cache_path(url: &str) -> Option<PathBuf> {
+    let mut hasher = DefaultHasher::new();
+    url.hash(&mut hasher);
+    module_config_path(&format!("splits/{:016x}.lss", hasher.finish()))
+}
+
+/// Returns the ID of the run if the URL points to a run on splits.io.
+fn splits_io_id(url: &str) -> Option<&str> {
+    let url = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(url);
+    let url = url.strip_prefix("www.").unwrap_or(url);
+    let id = url.strip_prefix("splits.io/")?.trim_end_matches('/');
+    if id.is_empty() || id.contains('/') {
+        return None;
+    }
+    Some(id)
+}
+
+fn download(url: &str, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    // splits.io hands out the splits in the format of the timer they were
+    // uploaded from when asked for the original timer's format.
+    let agent = http::agent();
+    let request = match splits_io_id(url) {
+        Some(id) => agent
+            .get(&format!("https://splits.io/api/v4/runs/{id}"))
+            .set("Accept", "application/original-timer"),
+        None => agent.get(url),
+    };
+
+    let mut splits = Vec::new();
+    request.call()?.into_reader().read_to_end(&mut splits)?;
+
+    // The splits are moved into place once they are complete, so they are
+    // never reloaded halfway written.
+    let partial = path.with_extension("part");
+    fs::write(&partial, splits)?;
+    fs::rename(&partial, path)?;
+
+    Ok(())
+}
+
+/// Downloads the splits on a thread of their own, so a slow server doesn't
+/// freeze OBS. The file watcher reloads the splits once they are written.
+fn download_in_background(url: String, path: PathBuf) {
+    {
+        let mut downloads = DOWNLOADS.lock().unwrap();
+        if downloads.contains(&path) {
+            return;
+        }
+        downloads.push(path.clone());
+    }
+    let result = thread::Builder::new()
+        .name("LiveSplit One Splits Download".into())
+        .spawn({
+            let path = path.clone();
+            move || {
+                match download(&url, &path) {
+                    Ok(()) => log::info!("Downloaded splits from {url}."),
+                    Err(e) => log::warn!("Failed to download splits from {url}: {e}"),
+                }
+                finish_download(&path);
+            }
+        });
+    if let Err(e) = result {
+        log::warn!("Failed to download splits: {e}");
+        finish_download(&path);
+    }
+}
+
+fn finish_download(path: &Path) {
+    DOWNLOADS
+        .lock()
+        .unwrap()
+        .retain(|download| download != path);
+}
+
+/// Returns the path of the cached splits of the URL. If they aren't cached
+/// yet, they are downloaded in the background, and the default splits are
+/// used until they are reloaded.
+pub unsafe fn cached_splits(url: &str) -> PathBuf {
+    let Some(path) = cache_path(url) else {
+        log::warn!("The config path of the module is unknown.");
+        return PathBuf::new();
+    };
+    if !path.exists() {
+        // The folder needs to exist already, so the file watcher can watch it
+        // for the downloaded splits.
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        download_in_background(url.to_owned(), path.clone());
+    }
+    path
+}
+
+unsafe extern "C" fn download_again(
+    _: *mut obs_properties_t,
+    _: *mut obs_property_t,
+    data: *mut c_void,
+) -> bool {
+    let state: &State = &*data.cast();
+    let settings = obs_source_get_settings(state.source);
+    let url = get_url(settings);
+    obs_data_release(settings);
+
+    if url.is_empty() {
+        return false;
+    }
+    let Some(path) = cache_path(&url) else {
+        return false;
+    };
+    download_in_background(url, path);
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_splits_io_runs() {
+        for url in [
+            "https://splits.io/abc",
+            "http://splits.io/abc",
+            "https://www.splits.io/abc",
+            "splits.io/abc/",
+        ] {
+            assert_eq!(splits_io_id(url), Some("abc"), "{url}");
+        }
+    }
+
+    #[test]
+    fn ignores_other_urls() {
+        for url in [
+            "https://splits.io/abc/edit",
+            "https://splits.io/",
+            "https://example.com/abc",
+            "https://notsplits.io/abc",
+        ] {
+            assert_eq!(splits_io_id(url), None, "{url}");
+        }
+    }
+}
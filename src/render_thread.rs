@@ -0,0 +1,123 @@
+use std::{
+    mem,
+    sync::{Arc, Condvar, Mutex},
+    thread::{self, JoinHandle},
+};
+
+use livesplit_core::layout::LayoutState;
+
+use crate::fonts;
+
+/// A rendered image of the layout.
+pub struct Frame {
+    pub image: Vec<u8>,
+    pub size: [u32; 2],
+    /// The size that the layout would like to be rendered at instead.
+    pub resize: Option<[f32; 2]>,
+}
+
+#[derive(Default)]
+struct Shared {
+    request: Option<(LayoutState, [u32; 2], f32)>,
+    finished: Option<Frame>,
+    spare: Vec<u8>,
+    quit: bool,
+}
+
+/// Renders the layout on a thread of its own, so that complex layouts don't
+/// stall OBS' graphics thread. The frames are double buffered: the thread
+/// renders into one buffer while the last finished one waits to be uploaded.
+pub struct RenderThread {
+    shared: Arc<(Mutex<Shared>, Condvar)>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl RenderThread {
+    pub fn new() -> Self {
+        let shared = Arc::new((Mutex::new(Shared::default()), Condvar::new()));
+        let thread = thread::Builder::new()
+            .name("LiveSplit One Renderer".into())
+            .spawn({
+                let shared = shared.clone();
+                move || render_loop(&shared)
+            })
+            .map_err(|e| log::error!("Failed to start the render thread: {e}"))
+            .ok();
+        Self { shared, thread }
+    }
+
+    /// Asks for the state to be rendered with the opacity. A request that
+    /// didn't start yet is replaced, as only the latest state is worth
+    /// rendering.
+    pub fn request(&self, state: &LayoutState, size: [u32; 2], opacity: f32) {
+        let (lock, condvar) = &*self.shared;
+        lock.lock().unwrap().request = Some((state.clone(), size, opacity));
+        condvar.notify_one();
+    }
+
+    /// Returns the latest frame that finished rendering since the last call.
+    pub fn take_frame(&self) -> Option<Frame> {
+        self.shared.0.lock().unwrap().finished.take()
+    }
+
+    /// Hands the buffer of an uploaded frame back, so it can be reused.
+    pub fn recycle(&self, frame: Frame) {
+        self.shared.0.lock().unwrap().spare = frame.image;
+    }
+}
+
+impl Drop for RenderThread {
+    fn drop(&mut self) {
+        let (lock, condvar) = &*self.shared;
+        lock.lock().unwrap().quit = true;
+        condvar.notify_one();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn render_loop(shared: &(Mutex<Shared>, Condvar)) {
+    let (lock, condvar) = shared;
+    let mut renderer = fonts::renderer();
+    loop {
+        let (state, size, opacity, mut image) = {
+            let mut shared = lock.lock().unwrap();
+            loop {
+                if shared.quit {
+                    return;
+                }
+                if let Some((state, size, opacity)) = shared.request.take() {
+                    break (state, size, opacity, mem::take(&mut shared.spare));
+                }
+                shared = condvar.wait(shared).unwrap();
+            }
+        };
+
+        // Every frame is uploaded as a whole, as OBS can't update part of a
+        // texture. Only states that differ from the last rendered one are
+        // requested in the first place, so this is rarely wasted.
+        let resize = renderer.render(&state, size);
+
+        image.clear();
+        image.extend_from_slice(renderer.image_data());
+        // The image has premultiplied alpha, so fading it out scales all of
+        // the channels.
+        if opacity < 1.0 {
+            for channel in &mut image {
+                *channel = (*channel as f32 * opacity).round() as u8;
+            }
+        }
+
+        let mut shared = lock.lock().unwrap();
+        // A frame that never got uploaded is outdated now, but its buffer can
+        // still be used for the next one.
+        if let Some(outdated) = shared.finished.replace(Frame {
+            image,
+            size,
+            resize,
+        }) {
+            shared.spare = outdated.image;
+        }
+    }
+}
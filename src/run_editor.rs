@@ -0,0 +1,467 @@
+use std::{
+    ffi::{c_void, CStr, CString},
+    fs,
+    os::raw::c_char,
+    ptr,
+    sync::mpsc,
+};
+
+use livesplit_core::{
+    run::editor::Editor,
+    timing::formatter::{Complete, TimeFormatter},
+    TimeSpan, TimerPhase,
+};
+
+use crate::{
+    custom_variables,
+    ffi::{
+        obs_data_get_bool, obs_data_get_int, obs_data_get_string, obs_data_release,
+        obs_data_set_bool, obs_data_set_int, obs_data_set_string, obs_data_t,
+        obs_properties_add_bool, obs_properties_add_button, obs_properties_add_group,
+        obs_properties_add_int, obs_properties_add_path, obs_properties_add_text,
+        obs_properties_create, obs_properties_get, obs_properties_t, obs_property_int_set_limits,
+        obs_property_set_long_description, obs_property_set_modified_callback2, obs_property_t,
+        obs_source_get_settings, obs_source_update_properties, OBS_GROUP_NORMAL, OBS_PATH_FILE,
+        OBS_TEXT_DEFAULT, OBS_TEXT_MULTILINE,
+    },
+    path, speedrun_com, State,
+};
+
+const SETTINGS_RUN_EDITOR: *const c_char = cstr!("run_editor");
+const SETTINGS_SEGMENT: *const c_char = cstr!("run_editor_segment");
+const SETTINGS_SEGMENT_NAME: *const c_char = cstr!("run_editor_segment_name");
+const SETTINGS_SEGMENT_ICON: *const c_char = cstr!("run_editor_segment_icon");
+const SETTINGS_REMOVE_ICON: *const c_char = cstr!("run_editor_remove_icon");
+const SETTINGS_SPLIT_TIME: *const c_char = cstr!("run_editor_split_time");
+const SETTINGS_BEST_SEGMENT: *const c_char = cstr!("run_editor_best_segment");
+const SETTINGS_APPLY: *const c_char = cstr!("run_editor_apply");
+const SETTINGS_INSERT_BELOW: *const c_char = cstr!("run_editor_insert_below");
+const SETTINGS_REMOVE: *const c_char = cstr!("run_editor_remove");
+const SETTINGS_OFFSET: *const c_char = cstr!("run_editor_offset");
+const SETTINGS_GAME: *const c_char = cstr!("run_editor_game");
+const SETTINGS_CATEGORY: *const c_char = cstr!("run_editor_category");
+const SETTINGS_PLATFORM: *const c_char = cstr!("run_editor_platform");
+const SETTINGS_REGION: *const c_char = cstr!("run_editor_region");
+const SETTINGS_EMULATOR: *const c_char = cstr!("run_editor_emulator");
+const SETTINGS_VARIABLES: *const c_char = cstr!("run_editor_variables");
+const SETTINGS_LOOK_UP: *const c_char = cstr!("run_editor_look_up");
+const SETTINGS_BOX_ART: *const c_char = cstr!("run_editor_box_art");
+
+/// How much the offset hotkeys change the offset by.
+const OFFSET_STEP: f64 = 1.0;
+
+pub unsafe fn add_properties(props: *mut obs_properties_t, state: &mut State) {
+    let segment_count = state.main_timer().read().unwrap().run().len();
+    let data: *mut c_void = (state as *mut State).cast();
+
+    let group = obs_properties_create();
+    let segment = obs_properties_add_int(
+        group,
+        SETTINGS_SEGMENT,
+        cstr!("Segment"),
+        1,
+        segment_count as _,
+        1,
+    );
+    obs_property_set_modified_callback2(segment, Some(select_segment), data);
+    obs_properties_add_text(
+        group,
+        SETTINGS_SEGMENT_NAME,
+        cstr!("Name"),
+        OBS_TEXT_DEFAULT,
+    );
+    let icon = obs_properties_add_path(
+        group,
+        SETTINGS_SEGMENT_ICON,
+        cstr!("New Icon"),
+        OBS_PATH_FILE,
+        cstr!("Images (*.png *.jpg *.jpeg *.gif *.bmp *.ico *.webp)"),
+        ptr::null(),
+    );
+    obs_property_set_long_description(
+        icon,
+        cstr!("An image that replaces the segment's icon when the changes are applied."),
+    );
+    obs_properties_add_button(
+        group,
+        SETTINGS_REMOVE_ICON,
+        cstr!("Remove Icon"),
+        Some(remove_icon),
+    );
+    obs_properties_add_text(
+        group,
+        SETTINGS_SPLIT_TIME,
+        cstr!("Personal Best Split Time"),
+        OBS_TEXT_DEFAULT,
+    );
+    obs_properties_add_text(
+        group,
+        SETTINGS_BEST_SEGMENT,
+        cstr!("Best Segment Time"),
+        OBS_TEXT_DEFAULT,
+    );
+    let offset = obs_properties_add_text(group, SETTINGS_OFFSET, cstr!("Offset"), OBS_TEXT_DEFAULT);
+    obs_property_set_long_description(
+        offset,
+        cstr!("The time the timer starts at. A negative offset, such as -5.0, counts down to the start of the run."),
+    );
+    obs_properties_add_text(group, SETTINGS_GAME, cstr!("Game"), OBS_TEXT_DEFAULT);
+    obs_properties_add_text(
+        group,
+        SETTINGS_CATEGORY,
+        cstr!("Category"),
+        OBS_TEXT_DEFAULT,
+    );
+    obs_properties_add_text(
+        group,
+        SETTINGS_PLATFORM,
+        cstr!("Platform"),
+        OBS_TEXT_DEFAULT,
+    );
+    obs_properties_add_text(group, SETTINGS_REGION, cstr!("Region"), OBS_TEXT_DEFAULT);
+    obs_properties_add_bool(group, SETTINGS_EMULATOR, cstr!("Uses Emulator"));
+    let variables = obs_properties_add_text(
+        group,
+        SETTINGS_VARIABLES,
+        cstr!("speedrun.com Variables"),
+        OBS_TEXT_MULTILINE,
+    );
+    obs_property_set_long_description(
+        variables,
+        cstr!("One variable per line, like \"Difficulty = Hard\"."),
+    );
+    obs_properties_add_bool(
+        group,
+        SETTINGS_BOX_ART,
+        cstr!("Use the Box Art from speedrun.com as the Game Icon"),
+    );
+    let look_up = obs_properties_add_button(
+        group,
+        SETTINGS_LOOK_UP,
+        cstr!("Look Up on speedrun.com"),
+        Some(look_up),
+    );
+    obs_property_set_long_description(
+        look_up,
+        cstr!("Fills in the names, the variables and the world record of the game and category above from speedrun.com."),
+    );
+    obs_properties_add_button(group, SETTINGS_APPLY, cstr!("Apply Changes"), Some(apply));
+    obs_properties_add_button(
+        group,
+        SETTINGS_INSERT_BELOW,
+        cstr!("Insert Segment Below"),
+        Some(insert_below),
+    );
+    obs_properties_add_button(
+        group,
+        SETTINGS_REMOVE,
+        cstr!("Remove Segment"),
+        Some(remove),
+    );
+
+    obs_properties_add_group(
+        props,
+        SETTINGS_RUN_EDITOR,
+        cstr!("Run Editor"),
+        OBS_GROUP_NORMAL,
+        group,
+    );
+}
+
+unsafe fn selected_index(settings: *mut obs_data_t) -> usize {
+    (obs_data_get_int(settings, SETTINGS_SEGMENT) as usize).saturating_sub(1)
+}
+
+unsafe fn get_string(settings: *mut obs_data_t, name: *const c_char) -> String {
+    CStr::from_ptr(obs_data_get_string(settings, name))
+        .to_string_lossy()
+        .into_owned()
+}
+
+unsafe fn set_string(settings: *mut obs_data_t, name: *const c_char, value: &str) {
+    if let Ok(value) = CString::new(value) {
+        obs_data_set_string(settings, name, value.as_ptr());
+    }
+}
+
+fn format_time(time: Option<TimeSpan>) -> String {
+    time.map(|time| Complete.format(time).to_string())
+        .unwrap_or_default()
+}
+
+/// Selects the segment in the editor and shows it in the properties.
+unsafe fn show_segment(editor: &mut Editor, index: usize, settings: *mut obs_data_t) {
+    editor.select_only(index);
+    obs_data_set_int(settings, SETTINGS_SEGMENT, index as i64 + 1);
+    let segment = editor.active_segment();
+    set_string(settings, SETTINGS_SEGMENT_NAME, segment.name());
+    // The icon can't be shown as a path, so only a new one can be chosen.
+    set_string(settings, SETTINGS_SEGMENT_ICON, "");
+    set_string(
+        settings,
+        SETTINGS_SPLIT_TIME,
+        &format_time(segment.split_time()),
+    );
+    set_string(
+        settings,
+        SETTINGS_BEST_SEGMENT,
+        &format_time(segment.best_segment_time()),
+    );
+    set_string(
+        settings,
+        SETTINGS_OFFSET,
+        &Complete.format(editor.run().offset()).to_string(),
+    );
+
+    let run = editor.run();
+    let metadata = run.metadata();
+    set_string(settings, SETTINGS_GAME, run.game_name());
+    set_string(settings, SETTINGS_CATEGORY, run.category_name());
+    set_string(settings, SETTINGS_PLATFORM, metadata.platform_name());
+    set_string(settings, SETTINGS_REGION, metadata.region_name());
+    obs_data_set_bool(settings, SETTINGS_EMULATOR, metadata.uses_emulator());
+    let variables: Vec<_> = metadata
+        .speedrun_com_variables()
+        .map(|(name, value)| format!("{name} = {value}"))
+        .collect();
+    set_string(settings, SETTINGS_VARIABLES, &variables.join("\n"));
+}
+
+/// Writes the metadata of the run from the settings to the editor.
+unsafe fn apply_metadata(editor: &mut Editor, settings: *mut obs_data_t) {
+    editor.set_game_name(get_string(settings, SETTINGS_GAME));
+    editor.set_category_name(get_string(settings, SETTINGS_CATEGORY));
+    editor.set_platform_name(get_string(settings, SETTINGS_PLATFORM));
+    editor.set_region_name(get_string(settings, SETTINGS_REGION));
+    editor.set_emulator_usage(obs_data_get_bool(settings, SETTINGS_EMULATOR));
+
+    let variables = custom_variables::parse_lines(&get_string(settings, SETTINGS_VARIABLES));
+    let removed: Vec<String> = editor
+        .run()
+        .metadata()
+        .speedrun_com_variables()
+        .map(|(name, _)| name.to_owned())
+        .filter(|name| !variables.iter().any(|(variable, _)| variable == name))
+        .collect();
+    for name in removed {
+        editor.remove_speedrun_com_variable(&name);
+    }
+    for (name, value) in variables {
+        editor.set_speedrun_com_variable(name, value);
+    }
+}
+
+unsafe fn open_editor(state: &State, settings: *mut obs_data_t) -> Option<(Editor, usize)> {
+    let timer = state.main_timer().read().unwrap();
+    let mut editor = Editor::new(timer.run().clone()).ok()?;
+    editor.select_timing_method(timer.current_timing_method());
+    let index = selected_index(settings).min(editor.run().len() - 1);
+    editor.select_only(index);
+    Some((editor, index))
+}
+
+unsafe extern "C" fn select_segment(
+    data: *mut c_void,
+    _: *mut obs_properties_t,
+    _: *mut obs_property_t,
+    settings: *mut obs_data_t,
+) -> bool {
+    let state: &mut State = &mut *data.cast();
+    if let Some((mut editor, index)) = open_editor(state, settings) {
+        show_segment(&mut editor, index, settings);
+    }
+    true
+}
+
+/// Applies a modification to the run through the run editor and writes the
+/// result back to the splits file. The modification returns the index of the
+/// segment to show afterwards. Returns the number of segments of the run if it
+/// could be edited.
+unsafe fn edit(
+    state: &mut State,
+    edit: impl FnOnce(&mut Editor, usize, *mut obs_data_t) -> usize,
+) -> Option<usize> {
+    if state.main_timer().read().unwrap().current_phase() != TimerPhase::NotRunning {
+        log::warn!("The run can't be edited while the timer is running.");
+        return None;
+    }
+
+    let settings = obs_source_get_settings(state.source);
+    let segment_count = open_editor(state, settings).map(|(mut editor, index)| {
+        let index = edit(&mut editor, index, settings);
+        show_segment(&mut editor, index, settings);
+
+        let run = editor.close();
+        let segment_count = run.len();
+        if state.main_timer().write().unwrap().set_run(run).is_ok() {
+            state.save_splits();
+        }
+        segment_count
+    });
+    obs_data_release(settings);
+    segment_count
+}
+
+/// Edits the run like [`edit`] does from a button in the properties, which get
+/// updated to the new number of segments.
+unsafe fn edit_run(
+    props: *mut obs_properties_t,
+    data: *mut c_void,
+    modification: impl FnOnce(&mut Editor, usize, *mut obs_data_t) -> usize,
+) -> bool {
+    let state: &mut State = &mut *data.cast();
+    let Some(segment_count) = edit(state, modification) else {
+        return false;
+    };
+    obs_property_int_set_limits(
+        obs_properties_get(props, SETTINGS_SEGMENT),
+        1,
+        segment_count as _,
+        1,
+    );
+    true
+}
+
+unsafe extern "C" fn apply(
+    props: *mut obs_properties_t,
+    _: *mut obs_property_t,
+    data: *mut c_void,
+) -> bool {
+    edit_run(props, data, |editor, index, settings| {
+        let mut segment = editor.active_segment();
+        segment.set_name(get_string(settings, SETTINGS_SEGMENT_NAME));
+        let icon_path = path::from_obs(obs_data_get_string(settings, SETTINGS_SEGMENT_ICON));
+        if !icon_path.as_os_str().is_empty() {
+            match fs::read(&icon_path) {
+                Ok(icon) => segment.set_icon(&*icon),
+                Err(e) => log::warn!("Failed to read the icon: {e}"),
+            }
+        }
+        if segment
+            .parse_and_set_split_time(&get_string(settings, SETTINGS_SPLIT_TIME))
+            .is_err()
+        {
+            log::warn!("Invalid split time.");
+        }
+        if segment
+            .parse_and_set_best_segment_time(&get_string(settings, SETTINGS_BEST_SEGMENT))
+            .is_err()
+        {
+            log::warn!("Invalid best segment time.");
+        }
+        if editor
+            .parse_and_set_offset(&get_string(settings, SETTINGS_OFFSET))
+            .is_err()
+        {
+            log::warn!("Invalid offset.");
+        }
+        apply_metadata(editor, settings);
+        index
+    })
+}
+
+unsafe extern "C" fn insert_below(
+    props: *mut obs_properties_t,
+    _: *mut obs_property_t,
+    data: *mut c_void,
+) -> bool {
+    edit_run(props, data, |editor, index, _| {
+        editor.insert_segment_below();
+        index + 1
+    })
+}
+
+unsafe extern "C" fn remove(
+    props: *mut obs_properties_t,
+    _: *mut obs_property_t,
+    data: *mut c_void,
+) -> bool {
+    edit_run(props, data, |editor, index, _| {
+        if editor.can_remove_segments() {
+            editor.remove_segments();
+        }
+        index.min(editor.run().len() - 1)
+    })
+}
+
+unsafe extern "C" fn remove_icon(
+    props: *mut obs_properties_t,
+    _: *mut obs_property_t,
+    data: *mut c_void,
+) -> bool {
+    edit_run(props, data, |editor, index, _| {
+        editor.active_segment().remove_icon();
+        index
+    })
+}
+
+unsafe extern "C" fn look_up(
+    _: *mut obs_properties_t,
+    _: *mut obs_property_t,
+    data: *mut c_void,
+) -> bool {
+    let state: &mut State = &mut *data.cast();
+    if state.speedrun_com_lookup.is_some() {
+        return false;
+    }
+    let settings = obs_source_get_settings(state.source);
+    let game = get_string(settings, SETTINGS_GAME);
+    let category = get_string(settings, SETTINGS_CATEGORY);
+    let box_art = obs_data_get_bool(settings, SETTINGS_BOX_ART);
+    obs_data_release(settings);
+
+    state.speedrun_com_lookup = speedrun_com::look_up_in_background(game, category, box_art);
+    false
+}
+
+/// Applies the speedrun.com lookup to the run once it's done and shows the
+/// result in the properties.
+pub unsafe fn finish_look_up(state: &mut State) {
+    let Some(lookup) = &state.speedrun_com_lookup else {
+        return;
+    };
+    let lookup = match lookup.try_recv() {
+        Ok(lookup) => lookup,
+        Err(mpsc::TryRecvError::Empty) => return,
+        Err(mpsc::TryRecvError::Disconnected) => Err("The lookup was cancelled.".into()),
+    };
+    state.speedrun_com_lookup = None;
+
+    match lookup {
+        Ok(lookup) => {
+            let edited = edit(state, |editor, index, _| {
+                lookup.apply(editor);
+                index
+            });
+            if edited.is_some() {
+                obs_source_update_properties(state.source);
+            }
+        }
+        Err(e) => log::warn!("Failed to look up the run on speedrun.com: {e}"),
+    }
+}
+
+/// Moves the offset of the run by the amount of steps and saves the splits.
+pub unsafe fn shift_offset(state: &mut State, steps: f64) {
+    let offset = {
+        let mut timer = state.main_timer().write().unwrap();
+        if timer.current_phase() != TimerPhase::NotRunning {
+            log::warn!("The offset can't be changed while the timer is running.");
+            return;
+        }
+        let mut run = timer.run().clone();
+        let offset = run.offset() + TimeSpan::from_seconds(steps * OFFSET_STEP);
+        run.set_offset(offset);
+        if timer.set_run(run).is_err() {
+            return;
+        }
+        offset
+    };
+    state.save_splits();
+
+    let offset = Complete.format(offset).to_string();
+    log::info!("Changed the offset to {offset}.");
+    let settings = obs_source_get_settings(state.source);
+    set_string(settings, SETTINGS_OFFSET, &offset);
+    obs_data_release(settings);
+}
@@ -0,0 +1,155 @@
+use std::{
+    ffi::{c_void, CString},
+    os::raw::c_char,
+};
+
+use livesplit_core::{Run, SharedTimer, Time, TimerPhase};
+
+use crate::{
+    backup,
+    ffi::{
+        obs_data_get_bool, obs_data_get_int, obs_data_release, obs_data_set_bool,
+        obs_properties_add_bool, obs_properties_add_button, obs_properties_add_group,
+        obs_properties_add_list, obs_properties_create, obs_properties_t,
+        obs_property_list_add_int, obs_property_set_long_description, obs_property_t,
+        obs_source_get_settings, OBS_COMBO_FORMAT_INT, OBS_COMBO_TYPE_LIST, OBS_GROUP_NORMAL,
+    },
+    State,
+};
+
+const SETTINGS_MAINTENANCE: *const c_char = cstr!("maintenance");
+const SETTINGS_MAINTENANCE_CONFIRM: *const c_char = cstr!("maintenance_confirm");
+const SETTINGS_MAINTENANCE_CLEAR_HISTORY: *const c_char = cstr!("maintenance_clear_history");
+const SETTINGS_MAINTENANCE_REMOVE_PB: *const c_char = cstr!("maintenance_remove_pb");
+const SETTINGS_MAINTENANCE_SEGMENT: *const c_char = cstr!("maintenance_segment");
+const SETTINGS_MAINTENANCE_CLEAR_SEGMENT: *const c_char = cstr!("maintenance_clear_segment");
+
+pub unsafe fn add_properties(props: *mut obs_properties_t, timer: Option<&SharedTimer>) {
+    let group = obs_properties_create();
+    let confirm = obs_properties_add_bool(
+        group,
+        SETTINGS_MAINTENANCE_CONFIRM,
+        cstr!("I Understand That This Can't Be Undone"),
+    );
+    obs_property_set_long_description(
+        confirm,
+        cstr!("Needs to be checked again before every button below. The splits are backed up before they're changed."),
+    );
+    obs_properties_add_button(
+        group,
+        SETTINGS_MAINTENANCE_CLEAR_HISTORY,
+        cstr!("Clear History"),
+        Some(clear_history),
+    );
+    obs_properties_add_button(
+        group,
+        SETTINGS_MAINTENANCE_REMOVE_PB,
+        cstr!("Remove Personal Best"),
+        Some(remove_personal_best),
+    );
+    let segments = obs_properties_add_list(
+        group,
+        SETTINGS_MAINTENANCE_SEGMENT,
+        cstr!("Segment"),
+        OBS_COMBO_TYPE_LIST,
+        OBS_COMBO_FORMAT_INT,
+    );
+    if let Some(timer) = timer {
+        let timer = timer.read().unwrap();
+        for (index, segment) in timer.run().segments().iter().enumerate() {
+            if let Ok(name) = CString::new(format!("{}. {}", index + 1, segment.name())) {
+                obs_property_list_add_int(segments, name.as_ptr(), index as _);
+            }
+        }
+    }
+    obs_properties_add_button(
+        group,
+        SETTINGS_MAINTENANCE_CLEAR_SEGMENT,
+        cstr!("Clear Segment History"),
+        Some(clear_segment_history),
+    );
+
+    obs_properties_add_group(
+        props,
+        SETTINGS_MAINTENANCE,
+        cstr!("Maintenance"),
+        OBS_GROUP_NORMAL,
+        group,
+    );
+}
+
+/// Changes the run if the change was confirmed, and writes it back to the
+/// splits file. The confirmation only holds for a single change.
+pub unsafe fn maintain(
+    data: *mut c_void,
+    done: &str,
+    change: impl FnOnce(&mut Run, usize),
+) -> bool {
+    let state: &mut State = &mut *data.cast();
+    let settings = obs_source_get_settings(state.source);
+    let confirmed = obs_data_get_bool(settings, SETTINGS_MAINTENANCE_CONFIRM);
+    let segment = obs_data_get_int(settings, SETTINGS_MAINTENANCE_SEGMENT).max(0) as usize;
+    obs_data_set_bool(settings, SETTINGS_MAINTENANCE_CONFIRM, false);
+    obs_data_release(settings);
+
+    if !confirmed {
+        log::warn!("Confirm that the splits may be changed first.");
+        return false;
+    }
+
+    {
+        let mut timer = state.main_timer().write().unwrap();
+        if timer.current_phase() != TimerPhase::NotRunning {
+            log::warn!("The splits can't be changed while the timer is running.");
+            return true;
+        }
+        let mut run = timer.run().clone();
+        change(&mut run, segment.min(run.len() - 1));
+        if timer.set_run(run).is_err() {
+            return true;
+        }
+    }
+
+    // Saving the splits only backs them up if backups are turned on, but these
+    // changes are worth a backup either way.
+    if state.backup_count == 0 {
+        backup::backup(&state.splits_path, 1);
+    }
+    state.save_splits();
+    log::info!("{done}");
+    true
+}
+
+unsafe extern "C" fn clear_history(
+    _: *mut obs_properties_t,
+    _: *mut obs_property_t,
+    data: *mut c_void,
+) -> bool {
+    maintain(data, "Cleared the history.", |run, _| run.clear_history())
+}
+
+unsafe extern "C" fn remove_personal_best(
+    _: *mut obs_properties_t,
+    _: *mut obs_property_t,
+    data: *mut c_void,
+) -> bool {
+    maintain(data, "Removed the personal best.", |run, _| {
+        for segment in run.segments_mut() {
+            *segment.personal_best_split_time_mut() = Time::default();
+        }
+    })
+}
+
+unsafe extern "C" fn clear_segment_history(
+    _: *mut obs_properties_t,
+    _: *mut obs_property_t,
+    data: *mut c_void,
+) -> bool {
+    maintain(
+        data,
+        "Cleared the history of the segment.",
+        |run, segment| {
+            run.segment_mut(segment).segment_history_mut().clear();
+        },
+    )
+}
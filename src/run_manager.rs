@@ -0,0 +1,289 @@
+use std::{
+    ffi::{c_void, CStr, CString},
+    fs::File,
+    io::BufWriter,
+    os::raw::c_char,
+    ptr,
+    sync::atomic::{self, AtomicPtr},
+};
+
+use livesplit_core::{
+    run::{
+        editor::Editor,
+        saver::livesplit::{save_timer, IoWrite},
+    },
+    TimerPhase,
+};
+
+use crate::{
+    backup,
+    ffi::{
+        obs_data_create, obs_data_get_string, obs_data_release, obs_data_set_string, obs_data_t,
+        obs_enum_sources, obs_properties_add_button, obs_properties_add_path,
+        obs_properties_add_text, obs_properties_create, obs_properties_t, obs_property_t,
+        obs_source_create_private, obs_source_get_settings, obs_source_get_unversioned_id,
+        obs_source_info, obs_source_release, obs_source_t, obs_source_update,
+        OBS_ICON_TYPE_GAME_CAPTURE, OBS_PATH_FILE, OBS_SOURCE_CAP_DISABLED, OBS_SOURCE_TYPE_INPUT,
+        OBS_TEXT_DEFAULT,
+    },
+    frontend, parse_run, path, register_source, splits_timer, UnsafeMultiThread,
+    SETTINGS_LAYOUT_PATH, SETTINGS_SPLITS_PATH,
+};
+
+const SOURCE_ID: *const c_char = cstr!("livesplit-one-run-manager");
+const SETTINGS_GAME_NAME: *const c_char = cstr!("game_name");
+const SETTINGS_CATEGORY_NAME: *const c_char = cstr!("category_name");
+const SETTINGS_SAVE: *const c_char = cstr!("save");
+
+/// A private source whose properties are used as the dialog for managing the
+/// run of all the LiveSplit One sources at once. It's created the first time
+/// the dialog is opened.
+static SOURCE: AtomicPtr<obs_source_t> = AtomicPtr::new(ptr::null_mut());
+
+unsafe fn set_string(settings: *mut obs_data_t, name: *const c_char, value: &str) {
+    if let Ok(value) = CString::new(value) {
+        obs_data_set_string(settings, name, value.as_ptr());
+    }
+}
+
+unsafe fn get_string(settings: *mut obs_data_t, name: *const c_char) -> String {
+    CStr::from_ptr(obs_data_get_string(settings, name))
+        .to_string_lossy()
+        .into_owned()
+}
+
+unsafe fn is_livesplit_one(source: *mut obs_source_t) -> bool {
+    let id = obs_source_get_unversioned_id(source);
+    !id.is_null() && CStr::from_ptr(id).to_bytes() == b"livesplit-one"
+}
+
+/// Copies the paths of the first LiveSplit One source into the settings.
+unsafe extern "C" fn copy_paths(param: *mut c_void, source: *mut obs_source_t) -> bool {
+    if !is_livesplit_one(source) {
+        return true;
+    }
+    let settings: *mut obs_data_t = param.cast();
+    let source_settings = obs_source_get_settings(source);
+    for name in [SETTINGS_SPLITS_PATH, SETTINGS_LAYOUT_PATH] {
+        obs_data_set_string(settings, name, obs_data_get_string(source_settings, name));
+    }
+    obs_data_release(source_settings);
+    false
+}
+
+/// Applies the paths in the settings to a LiveSplit One source.
+unsafe extern "C" fn apply_paths(param: *mut c_void, source: *mut obs_source_t) -> bool {
+    if is_livesplit_one(source) {
+        let settings: *mut obs_data_t = param.cast();
+        let changes = obs_data_create();
+        for name in [SETTINGS_SPLITS_PATH, SETTINGS_LAYOUT_PATH] {
+            obs_data_set_string(changes, name, obs_data_get_string(settings, name));
+        }
+        obs_source_update(source, changes);
+        obs_data_release(changes);
+    }
+    true
+}
+
+/// Fills the settings with the current paths of the sources and the current
+/// metadata of their run.
+unsafe fn refresh(settings: *mut obs_data_t) {
+    obs_enum_sources(Some(copy_paths), settings.cast());
+    let timer = splits_timer(settings);
+    let timer = timer.read().unwrap();
+    set_string(settings, SETTINGS_GAME_NAME, timer.run().game_name());
+    set_string(
+        settings,
+        SETTINGS_CATEGORY_NAME,
+        timer.run().category_name(),
+    );
+}
+
+unsafe extern "C" fn open(_: *mut c_void) {
+    let mut source = SOURCE.load(atomic::Ordering::Acquire);
+    if source.is_null() {
+        source = obs_source_create_private(SOURCE_ID, cstr!("LiveSplit One"), ptr::null_mut());
+        if source.is_null() {
+            return;
+        }
+        SOURCE.store(source, atomic::Ordering::Release);
+    }
+
+    let settings = obs_source_get_settings(source);
+    refresh(settings);
+    obs_data_release(settings);
+
+    frontend::open_source_properties(source);
+}
+
+unsafe extern "C" fn get_name(_: *mut c_void) -> *const c_char {
+    cstr!("LiveSplit One Run Manager")
+}
+
+unsafe extern "C" fn create(_: *mut obs_data_t, source: *mut obs_source_t) -> *mut c_void {
+    source.cast()
+}
+
+unsafe extern "C" fn destroy(_: *mut c_void) {}
+
+unsafe extern "C" fn update(_: *mut c_void, settings: *mut obs_data_t) {
+    // Every change in the dialog ends up here, so the sources are only updated
+    // if the paths actually changed.
+    let current = obs_data_create();
+    obs_enum_sources(Some(copy_paths), current.cast());
+    let changed = [SETTINGS_SPLITS_PATH, SETTINGS_LAYOUT_PATH]
+        .iter()
+        .any(|&name| get_string(settings, name) != get_string(current, name));
+    obs_data_release(current);
+
+    if changed {
+        log::info!("Applying splits and layout to all sources.");
+        obs_enum_sources(Some(apply_paths), settings.cast());
+
+        let timer = splits_timer(settings);
+        let timer = timer.read().unwrap();
+        set_string(settings, SETTINGS_GAME_NAME, timer.run().game_name());
+        set_string(
+            settings,
+            SETTINGS_CATEGORY_NAME,
+            timer.run().category_name(),
+        );
+    }
+}
+
+unsafe extern "C" fn save(
+    _: *mut obs_properties_t,
+    _: *mut obs_property_t,
+    data: *mut c_void,
+) -> bool {
+    let source: *mut obs_source_t = data.cast();
+    let settings = obs_source_get_settings(source);
+
+    let splits_path = path::from_obs(obs_data_get_string(settings, SETTINGS_SPLITS_PATH));
+    let timer = splits_timer(settings);
+    let mut timer = timer.write().unwrap();
+
+    if timer.current_phase() != TimerPhase::NotRunning {
+        log::warn!("The run can't be edited while the timer is running.");
+    } else if let Ok(mut editor) = Editor::new(timer.run().clone()) {
+        editor.set_game_name(get_string(settings, SETTINGS_GAME_NAME));
+        editor.set_category_name(get_string(settings, SETTINGS_CATEGORY_NAME));
+        if timer.set_run(editor.close()).is_ok() {
+            // Splits in other formats would get overwritten by LiveSplit's
+            // format, so only those are saved.
+            if parse_run(&splits_path).map_or(false, |(_, can_save)| can_save) {
+                backup::backup(&splits_path, backup::DEFAULT_COUNT);
+                if let Ok(file) = File::create(&splits_path) {
+                    let _ = save_timer(&timer, IoWrite(BufWriter::new(file)));
+                }
+            } else {
+                log::warn!("The splits can't be saved in their format.");
+            }
+        }
+    }
+
+    drop(timer);
+    obs_data_release(settings);
+    false
+}
+
+unsafe extern "C" fn get_properties(_: *mut c_void) -> *mut obs_properties_t {
+    let props = obs_properties_create();
+    obs_properties_add_path(
+        props,
+        SETTINGS_SPLITS_PATH,
+        cstr!("Splits"),
+        OBS_PATH_FILE,
+        cstr!("LiveSplit Splits (*.lss)"),
+        ptr::null(),
+    );
+    obs_properties_add_path(
+        props,
+        SETTINGS_LAYOUT_PATH,
+        cstr!("Layout"),
+        OBS_PATH_FILE,
+        cstr!("LiveSplit Layouts (*.lsl *.ls1l)"),
+        ptr::null(),
+    );
+    obs_properties_add_text(props, SETTINGS_GAME_NAME, cstr!("Game"), OBS_TEXT_DEFAULT);
+    obs_properties_add_text(
+        props,
+        SETTINGS_CATEGORY_NAME,
+        cstr!("Category"),
+        OBS_TEXT_DEFAULT,
+    );
+    obs_properties_add_button(props, SETTINGS_SAVE, cstr!("Save Splits"), Some(save));
+    props
+}
+
+pub fn register() {
+    static SOURCE_INFO: UnsafeMultiThread<obs_source_info> = UnsafeMultiThread(obs_source_info {
+        id: SOURCE_ID,
+        type_: OBS_SOURCE_TYPE_INPUT,
+        output_flags: OBS_SOURCE_CAP_DISABLED,
+        get_name: Some(get_name),
+        create: Some(create),
+        destroy: Some(destroy),
+        get_width: None,
+        get_height: None,
+        video_render: None,
+        mouse_wheel: None,
+        get_properties: Some(get_properties),
+        get_defaults: None,
+        update: Some(update),
+        icon_type: OBS_ICON_TYPE_GAME_CAPTURE,
+        activate: None,
+        deactivate: None,
+        show: None,
+        hide: None,
+        video_tick: None,
+        filter_video: None,
+        filter_audio: None,
+        enum_active_sources: None,
+        save: None,
+        load: None,
+        mouse_click: None,
+        mouse_move: None,
+        focus: None,
+        key_click: None,
+        filter_remove: None,
+        type_data: ptr::null_mut(),
+        free_type_data: None,
+        audio_render: None,
+        enum_all_sources: None,
+        transition_start: None,
+        transition_stop: None,
+        get_defaults2: None,
+        get_properties2: None,
+        audio_mix: None,
+        media_play_pause: None,
+        media_restart: None,
+        media_stop: None,
+        media_next: None,
+        media_previous: None,
+        media_get_duration: None,
+        media_get_time: None,
+        media_set_time: None,
+        media_get_state: None,
+        version: 0,
+        unversioned_id: ptr::null(),
+        missing_files: None,
+        video_get_color_space: None,
+    });
+
+    unsafe {
+        register_source(&SOURCE_INFO.0);
+    }
+}
+
+/// Adds the entry for the dialog to the frontend's Tools menu.
+pub unsafe fn add_tools_menu_item() {
+    frontend::add_tools_menu_item(cstr!("LiveSplit One"), open, ptr::null_mut());
+}
+
+pub unsafe fn unload() {
+    let source = SOURCE.swap(ptr::null_mut(), atomic::Ordering::AcqRel);
+    if !source.is_null() {
+        obs_source_release(source);
+    }
+}
@@ -0,0 +1,170 @@
+use std::{
+    ffi::{c_void, CStr},
+    fmt::Write,
+    os::raw::c_char,
+};
+
+use livesplit_core::{
+    analysis::sum_of_segments,
+    timing::formatter::{Delta, Regular, TimeFormatter},
+    TimeSpan, Timer,
+};
+
+use crate::{
+    ffi::{
+        obs_data_get_string, obs_data_release, obs_data_set_default_string, obs_data_t,
+        obs_properties_add_button, obs_properties_add_group, obs_properties_add_list,
+        obs_properties_create, obs_properties_t, obs_property_list_add_string, obs_property_t,
+        obs_source_get_settings, OBS_COMBO_FORMAT_STRING, OBS_COMBO_TYPE_LIST, OBS_GROUP_NORMAL,
+    },
+    State,
+};
+
+const SETTINGS_RUN_SUMMARY: *const c_char = cstr!("run_summary");
+const SETTINGS_RUN_SUMMARY_FORMAT: *const c_char = cstr!("run_summary_format");
+const SETTINGS_RUN_SUMMARY_COPY: *const c_char = cstr!("run_summary_copy");
+
+/// A row of the summary with the segment's name, split time, delta and
+/// segment time.
+type Row = [String; 4];
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let group = obs_properties_create();
+    let format = obs_properties_add_list(
+        group,
+        SETTINGS_RUN_SUMMARY_FORMAT,
+        cstr!("Format"),
+        OBS_COMBO_TYPE_LIST,
+        OBS_COMBO_FORMAT_STRING,
+    );
+    obs_property_list_add_string(format, cstr!("Markdown Table"), cstr!("markdown"));
+    obs_property_list_add_string(format, cstr!("Plain Text"), cstr!("text"));
+    obs_properties_add_button(
+        group,
+        SETTINGS_RUN_SUMMARY_COPY,
+        cstr!("Copy Run Summary"),
+        Some(copy),
+    );
+
+    obs_properties_add_group(
+        props,
+        SETTINGS_RUN_SUMMARY,
+        cstr!("Run Summary"),
+        OBS_GROUP_NORMAL,
+        group,
+    );
+}
+
+pub unsafe fn set_defaults(settings: *mut obs_data_t) {
+    obs_data_set_default_string(settings, SETTINGS_RUN_SUMMARY_FORMAT, cstr!("markdown"));
+}
+
+/// Collects a row for every segment, along with the final time and the sum of
+/// best segments.
+fn rows(timer: &Timer) -> (Vec<Row>, Option<TimeSpan>, Option<TimeSpan>) {
+    let run = timer.run();
+    let method = timer.current_timing_method();
+    let comparison = timer.current_comparison();
+
+    let mut previous_split = Some(TimeSpan::zero());
+    let mut final_time = None;
+    let rows = run
+        .segments()
+        .iter()
+        .map(|segment| {
+            let split = segment.split_time()[method];
+            let delta = split
+                .zip(segment.comparison(comparison)[method])
+                .map(|(split, comparison)| split - comparison);
+            // Skipped splits add their time to the next segment.
+            let segment_time = split
+                .zip(previous_split)
+                .map(|(split, previous)| split - previous);
+            if split.is_some() {
+                previous_split = split;
+                final_time = split;
+            }
+            [
+                segment.name().to_owned(),
+                Regular::new().format(split).to_string(),
+                delta.map_or_else(String::new, |delta| Delta::new().format(delta).to_string()),
+                Regular::new().format(segment_time).to_string(),
+            ]
+        })
+        .collect();
+    let sum_of_best = sum_of_segments::calculate_best(run.segments(), false, true, method);
+    (rows, final_time, sum_of_best)
+}
+
+fn markdown(timer: &Timer) -> String {
+    let (rows, final_time, sum_of_best) = rows(timer);
+    let mut text = format!("**{}**\n\n", timer.run().extended_name(true));
+    text.push_str("| Segment | Split | Delta | Segment Time |\n");
+    text.push_str("| :--- | ---: | ---: | ---: |\n");
+    for [name, split, delta, segment] in rows {
+        let name = name.replace('|', "\\|");
+        let _ = writeln!(text, "| {name} | {split} | {delta} | {segment} |");
+    }
+    let _ = write!(
+        text,
+        "\n**Final Time:** {}  \n**Sum of Best:** {}\n",
+        Regular::new().format(final_time),
+        Regular::new().format(sum_of_best),
+    );
+    text
+}
+
+fn plain_text(timer: &Timer) -> String {
+    let (rows, final_time, sum_of_best) = rows(timer);
+    let mut widths = [0; 4];
+    for row in &rows {
+        for (width, column) in widths.iter_mut().zip(row) {
+            *width = (*width).max(column.chars().count());
+        }
+    }
+    let mut text = format!("{}\n\n", timer.run().extended_name(true));
+    for [name, split, delta, segment] in rows {
+        let _ = writeln!(
+            text,
+            "{name:<0$}  {split:>1$}  {delta:>2$}  {segment:>3$}",
+            widths[0], widths[1], widths[2], widths[3],
+        );
+    }
+    let _ = write!(
+        text,
+        "\nFinal Time: {}\nSum of Best: {}\n",
+        Regular::new().format(final_time),
+        Regular::new().format(sum_of_best),
+    );
+    text
+}
+
+unsafe extern "C" fn copy(
+    _: *mut obs_properties_t,
+    _: *mut obs_property_t,
+    data: *mut c_void,
+) -> bool {
+    let state: &State = &*data.cast();
+    let settings = obs_source_get_settings(state.source);
+    let is_markdown = CStr::from_ptr(obs_data_get_string(settings, SETTINGS_RUN_SUMMARY_FORMAT))
+        .to_bytes()
+        != b"text";
+    obs_data_release(settings);
+
+    let summary = {
+        let timer = state.timer.read().unwrap();
+        if is_markdown {
+            markdown(&timer)
+        } else {
+            plain_text(&timer)
+        }
+    };
+    // On Linux, the clipboard is handed over to the clipboard manager once it
+    // gets dropped, so it doesn't need to be kept around.
+    let result = arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(summary));
+    match result {
+        Ok(()) => log::info!("Copied the run summary to the clipboard."),
+        Err(e) => log::warn!("Failed to copy the run summary: {e}"),
+    }
+    false
+}
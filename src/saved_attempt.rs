@@ -0,0 +1,251 @@
+use std::{
+    ffi::{CStr, CString},
+    os::raw::c_char,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use livesplit_core::{SharedTimer, TimeSpan, Timer, TimerPhase};
+use serde::{Deserialize, Serialize};
+
+use crate::ffi::{obs_data_get_string, obs_data_set_string, obs_data_t};
+
+const SETTINGS_SAVED_ATTEMPT: *const c_char = cstr!("saved_attempt");
+
+/// The state of an attempt that is in progress, stored so that the attempt
+/// survives OBS restarting.
+#[derive(Serialize, Deserialize)]
+pub struct SavedAttempt {
+    paused: bool,
+    split_index: usize,
+    real_time: f64,
+    game_time: Option<f64>,
+    saved_at: f64,
+}
+
+fn now() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0.0, |time| time.as_secs_f64())
+}
+
+impl SavedAttempt {
+    /// Captures the attempt that is in progress, if there is one.
+    pub fn capture(timer: &Timer) -> Option<Self> {
+        let phase = timer.current_phase();
+        if phase != TimerPhase::Running && phase != TimerPhase::Paused {
+            return None;
+        }
+        let time = timer.snapshot().current_time();
+        Some(Self {
+            paused: phase == TimerPhase::Paused,
+            split_index: timer.current_split_index().unwrap_or_default(),
+            real_time: time.real_time.unwrap_or_default().total_seconds(),
+            game_time: if timer.is_game_time_initialized() {
+                time.game_time.map(|time| time.total_seconds())
+            } else {
+                None
+            },
+            saved_at: now(),
+        })
+    }
+
+    pub fn split_index(&self) -> usize {
+        self.split_index
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Dates the attempt by this machine's clock, as it was captured the given
+    /// time ago by another machine.
+    pub fn captured_before(&mut self, duration: Duration) {
+        self.saved_at = now() - duration.as_secs_f64();
+    }
+
+    /// Moves the real time of the attempt to the time, as of now.
+    pub fn set_real_time(&mut self, time: TimeSpan) {
+        self.real_time = time.total_seconds();
+        self.saved_at = now();
+    }
+
+    /// Returns whether the timer shows this attempt, allowing the real time to
+    /// be off by the tolerance in seconds.
+    pub fn is_shown_by(&self, timer: &Timer, tolerance: f64) -> bool {
+        let phase = timer.current_phase();
+        if phase != TimerPhase::Running && phase != TimerPhase::Paused {
+            return false;
+        }
+        let elapsed = if self.paused {
+            0.0
+        } else {
+            (now() - self.saved_at).max(0.0)
+        };
+        let real_time = timer
+            .snapshot()
+            .current_time()
+            .real_time
+            .unwrap_or_default()
+            .total_seconds();
+        (phase == TimerPhase::Paused) == self.paused
+            && timer.current_split_index() == Some(self.split_index)
+            && (real_time - (self.real_time + elapsed)).abs() <= tolerance
+    }
+
+    /// Continues the attempt. The timer can't be started at an arbitrary time,
+    /// so the run's offset is changed to get there. The original offset is
+    /// returned, so that it can be put back once the attempt is over.
+    pub fn restore(&self, timer: &mut Timer) -> Option<TimeSpan> {
+        if timer.current_phase() != TimerPhase::NotRunning || self.split_index >= timer.run().len()
+        {
+            return None;
+        }
+
+        // A running attempt kept going while OBS wasn't.
+        let elapsed = if self.paused {
+            0.0
+        } else {
+            (now() - self.saved_at).max(0.0)
+        };
+
+        let offset = timer.run().offset();
+        let mut run = timer.run().clone();
+        run.set_offset(TimeSpan::from_seconds(self.real_time + elapsed));
+        timer.set_run(run).ok()?;
+        timer.start();
+
+        // The times of the splits that already happened can't be put back into
+        // the timer, so they are skipped instead.
+        for _ in 0..self.split_index {
+            timer.skip_split();
+        }
+
+        if let Some(game_time) = self.game_time {
+            timer.initialize_game_time();
+            timer.set_game_time(TimeSpan::from_seconds(game_time + elapsed));
+        }
+
+        if self.paused {
+            timer.pause();
+        }
+
+        log::info!("Restored the attempt that was in progress.");
+
+        Some(offset)
+    }
+}
+
+/// Carries the attempt that is in progress over to another timer, so that it
+/// continues with different splits. Returns the original offset of the run of
+/// the other timer.
+pub fn transfer(from: &SharedTimer, to: &SharedTimer) -> Option<TimeSpan> {
+    let attempt = SavedAttempt::capture(&from.read().unwrap())?;
+    let offset = attempt.restore(&mut to.write().unwrap())?;
+    log::info!("Carried the attempt over to the new splits.");
+    Some(offset)
+}
+
+pub unsafe fn save(timer: &Timer, settings: *mut obs_data_t) {
+    let attempt = SavedAttempt::capture(timer)
+        .and_then(|attempt| serde_json::to_string(&attempt).ok())
+        .unwrap_or_default();
+    if let Ok(attempt) = CString::new(attempt) {
+        obs_data_set_string(settings, SETTINGS_SAVED_ATTEMPT, attempt.as_ptr());
+    }
+}
+
+/// Continues the attempt that was saved in the settings and returns the
+/// original offset of the run.
+pub unsafe fn load(timer: &mut Timer, settings: *mut obs_data_t) -> Option<TimeSpan> {
+    let attempt = CStr::from_ptr(obs_data_get_string(settings, SETTINGS_SAVED_ATTEMPT));
+    let attempt: SavedAttempt = serde_json::from_slice(attempt.to_bytes()).ok()?;
+    attempt.restore(timer)
+}
+
+/// Puts the run's original offset back once the restored attempt is over.
+pub fn restore_offset(timer: &mut Timer, offset: &mut Option<TimeSpan>) {
+    if timer.current_phase() != TimerPhase::NotRunning {
+        return;
+    }
+    if let Some(offset) = offset.take() {
+        let mut run = timer.run().clone();
+        run.set_offset(offset);
+        let _ = timer.set_run(run);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use livesplit_core::{Run, Segment};
+
+    use super::*;
+
+    fn timer() -> Timer {
+        let mut run = Run::new();
+        for name in ["One", "Two", "Three"] {
+            run.push_segment(Segment::new(name));
+        }
+        Timer::new(run).unwrap()
+    }
+
+    #[test]
+    fn only_captures_attempts_in_progress() {
+        let mut timer = timer();
+        assert!(SavedAttempt::capture(&timer).is_none());
+        timer.start();
+        assert!(SavedAttempt::capture(&timer).is_some());
+        timer.reset(false);
+        assert!(SavedAttempt::capture(&timer).is_none());
+    }
+
+    #[test]
+    fn continues_the_attempt_in_another_timer() {
+        let mut from = timer();
+        from.start();
+        from.split();
+        from.pause();
+        let attempt = SavedAttempt::capture(&from).unwrap();
+        assert_eq!(attempt.split_index(), 1);
+        assert!(attempt.is_paused());
+
+        let mut to = timer();
+        assert_eq!(attempt.restore(&mut to), Some(TimeSpan::zero()));
+        assert_eq!(to.current_phase(), TimerPhase::Paused);
+        assert_eq!(to.current_split_index(), Some(1));
+        assert!(attempt.is_shown_by(&to, 0.5));
+
+        // The attempt is only continued once.
+        assert_eq!(attempt.restore(&mut to), None);
+    }
+
+    #[test]
+    fn restores_the_game_time() {
+        let attempt = SavedAttempt {
+            paused: true,
+            split_index: 0,
+            real_time: 60.0,
+            game_time: Some(30.0),
+            saved_at: now(),
+        };
+        let mut timer = timer();
+        assert!(attempt.restore(&mut timer).is_some());
+        assert!(timer.is_game_time_initialized());
+        let time = timer.snapshot().current_time();
+        assert!((time.real_time.unwrap().total_seconds() - 60.0).abs() <= 0.5);
+        assert!((time.game_time.unwrap().total_seconds() - 30.0).abs() <= 0.5);
+    }
+
+    #[test]
+    fn ignores_attempts_beyond_the_splits() {
+        let attempt = SavedAttempt {
+            paused: false,
+            split_index: 3,
+            real_time: 60.0,
+            game_time: None,
+            saved_at: now(),
+        };
+        let mut timer = timer();
+        assert_eq!(attempt.restore(&mut timer), None);
+        assert_eq!(timer.current_phase(), TimerPhase::NotRunning);
+    }
+}
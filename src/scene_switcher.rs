@@ -0,0 +1,139 @@
+use std::{
+    ffi::{c_void, CStr, CString},
+    os::raw::c_char,
+};
+
+use livesplit_core::Timer;
+
+use crate::{
+    events::{EventTracker, TimerEvent},
+    ffi::{
+        obs_data_get_string, obs_data_t, obs_enum_scenes, obs_get_source_by_name,
+        obs_properties_add_group, obs_properties_add_list, obs_properties_create, obs_properties_t,
+        obs_property_list_add_string, obs_source_get_name, obs_source_release, obs_source_t,
+        OBS_COMBO_FORMAT_STRING, OBS_COMBO_TYPE_LIST, OBS_GROUP_NORMAL,
+    },
+    frontend,
+};
+
+const SETTINGS_SCENE_SWITCHER: *const c_char = cstr!("scene_switcher");
+const SETTINGS_SCENE_ON_START: *const c_char = cstr!("scene_on_start");
+const SETTINGS_SCENE_ON_FINISH: *const c_char = cstr!("scene_on_finish");
+const SETTINGS_SCENE_ON_RESET: *const c_char = cstr!("scene_on_reset");
+
+/// The scenes to switch to when something happens to the timer. Empty names
+/// mean that the scene stays as it is.
+#[derive(PartialEq, Eq)]
+pub struct Config {
+    on_start: String,
+    on_finish: String,
+    on_reset: String,
+}
+
+impl Config {
+    fn is_empty(&self) -> bool {
+        self.on_start.is_empty() && self.on_finish.is_empty() && self.on_reset.is_empty()
+    }
+}
+
+/// Switches the scene of the frontend when the timer starts, finishes or gets
+/// reset.
+pub struct SceneSwitcher {
+    config: Config,
+    tracker: EventTracker,
+}
+
+unsafe extern "C" fn add_scene(param: *mut c_void, scene: *mut obs_source_t) -> bool {
+    let name = obs_source_get_name(scene);
+    obs_property_list_add_string(param.cast(), name, name);
+    true
+}
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let group = obs_properties_create();
+    for (name, description) in [
+        (SETTINGS_SCENE_ON_START, cstr!("When the Run Starts")),
+        (SETTINGS_SCENE_ON_FINISH, cstr!("When the Run Finishes")),
+        (SETTINGS_SCENE_ON_RESET, cstr!("When the Run Is Reset")),
+    ] {
+        let list = obs_properties_add_list(
+            group,
+            name,
+            description,
+            OBS_COMBO_TYPE_LIST,
+            OBS_COMBO_FORMAT_STRING,
+        );
+        obs_property_list_add_string(list, cstr!("Don't Switch"), cstr!(""));
+        obs_enum_scenes(Some(add_scene), list.cast());
+    }
+
+    obs_properties_add_group(
+        props,
+        SETTINGS_SCENE_SWITCHER,
+        cstr!("Switch Scenes"),
+        OBS_GROUP_NORMAL,
+        group,
+    );
+}
+
+pub unsafe fn parse_settings(settings: *mut obs_data_t) -> Option<Config> {
+    let get = |name| {
+        CStr::from_ptr(obs_data_get_string(settings, name))
+            .to_string_lossy()
+            .into_owned()
+    };
+    let config = Config {
+        on_start: get(SETTINGS_SCENE_ON_START),
+        on_finish: get(SETTINGS_SCENE_ON_FINISH),
+        on_reset: get(SETTINGS_SCENE_ON_RESET),
+    };
+    if config.is_empty() {
+        None
+    } else {
+        Some(config)
+    }
+}
+
+/// Switches to the scene on the UI thread, as the graphics thread that polls
+/// the timer must not use the frontend.
+fn switch_to(name: &str) {
+    if name.is_empty() {
+        return;
+    }
+    let Ok(name) = CString::new(name) else {
+        return;
+    };
+    frontend::queue_ui_task(move || unsafe {
+        let scene = obs_get_source_by_name(name.as_ptr());
+        if scene.is_null() {
+            log::warn!("The scene {name:?} doesn't exist.");
+            return;
+        }
+        frontend::set_current_scene(scene);
+        obs_source_release(scene);
+    });
+}
+
+impl SceneSwitcher {
+    pub fn new(config: Config, timer: &Timer) -> Self {
+        Self {
+            config,
+            tracker: EventTracker::new(timer),
+        }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn poll(&mut self, timer: &Timer) {
+        for event in self.tracker.poll(timer) {
+            match event {
+                TimerEvent::Started => switch_to(&self.config.on_start),
+                TimerEvent::Finished => switch_to(&self.config.on_finish),
+                TimerEvent::Reset => switch_to(&self.config.on_reset),
+                _ => {}
+            }
+        }
+    }
+}
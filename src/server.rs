@@ -0,0 +1,768 @@
+use std::{
+    io,
+    net::{IpAddr, Ipv4Addr, TcpListener as StdTcpListener},
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use futures_util::{SinkExt, StreamExt};
+use livesplit_core::{
+    timing::formatter::{Complete, Delta, TimeFormatter},
+    SharedTimer, Timer,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    runtime,
+    sync::{oneshot, watch},
+    time,
+};
+use tokio_tungstenite::tungstenite::{
+    handshake::server::{ErrorResponse, Request, Response as HandshakeResponse},
+    http::StatusCode,
+    Message,
+};
+
+use crate::{
+    command::{self, phase_name, Command, Response},
+    events::{self, EventTracker, TimerEvent},
+    state_export,
+};
+
+/// The control panel that is served to plain HTTP requests, so it can be added
+/// as a custom browser dock.
+const DOCK: &str = include_str!("dock.html");
+
+/// How often the timer is checked for events to send to the event streams.
+const EVENT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// Proxies close connections that are quiet for too long, so the event
+/// streams send a comment every now and then.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+/// How often subscribed WebSocket connections are sent the feedback of the
+/// timer at most.
+const FEEDBACK_INTERVAL: Duration = Duration::from_millis(100);
+/// The layout stream doesn't need to keep up with the frame rate of OBS, so it
+/// sends at most this many frames per second.
+const LAYOUT_STREAM_INTERVAL: Duration = Duration::from_millis(1000 / 30);
+const LAYOUT_STREAM_BOUNDARY: &str = "layout-frame";
+
+/// A rendered image of the layout, with premultiplied alpha.
+struct Image {
+    data: Vec<u8>,
+    size: [u32; 2],
+}
+
+type Frames = watch::Receiver<Option<Arc<Image>>>;
+
+/// Who is allowed to connect to the server.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Config {
+    pub port: u16,
+    /// Whether other devices on the network can connect too, rather than only
+    /// this computer.
+    pub lan: bool,
+    /// If not empty, every request needs to carry it as its `token` query
+    /// parameter.
+    pub token: String,
+    /// The web pages besides the dock that are allowed to connect from a
+    /// browser, like `https://example.com`.
+    pub allowed_origins: Vec<String>,
+}
+
+/// Returns the name of the host without its port, if it's followed by a valid
+/// one at all. IPv6 addresses keep their brackets.
+fn host_name(host: &str) -> Option<&str> {
+    let end = if host.starts_with('[') {
+        host.find(']')? + 1
+    } else {
+        host.find(':').unwrap_or(host.len())
+    };
+    let (name, port) = host.split_at(end);
+    let valid_port = port.is_empty()
+        || port
+            .strip_prefix(':')
+            .map_or(false, |port| port.parse::<u16>().is_ok());
+    valid_port.then_some(name)
+}
+
+impl Config {
+    /// Returns whether a request for the target may be answered. Web pages
+    /// send where they're from, as any page could connect otherwise. Only the
+    /// dock that the server serves itself and the allowed pages may connect.
+    /// Other clients don't send that, so only the token matters for them.
+    fn allows(
+        &self,
+        target: &str,
+        origin: Option<&str>,
+        host: Option<&str>,
+        local: Option<IpAddr>,
+    ) -> bool {
+        let query = target.split_once('?').map_or("", |(_, query)| query);
+        let has_token = self.token.is_empty()
+            || query
+                .split('&')
+                .any(|param| param.strip_prefix("token=") == Some(&self.token));
+        let origin_allowed = origin.map_or(true, |origin| {
+            host.map_or(false, |host| {
+                origin == format!("http://{host}") && self.is_own_host(host, local)
+            }) || self.allowed_origins.iter().any(|allowed| allowed == origin)
+        });
+        has_token && origin_allowed
+    }
+
+    /// Returns whether the host names the server itself. A page that claims
+    /// to come from the host it's sent to would otherwise be trusted for any
+    /// host, which is exactly what a DNS rebinding attack makes it send. So
+    /// only the loopback addresses count, and the address that the connection
+    /// arrived on if other devices may connect.
+    fn is_own_host(&self, host: &str, local: Option<IpAddr>) -> bool {
+        let Some(name) = host_name(host) else {
+            return false;
+        };
+        matches!(name, "localhost" | "127.0.0.1" | "[::1]")
+            || self.lan
+                && local.map_or(false, |local| match local {
+                    IpAddr::V4(local) => name == local.to_string(),
+                    IpAddr::V6(local) => name == format!("[{local}]"),
+                })
+    }
+}
+
+/// A WebSocket server that allows remote control surfaces, such as a browser
+/// dock or a phone, to control the timer. It runs on its own thread and shuts
+/// down when dropped.
+pub struct Server {
+    config: Config,
+    timer: Arc<Mutex<SharedTimer>>,
+    frames: watch::Sender<Option<Arc<Image>>>,
+    shutdown: Option<oneshot::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Server {
+    pub fn start(config: Config, timer: SharedTimer) -> io::Result<Self> {
+        let address = if config.lan {
+            Ipv4Addr::UNSPECIFIED
+        } else {
+            Ipv4Addr::LOCALHOST
+        };
+        let listener = StdTcpListener::bind((address, config.port))?;
+        listener.set_nonblocking(true)?;
+
+        let runtime = runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+
+        let timer = Arc::new(Mutex::new(timer));
+        let (frames, frames_rx) = watch::channel(None);
+        let (shutdown, shutdown_rx) = oneshot::channel();
+
+        let thread = thread::Builder::new()
+            .name("LiveSplit One Server".into())
+            .spawn({
+                let timer = timer.clone();
+                let config = Arc::new(config.clone());
+                move || {
+                    runtime.block_on(async move {
+                        let listener = match TcpListener::from_std(listener) {
+                            Ok(listener) => listener,
+                            Err(e) => {
+                                log::error!("Failed to start the server: {e}");
+                                return;
+                            }
+                        };
+                        tokio::select! {
+                            _ = accept_connections(listener, config, timer, frames_rx) => {}
+                            _ = shutdown_rx => {}
+                        }
+                    })
+                }
+            })?;
+
+        log::info!("Server listening on port {}.", config.port);
+
+        Ok(Self {
+            config,
+            timer,
+            frames,
+            shutdown: Some(shutdown),
+            thread: Some(thread),
+        })
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn set_timer(&self, timer: SharedTimer) {
+        *self.timer.lock().unwrap() = timer;
+    }
+
+    /// Makes the frame the one that the layout endpoints serve.
+    pub fn show_frame(&self, image: &[u8], size: [u32; 2]) {
+        self.frames.send_replace(Some(Arc::new(Image {
+            data: image.to_vec(),
+            size,
+        })));
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        log::info!("Server on port {} stopped.", self.config.port);
+    }
+}
+
+async fn accept_connections(
+    listener: TcpListener,
+    config: Arc<Config>,
+    timer: Arc<Mutex<SharedTimer>>,
+    frames: Frames,
+) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, addr)) => {
+                log::debug!("Accepted connection from {addr}.");
+                tokio::spawn(handle_connection(
+                    stream,
+                    config.clone(),
+                    timer.clone(),
+                    frames.clone(),
+                ));
+            }
+            Err(e) => log::warn!("Failed to accept connection: {e}"),
+        }
+    }
+}
+
+/// Returns whether the request that is about to be received asks for an
+/// upgrade to a WebSocket connection, without consuming it.
+async fn is_websocket_upgrade(stream: &TcpStream) -> bool {
+    let mut buf = [0; 2048];
+    let Ok(len) = stream.peek(&mut buf).await else {
+        return false;
+    };
+    String::from_utf8_lossy(&buf[..len])
+        .to_ascii_lowercase()
+        .contains("upgrade: websocket")
+}
+
+/// The parts of an HTTP request that the server cares about.
+#[derive(Default)]
+struct HttpRequest {
+    /// The path and the query that the request asks for.
+    target: String,
+    origin: Option<String>,
+    host: Option<String>,
+}
+
+impl HttpRequest {
+    /// The path that the request asks for, without the query.
+    fn path(&self) -> &str {
+        self.target.split('?').next().unwrap_or_default()
+    }
+
+    /// Allows the web page that sent the request to read the response, as it
+    /// was already checked that it may connect.
+    fn cors_header(&self) -> String {
+        self.origin
+            .as_ref()
+            .map(|origin| format!("Access-Control-Allow-Origin: {origin}\r\nVary: Origin\r\n"))
+            .unwrap_or_default()
+    }
+}
+
+/// Reads the head of the HTTP request.
+async fn read_request(stream: &mut TcpStream) -> Option<HttpRequest> {
+    let mut request = Vec::new();
+    let mut buf = [0; 1024];
+    while !request.windows(4).any(|end| end == b"\r\n\r\n") {
+        if request.len() > 16 * 1024 {
+            return None;
+        }
+        let len = stream.read(&mut buf).await.ok()?;
+        if len == 0 {
+            return None;
+        }
+        request.extend_from_slice(&buf[..len]);
+    }
+    let request = String::from_utf8_lossy(&request);
+    let target = request
+        .lines()
+        .next()?
+        .split_whitespace()
+        .nth(1)?
+        .to_owned();
+    let header = |name: &str| {
+        request.lines().skip(1).find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            key.trim()
+                .eq_ignore_ascii_case(name)
+                .then(|| value.trim().to_owned())
+        })
+    };
+    Some(HttpRequest {
+        origin: header("origin"),
+        host: header("host"),
+        target,
+    })
+}
+
+async fn respond(
+    mut stream: TcpStream,
+    request: &HttpRequest,
+    status: &str,
+    content_type: &str,
+    body: &[u8],
+) {
+    let header = format!(
+        "HTTP/1.1 {status}\r\n\
+         Content-Type: {content_type}\r\n\
+         Content-Length: {}\r\n\
+         {}\
+         Cache-Control: no-cache\r\n\
+         Connection: close\r\n\r\n",
+        body.len(),
+        request.cors_header(),
+    );
+    if stream.write_all(header.as_bytes()).await.is_ok() {
+        let _ = stream.write_all(body).await;
+    }
+    let _ = stream.shutdown().await;
+}
+
+/// Asks for the connection to be sent the feedback of the timer whenever it
+/// changes, or to stop that. This is the connection's business rather than
+/// the timer's, so it isn't a command.
+#[derive(Deserialize, PartialEq)]
+#[serde(tag = "command", rename_all = "camelCase")]
+enum Subscription {
+    Subscribe,
+    Unsubscribe,
+}
+
+impl Subscription {
+    fn parse(message: &str) -> Option<Self> {
+        serde_json::from_str(message).ok()
+    }
+}
+
+/// What control surfaces like Stream Decks show on their keys.
+fn feedback(timer: &Timer) -> Value {
+    let method = timer.current_timing_method();
+    json!({
+        "phase": phase_name(timer.current_phase()),
+        "time": Complete.format(timer.snapshot().current_time()[method]).to_string(),
+        "splitName": timer.current_split().map(|segment| segment.name()),
+        "splitIndex": timer.current_split_index(),
+        "delta": events::last_delta(timer).map(|delta| Delta::new().format(delta).to_string()),
+        "comparison": timer.current_comparison(),
+    })
+}
+
+fn with_timer<T>(timer: &Mutex<SharedTimer>, f: impl FnOnce(&Timer) -> T) -> T {
+    let timer = timer.lock().unwrap().clone();
+    let timer = timer.read().unwrap();
+    f(&timer)
+}
+
+fn event_name(event: TimerEvent) -> &'static str {
+    match event {
+        TimerEvent::Started => "RunStarted",
+        TimerEvent::Splitted { .. } => "Split",
+        TimerEvent::SkippedSplit { .. } => "SplitSkipped",
+        TimerEvent::UndoneSplit { .. } => "SplitUndone",
+        TimerEvent::Finished => "RunFinished",
+        TimerEvent::Reset => "RunReset",
+        TimerEvent::Paused => "RunPaused",
+        TimerEvent::Resumed => "RunResumed",
+    }
+}
+
+/// Formats a server-sent event. Every event carries the state of the timer
+/// after it happened.
+fn format_event(name: &str, index: Option<usize>, timer: &Timer) -> String {
+    let data = json!({
+        "segmentIndex": index,
+        "state": state_export::timer_state(timer),
+    });
+    format!("event: {name}\ndata: {data}\n\n")
+}
+
+/// Streams the events of the timer as server-sent events, starting with its
+/// current state.
+async fn serve_events(
+    mut stream: TcpStream,
+    request: &HttpRequest,
+    timer: Arc<Mutex<SharedTimer>>,
+) {
+    let (mut tracker, state) = with_timer(&timer, |timer| {
+        (EventTracker::new(timer), format_event("State", None, timer))
+    });
+    let header = format!(
+        "HTTP/1.1 200 OK\r\n\
+         Content-Type: text/event-stream\r\n\
+         Cache-Control: no-cache\r\n\
+         {}\r\n",
+        request.cors_header(),
+    );
+    if stream.write_all(header.as_bytes()).await.is_err()
+        || stream.write_all(state.as_bytes()).await.is_err()
+    {
+        return;
+    }
+
+    let mut interval = time::interval(EVENT_POLL_INTERVAL);
+    let mut last_write = Instant::now();
+    loop {
+        interval.tick().await;
+        let mut message = with_timer(&timer, |timer| {
+            tracker
+                .poll(timer)
+                .into_iter()
+                .map(|event| {
+                    let index = match event {
+                        TimerEvent::Splitted { index }
+                        | TimerEvent::SkippedSplit { index }
+                        | TimerEvent::UndoneSplit { index } => Some(index),
+                        _ => None,
+                    };
+                    format_event(event_name(event), index, timer)
+                })
+                .collect::<String>()
+        });
+        if message.is_empty() {
+            if last_write.elapsed() < KEEP_ALIVE_INTERVAL {
+                continue;
+            }
+            message.push_str(":\n\n");
+        }
+        if stream.write_all(message.as_bytes()).await.is_err() {
+            return;
+        }
+        last_write = Instant::now();
+    }
+}
+
+/// Encodes the image as a PNG. Its alpha isn't premultiplied in PNGs.
+pub fn encode_png(image: &[u8], [width, height]: [u32; 2]) -> Option<Vec<u8>> {
+    let mut data = image.to_vec();
+    for pixel in data.chunks_exact_mut(4) {
+        let alpha = pixel[3] as u32;
+        if alpha != 0 && alpha != 255 {
+            for channel in &mut pixel[..3] {
+                *channel = ((*channel as u32 * 255 + alpha / 2) / alpha).min(255) as u8;
+            }
+        }
+    }
+
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let result = encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&data));
+    if let Err(e) = result {
+        log::warn!("Failed to encode the layout: {e}");
+        return None;
+    }
+    Some(png)
+}
+
+/// Encodes the latest frame, if there is one already.
+fn latest_png(frames: &Frames) -> Option<Vec<u8>> {
+    // The image is shared rather than borrowed, as the graphics thread would
+    // have to wait for the encoding otherwise.
+    let image = frames.borrow().clone()?;
+    encode_png(&image.data, image.size)
+}
+
+/// Streams the frames of the layout as they change, as a multipart response
+/// that browsers show like a video in an image.
+async fn serve_layout_stream(mut stream: TcpStream, request: &HttpRequest, mut frames: Frames) {
+    let header = format!(
+        "HTTP/1.1 200 OK\r\n\
+         Content-Type: multipart/x-mixed-replace; boundary={LAYOUT_STREAM_BOUNDARY}\r\n\
+         Cache-Control: no-cache\r\n\
+         {}\r\n",
+        request.cors_header(),
+    );
+    if stream.write_all(header.as_bytes()).await.is_err() {
+        return;
+    }
+
+    loop {
+        if let Some(png) = latest_png(&frames) {
+            let part = format!(
+                "--{LAYOUT_STREAM_BOUNDARY}\r\n\
+                 Content-Type: image/png\r\n\
+                 Content-Length: {}\r\n\r\n",
+                png.len(),
+            );
+            if stream.write_all(part.as_bytes()).await.is_err()
+                || stream.write_all(&png).await.is_err()
+                || stream.write_all(b"\r\n").await.is_err()
+            {
+                return;
+            }
+        }
+        time::sleep(LAYOUT_STREAM_INTERVAL).await;
+        if frames.changed().await.is_err() {
+            return;
+        }
+    }
+}
+
+/// Answers a plain HTTP request. Besides the dock, the state of the timer, a
+/// stream of its events and the rendered layout are served for browser
+/// sources and dashboards.
+async fn serve_http(
+    mut stream: TcpStream,
+    config: &Config,
+    timer: Arc<Mutex<SharedTimer>>,
+    frames: Frames,
+) {
+    let local = stream.local_addr().ok().map(|addr| addr.ip());
+    let Some(request) = read_request(&mut stream).await else {
+        return;
+    };
+    let request = &request;
+    if !config.allows(
+        &request.target,
+        request.origin.as_deref(),
+        request.host.as_deref(),
+        local,
+    ) {
+        log::warn!("Refused a request that isn't allowed to connect.");
+        // The page that sent it isn't allowed to read the answer either.
+        respond(
+            stream,
+            &HttpRequest::default(),
+            "403 Forbidden",
+            "text/plain",
+            b"Forbidden",
+        )
+        .await;
+        return;
+    }
+    match request.path() {
+        "/state" => {
+            let state = with_timer(&timer, state_export::timer_state);
+            let body = state.to_string();
+            respond(
+                stream,
+                request,
+                "200 OK",
+                "application/json",
+                body.as_bytes(),
+            )
+            .await;
+        }
+        "/events" => serve_events(stream, request, timer).await,
+        "/layout.png" => match latest_png(&frames) {
+            Some(png) => respond(stream, request, "200 OK", "image/png", &png).await,
+            None => {
+                let body = b"The layout wasn't rendered yet.";
+                respond(
+                    stream,
+                    request,
+                    "503 Service Unavailable",
+                    "text/plain",
+                    body,
+                )
+                .await;
+            }
+        },
+        "/layout" => serve_layout_stream(stream, request, frames).await,
+        _ => {
+            respond(
+                stream,
+                request,
+                "200 OK",
+                "text/html; charset=utf-8",
+                DOCK.as_bytes(),
+            )
+            .await
+        }
+    }
+}
+
+async fn handle_connection(
+    stream: TcpStream,
+    config: Arc<Config>,
+    timer: Arc<Mutex<SharedTimer>>,
+    frames: Frames,
+) {
+    if !is_websocket_upgrade(&stream).await {
+        serve_http(stream, &config, timer, frames).await;
+        return;
+    }
+
+    let local = stream.local_addr().ok().map(|addr| addr.ip());
+    let check_access = |request: &Request, response: HandshakeResponse| {
+        let header = |name: &str| {
+            request
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+        };
+        let target = request.uri().to_string();
+        if config.allows(&target, header("origin"), header("host"), local) {
+            Ok(response)
+        } else {
+            log::warn!("Refused a WebSocket connection that isn't allowed to connect.");
+            let mut response = ErrorResponse::new(Some("Forbidden".to_owned()));
+            *response.status_mut() = StatusCode::FORBIDDEN;
+            Err(response)
+        }
+    };
+    let mut socket = match tokio_tungstenite::accept_hdr_async(stream, check_access).await {
+        Ok(socket) => socket,
+        Err(e) => {
+            log::warn!("WebSocket handshake failed: {e}");
+            return;
+        }
+    };
+
+    let mut subscribed = false;
+    let mut last_feedback = None;
+    let mut interval = time::interval(FEEDBACK_INTERVAL);
+    loop {
+        let response = tokio::select! {
+            message = socket.next() => {
+                let message = match message {
+                    Some(Ok(Message::Text(message))) => message,
+                    Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                    Some(Ok(_)) => continue,
+                };
+                match Subscription::parse(&message) {
+                    Some(subscription) => {
+                        subscribed = subscription == Subscription::Subscribe;
+                        last_feedback = None;
+                        json!({ "success": null })
+                    }
+                    None => json!(execute(&message, &timer)),
+                }
+            }
+            _ = interval.tick(), if subscribed => {
+                let feedback = with_timer(&timer, feedback);
+                if last_feedback.as_ref() == Some(&feedback) {
+                    continue;
+                }
+                last_feedback = Some(feedback.clone());
+                json!({ "feedback": feedback })
+            }
+        };
+
+        if socket
+            .send(Message::Text(response.to_string()))
+            .await
+            .is_err()
+        {
+            break;
+        }
+    }
+}
+
+fn execute(message: &str, timer: &Mutex<SharedTimer>) -> Response {
+    match Command::parse(message) {
+        Ok(command) => {
+            let timer = timer.lock().unwrap().clone();
+            match command::execute(command, &timer) {
+                Ok(value) => Response::Success(value),
+                Err(e) => Response::Error(e),
+            }
+        }
+        Err(e) => Response::Error(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(lan: bool, token: &str) -> Config {
+        Config {
+            port: 0,
+            lan,
+            token: token.into(),
+            allowed_origins: vec!["https://example.com".into()],
+        }
+    }
+
+    const LAN: Option<IpAddr> = Some(IpAddr::V4(Ipv4Addr::new(192, 168, 0, 2)));
+
+    #[test]
+    fn requires_the_token() {
+        let config = config(false, "secret");
+        assert!(config.allows("/?token=secret", None, None, None));
+        assert!(config.allows("/ws?foo=bar&token=secret", None, None, None));
+        assert!(!config.allows("/", None, None, None));
+        assert!(!config.allows("/?token=guess", None, None, None));
+        assert!(!config.allows("/?token=secrets", None, None, None));
+        assert!(config(false, "").allows("/", None, None, None));
+    }
+
+    #[test]
+    fn trusts_the_dock_on_this_computer() {
+        let config = config(false, "");
+        for host in [
+            "localhost:16834",
+            "127.0.0.1:16834",
+            "[::1]:16834",
+            "localhost",
+        ] {
+            let origin = format!("http://{host}");
+            assert!(
+                config.allows("/", Some(&origin), Some(host), None),
+                "{host}"
+            );
+        }
+    }
+
+    #[test]
+    fn refuses_rebound_hosts() {
+        let config = config(true, "");
+        let host = "evil.com:16834";
+        assert!(!config.allows("/", Some("http://evil.com:16834"), Some(host), LAN));
+        let host = "localhost:99999";
+        assert!(!config.allows("/", Some("http://localhost:99999"), Some(host), None));
+    }
+
+    #[test]
+    fn trusts_the_lan_address_only_with_lan() {
+        let origin = Some("http://192.168.0.2:16834");
+        let host = Some("192.168.0.2:16834");
+        assert!(config(true, "").allows("/", origin, host, LAN));
+        assert!(!config(false, "").allows("/", origin, host, LAN));
+        assert!(!config(true, "").allows("/", origin, host, None));
+    }
+
+    #[test]
+    fn checks_the_origin() {
+        let config = config(false, "");
+        let host = Some("localhost:16834");
+        assert!(config.allows("/", Some("https://example.com"), host, None));
+        assert!(!config.allows("/", Some("https://example.org"), host, None));
+        assert!(!config.allows("/", Some("http://localhost:16834"), None, None));
+    }
+
+    #[test]
+    fn strips_the_port() {
+        assert_eq!(host_name("localhost:16834"), Some("localhost"));
+        assert_eq!(host_name("[::1]:16834"), Some("[::1]"));
+        assert_eq!(host_name("[::1]"), Some("[::1]"));
+        assert_eq!(host_name("localhost:port"), None);
+        assert_eq!(host_name("[::1"), None);
+    }
+}
@@ -0,0 +1,108 @@
+use std::{
+    ffi::{c_void, CString},
+    os::raw::c_char,
+};
+
+use livesplit_core::{
+    timing::formatter::{Complete, TimeFormatter},
+    TimeSpan, Timer,
+};
+
+use crate::{
+    calldata::{self, CallData},
+    events::{self, EventTracker, TimerEvent},
+    ffi::{
+        calldata_t, obs_source_get_signal_handler, obs_source_t, signal_handler_add,
+        signal_handler_signal,
+    },
+};
+
+const SIGNALS: [*const c_char; 5] = [
+    cstr!("void run_started(ptr source)"),
+    cstr!(
+        "void split(ptr source, int segment_index, string segment_name, string split_time, \
+         string segment_time)"
+    ),
+    cstr!(
+        "void gold_split(ptr source, int segment_index, string segment_name, string split_time, \
+         string segment_time)"
+    ),
+    cstr!("void run_finished(ptr source, string final_time)"),
+    cstr!("void run_reset(ptr source)"),
+];
+
+/// Declares the signals on the signal handler of the source.
+pub unsafe fn register(source: *mut obs_source_t) {
+    let handler = obs_source_get_signal_handler(source);
+    for signal in SIGNALS {
+        signal_handler_add(handler, signal);
+    }
+}
+
+/// Emits the source's signals when something happens to the timer.
+pub struct Signals {
+    tracker: EventTracker,
+}
+
+unsafe fn set_time(cd: *mut calldata_t, name: *const c_char, time: Option<TimeSpan>) {
+    if let Ok(time) = CString::new(Complete.format(time).to_string()) {
+        calldata::set_string(cd, name, &time);
+    }
+}
+
+/// Fills in the parameters of the `split` and `gold_split` signals.
+unsafe fn set_split(cd: *mut calldata_t, timer: &Timer, index: usize) {
+    let method = timer.current_timing_method();
+    let segments = timer.run().segments();
+    let split_time = segments[index].split_time()[method];
+    let previous_split_time = segments[..index]
+        .iter()
+        .rev()
+        .find_map(|segment| segment.split_time()[method])
+        .unwrap_or_default();
+
+    calldata::set_int(cd, cstr!("segment_index"), index as i64);
+    if let Ok(name) = CString::new(segments[index].name()) {
+        calldata::set_string(cd, cstr!("segment_name"), &name);
+    }
+    set_time(cd, cstr!("split_time"), split_time);
+    set_time(
+        cd,
+        cstr!("segment_time"),
+        split_time.map(|time| time - previous_split_time),
+    );
+}
+
+impl Signals {
+    pub fn new(timer: &Timer) -> Self {
+        Self {
+            tracker: EventTracker::new(timer),
+        }
+    }
+
+    pub unsafe fn poll(&mut self, source: *mut obs_source_t, timer: &Timer) {
+        let handler = obs_source_get_signal_handler(source);
+        for event in self.tracker.poll(timer) {
+            let mut cd = CallData::default();
+            calldata::set_ptr(cd.as_mut_ptr(), cstr!("source"), source.cast::<c_void>());
+            let signal = match event {
+                TimerEvent::Started => cstr!("run_started"),
+                TimerEvent::Splitted { index } => {
+                    set_split(cd.as_mut_ptr(), timer, index);
+                    if events::is_gold(timer, index) {
+                        signal_handler_signal(handler, cstr!("gold_split"), cd.as_mut_ptr());
+                    }
+                    cstr!("split")
+                }
+                TimerEvent::Finished => {
+                    let time = timer.snapshot().current_time()[timer.current_timing_method()];
+                    set_time(cd.as_mut_ptr(), cstr!("final_time"), time);
+                    cstr!("run_finished")
+                }
+                TimerEvent::Reset => cstr!("run_reset"),
+                _ => continue,
+            };
+            signal_handler_signal(handler, signal, cd.as_mut_ptr());
+        }
+    }
+}
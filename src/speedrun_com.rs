@@ -0,0 +1,170 @@
+use std::{error::Error, io::Read, sync::mpsc, thread};
+
+use livesplit_core::{
+    run::editor::Editor,
+    timing::formatter::{Complete, TimeFormatter},
+    TimeSpan,
+};
+use serde_json::Value;
+
+use crate::http;
+
+const API_URL: &str = "https://www.speedrun.com/api/v1";
+
+const GAME_ID_VARIABLE: &str = "speedrun.com Game ID";
+const CATEGORY_ID_VARIABLE: &str = "speedrun.com Category ID";
+const WORLD_RECORD_VARIABLE: &str = "World Record";
+
+/// What speedrun.com knows about the game and category of a run.
+pub struct Lookup {
+    game_id: String,
+    game_name: String,
+    category_id: String,
+    category_name: String,
+    /// The variables of the category along with their default values.
+    variables: Vec<(String, String)>,
+    world_record: Option<TimeSpan>,
+    box_art: Option<Vec<u8>>,
+}
+
+fn get(agent: &ureq::Agent, path: &str, query: &[(&str, &str)]) -> Result<Value, Box<dyn Error>> {
+    let mut request = agent.get(&format!("{API_URL}{path}"));
+    for &(key, value) in query {
+        request = request.query(key, value);
+    }
+    let json: Value = serde_json::from_str(&request.call()?.into_string()?)?;
+    Ok(json["data"].clone())
+}
+
+fn string(value: &Value) -> String {
+    value.as_str().unwrap_or_default().to_owned()
+}
+
+fn download(agent: &ureq::Agent, url: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut data = Vec::new();
+    agent
+        .get(url)
+        .call()?
+        .into_reader()
+        .read_to_end(&mut data)?;
+    Ok(data)
+}
+
+/// Looks up the game and the category on a thread of their own, as this takes
+/// a few requests. The lookup can be received once it's done.
+pub fn look_up_in_background(
+    game: String,
+    category: String,
+    box_art: bool,
+) -> Option<mpsc::Receiver<Result<Lookup, String>>> {
+    let (sender, receiver) = mpsc::channel();
+    thread::Builder::new()
+        .name("LiveSplit One speedrun.com Lookup".into())
+        .spawn(move || {
+            let lookup = look_up(game.trim(), category.trim(), box_art);
+            let _ = sender.send(lookup.map_err(|e| e.to_string()));
+        })
+        .map_err(|e| log::warn!("Failed to start the lookup: {e}"))
+        .ok()?;
+    Some(receiver)
+}
+
+/// Looks up the game by its name and the category within it. Without a
+/// category, the game's first full game category is used. The game's box art
+/// is only downloaded if asked for.
+fn look_up(game: &str, category: &str, box_art: bool) -> Result<Lookup, Box<dyn Error>> {
+    let agent = http::agent();
+    let games = get(&agent, "/games", &[("name", game), ("max", "1")])?;
+    let game = games
+        .get(0)
+        .ok_or_else(|| format!("speedrun.com doesn't know the game {game}."))?;
+    let game_id = string(&game["id"]);
+    let box_art = match game["assets"]["cover-medium"]["uri"].as_str() {
+        Some(url) if box_art => match download(&agent, url) {
+            Ok(box_art) => Some(box_art),
+            Err(e) => {
+                log::warn!("Failed to download the box art: {e}");
+                None
+            }
+        },
+        _ => None,
+    };
+
+    let categories = get(&agent, &format!("/games/{game_id}/categories"), &[])?;
+    let category = categories
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|candidate| candidate["type"] == "per-game")
+        .find(|candidate| {
+            category.is_empty()
+                || candidate["name"]
+                    .as_str()
+                    .map_or(false, |name| name.eq_ignore_ascii_case(category))
+        })
+        .ok_or_else(|| format!("The game has no category called {category}."))?;
+    let category_id = string(&category["id"]);
+
+    let variables = get(&agent, &format!("/categories/{category_id}/variables"), &[])?
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|variable| {
+            let values = &variable["values"];
+            let default = values["default"]
+                .as_str()
+                .map(|id| string(&values["values"][id]["label"]))
+                .unwrap_or_default();
+            (string(&variable["name"]), default)
+        })
+        .collect();
+
+    let records = get(
+        &agent,
+        &format!("/categories/{category_id}/records"),
+        &[("top", "1")],
+    )?;
+    let world_record = records[0]["runs"][0]["run"]["times"]["primary_t"]
+        .as_f64()
+        .map(TimeSpan::from_seconds);
+
+    Ok(Lookup {
+        game_id,
+        game_name: string(&game["names"]["international"]),
+        category_id,
+        category_name: string(&category["name"]),
+        variables,
+        world_record,
+        box_art,
+    })
+}
+
+impl Lookup {
+    /// Stores the names, the IDs, the world record and the box art in the run.
+    /// Variables that the run already has keep their values.
+    pub fn apply(&self, editor: &mut Editor) {
+        editor.set_game_name(&*self.game_name);
+        if let Some(box_art) = &self.box_art {
+            editor.set_game_icon(&**box_art);
+        }
+        editor.set_category_name(&*self.category_name);
+        for (name, default) in &self.variables {
+            let exists = editor
+                .run()
+                .metadata()
+                .speedrun_com_variables()
+                .any(|(existing, _)| existing == name);
+            if !exists {
+                editor.set_speedrun_com_variable(&**name, &**default);
+            }
+        }
+        editor.set_custom_variable(GAME_ID_VARIABLE, &*self.game_id);
+        editor.set_custom_variable(CATEGORY_ID_VARIABLE, &*self.category_id);
+        if let Some(world_record) = self.world_record {
+            editor.set_custom_variable(
+                WORLD_RECORD_VARIABLE,
+                &*Complete.format(world_record).to_string(),
+            );
+        }
+    }
+}
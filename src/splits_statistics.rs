@@ -0,0 +1,77 @@
+use std::{ffi::CString, os::raw::c_char};
+
+use livesplit_core::{
+    analysis::sum_of_segments,
+    timing::formatter::{Regular, TimeFormatter},
+    SharedTimer,
+};
+
+use crate::ffi::{
+    obs_properties_add_group, obs_properties_add_text, obs_properties_create, obs_properties_t,
+    OBS_GROUP_NORMAL, OBS_TEXT_INFO,
+};
+
+const SETTINGS_STATISTICS: *const c_char = cstr!("statistics");
+const SETTINGS_STATISTICS_SUM_OF_BEST: *const c_char = cstr!("statistics_sum_of_best");
+const SETTINGS_STATISTICS_BEST_POSSIBLE_TIME: *const c_char =
+    cstr!("statistics_best_possible_time");
+const SETTINGS_STATISTICS_ATTEMPTS: *const c_char = cstr!("statistics_attempts");
+const SETTINGS_STATISTICS_COMPLETED: *const c_char = cstr!("statistics_completed");
+
+unsafe fn add_line(props: *mut obs_properties_t, name: *const c_char, text: String) {
+    if let Ok(text) = CString::new(text) {
+        obs_properties_add_text(props, name, text.as_ptr(), OBS_TEXT_INFO);
+    }
+}
+
+/// Shows what the splits add up to. The properties are recreated whenever the
+/// splits are reloaded, so they are always up to date.
+pub unsafe fn add_properties(props: *mut obs_properties_t, timer: Option<&SharedTimer>) {
+    let Some(timer) = timer else {
+        return;
+    };
+    let timer = timer.read().unwrap();
+    let run = timer.run();
+    let method = timer.current_timing_method();
+
+    let sum_of_best = sum_of_segments::calculate_best(run.segments(), false, false, method);
+    let best_possible_time = sum_of_segments::calculate_best(run.segments(), false, true, method);
+    let completed = run
+        .attempt_history()
+        .iter()
+        .filter(|attempt| attempt.time()[method].is_some())
+        .count();
+
+    let group = obs_properties_create();
+    add_line(
+        group,
+        SETTINGS_STATISTICS_SUM_OF_BEST,
+        format!("Sum of Best: {}", Regular::new().format(sum_of_best)),
+    );
+    add_line(
+        group,
+        SETTINGS_STATISTICS_BEST_POSSIBLE_TIME,
+        format!(
+            "Best Possible Time: {}",
+            Regular::new().format(best_possible_time)
+        ),
+    );
+    add_line(
+        group,
+        SETTINGS_STATISTICS_ATTEMPTS,
+        format!("Attempts: {}", run.attempt_count()),
+    );
+    add_line(
+        group,
+        SETTINGS_STATISTICS_COMPLETED,
+        format!("Completed Runs: {completed}"),
+    );
+
+    obs_properties_add_group(
+        props,
+        SETTINGS_STATISTICS,
+        cstr!("Statistics"),
+        OBS_GROUP_NORMAL,
+        group,
+    );
+}
@@ -0,0 +1,162 @@
+use std::{
+    fs,
+    os::raw::c_char,
+    path::PathBuf,
+    ptr,
+    time::{Duration, Instant},
+};
+
+use livesplit_core::{
+    layout::LayoutState,
+    timing::formatter::{Complete, TimeFormatter},
+    TimeSpan, Timer,
+};
+use serde_json::{json, Value};
+
+use crate::{
+    command::phase_name,
+    ffi::{
+        obs_data_get_int, obs_data_get_string, obs_data_set_default_int, obs_data_t,
+        obs_properties_add_group, obs_properties_add_int, obs_properties_add_path,
+        obs_properties_create, obs_properties_t, obs_property_set_long_description,
+        OBS_GROUP_NORMAL, OBS_PATH_FILE_SAVE,
+    },
+    path,
+};
+
+const SETTINGS_STATE_EXPORT: *const c_char = cstr!("state_export");
+const SETTINGS_STATE_EXPORT_PATH: *const c_char = cstr!("state_export_path");
+const SETTINGS_STATE_EXPORT_INTERVAL: *const c_char = cstr!("state_export_interval");
+
+#[derive(PartialEq, Eq)]
+pub struct Config {
+    path: PathBuf,
+    interval: Duration,
+}
+
+/// Regularly writes the state of the timer and the layout to a JSON file, for
+/// overlays and scripts that can't talk to the WebSocket server.
+pub struct StateExport {
+    config: Config,
+    last_export: Instant,
+}
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let group = obs_properties_create();
+    let path = obs_properties_add_path(
+        group,
+        SETTINGS_STATE_EXPORT_PATH,
+        cstr!("File"),
+        OBS_PATH_FILE_SAVE,
+        cstr!("JSON Files (*.json)"),
+        ptr::null(),
+    );
+    obs_property_set_long_description(
+        path,
+        cstr!("The file that the state of the timer and the layout is written to. Leave it empty to not write it."),
+    );
+    obs_properties_add_int(
+        group,
+        SETTINGS_STATE_EXPORT_INTERVAL,
+        cstr!("Interval (Milliseconds)"),
+        50,
+        60000,
+        50,
+    );
+
+    obs_properties_add_group(
+        props,
+        SETTINGS_STATE_EXPORT,
+        cstr!("State Export"),
+        OBS_GROUP_NORMAL,
+        group,
+    );
+}
+
+pub unsafe fn set_defaults(settings: *mut obs_data_t) {
+    obs_data_set_default_int(settings, SETTINGS_STATE_EXPORT_INTERVAL, 500);
+}
+
+pub unsafe fn parse_settings(settings: *mut obs_data_t) -> Option<Config> {
+    let path = path::from_obs(obs_data_get_string(settings, SETTINGS_STATE_EXPORT_PATH));
+    if path.as_os_str().is_empty() {
+        return None;
+    }
+    let interval = obs_data_get_int(settings, SETTINGS_STATE_EXPORT_INTERVAL).max(50);
+    Some(Config {
+        path,
+        interval: Duration::from_millis(interval as u64),
+    })
+}
+
+fn seconds(time: Option<TimeSpan>) -> Option<f64> {
+    time.map(|time| time.total_seconds())
+}
+
+/// The state of the timer, as it's exported and served by the server.
+pub fn timer_state(timer: &Timer) -> Value {
+    let method = timer.current_timing_method();
+    let segments: Vec<Value> = timer
+        .run()
+        .segments()
+        .iter()
+        .map(|segment| {
+            json!({
+                "name": segment.name(),
+                "splitTime": seconds(segment.split_time()[method]),
+                "personalBestSplitTime": seconds(segment.personal_best_split_time()[method]),
+                "bestSegmentTime": seconds(segment.best_segment_time()[method]),
+            })
+        })
+        .collect();
+    let time = timer.snapshot().current_time()[method];
+    json!({
+        "phase": phase_name(timer.current_phase()),
+        "splitIndex": timer.current_split_index(),
+        "comparison": timer.current_comparison(),
+        "timingMethod": method,
+        "currentTime": Complete.format(time).to_string(),
+        "currentSeconds": seconds(time),
+        "segments": segments,
+    })
+}
+
+fn export(timer: &Timer, layout: &LayoutState) -> Value {
+    let mut state = timer_state(timer);
+    state["layout"] = json!(layout);
+    state
+}
+
+impl StateExport {
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            last_export: Instant::now(),
+        }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn poll(&mut self, timer: &Timer, layout: &LayoutState) {
+        if self.last_export.elapsed() < self.config.interval {
+            return;
+        }
+        self.last_export = Instant::now();
+
+        let Ok(state) = serde_json::to_vec(&export(timer, layout)) else {
+            return;
+        };
+        // Readers shouldn't ever see a file that is only partially written,
+        // so it's written next to the file first and then moved over it.
+        let temporary = self.config.path.with_extension("json.tmp");
+        if let Err(e) =
+            fs::write(&temporary, state).and_then(|()| fs::rename(&temporary, &self.config.path))
+        {
+            log::warn!("Failed to export the state: {e}");
+            // Trying again every few milliseconds would flood the log.
+            self.last_export += Duration::from_secs(10);
+        }
+    }
+}
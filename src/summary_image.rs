@@ -0,0 +1,200 @@
+use std::{
+    error::Error,
+    ffi::c_void,
+    fs,
+    os::raw::c_char,
+    path::{Path, PathBuf},
+    ptr, thread,
+};
+
+use livesplit_core::{
+    layout::{Component, LayoutState},
+    Layout, Timer,
+};
+
+use crate::{
+    events::{EventTracker, TimerEvent},
+    ffi::{
+        obs_data_get_bool, obs_data_get_int, obs_data_get_string, obs_data_release,
+        obs_data_set_default_int, obs_data_t, obs_properties_add_bool, obs_properties_add_button,
+        obs_properties_add_group, obs_properties_add_int, obs_properties_add_path,
+        obs_properties_create, obs_properties_t, obs_property_set_long_description, obs_property_t,
+        obs_source_get_settings, OBS_GROUP_NORMAL, OBS_PATH_FILE_SAVE,
+    },
+    fonts, layout_builder, path, server, State,
+};
+
+const SETTINGS_SUMMARY_IMAGE: *const c_char = cstr!("summary_image");
+const SETTINGS_SUMMARY_IMAGE_PATH: *const c_char = cstr!("summary_image_path");
+const SETTINGS_SUMMARY_IMAGE_WIDTH: *const c_char = cstr!("summary_image_width");
+const SETTINGS_SUMMARY_IMAGE_ON_FINISH: *const c_char = cstr!("summary_image_on_finish");
+const SETTINGS_SUMMARY_IMAGE_SAVE: *const c_char = cstr!("summary_image_save");
+
+/// The components of the image. The splits show every segment of the run.
+const COMPONENTS: [&str; 4] = ["Title", "Splits", "Timer", "Sum of Best"];
+
+pub struct Config {
+    path: PathBuf,
+    width: u32,
+    layout: Layout,
+}
+
+/// Exports the summary image whenever a run finishes.
+pub struct SummaryImage {
+    config: Config,
+    tracker: EventTracker,
+}
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let group = obs_properties_create();
+    obs_properties_add_path(
+        group,
+        SETTINGS_SUMMARY_IMAGE_PATH,
+        cstr!("File"),
+        OBS_PATH_FILE_SAVE,
+        cstr!("PNG Images (*.png)"),
+        ptr::null(),
+    );
+    obs_properties_add_int(
+        group,
+        SETTINGS_SUMMARY_IMAGE_WIDTH,
+        cstr!("Width"),
+        100,
+        4000,
+        10,
+    );
+    let on_finish = obs_properties_add_bool(
+        group,
+        SETTINGS_SUMMARY_IMAGE_ON_FINISH,
+        cstr!("Export When the Run Finishes"),
+    );
+    obs_property_set_long_description(
+        on_finish,
+        cstr!("The image is replaced by every run that finishes, so copy it somewhere else to keep it."),
+    );
+    obs_properties_add_button(
+        group,
+        SETTINGS_SUMMARY_IMAGE_SAVE,
+        cstr!("Export Summary Image"),
+        Some(export),
+    );
+
+    obs_properties_add_group(
+        props,
+        SETTINGS_SUMMARY_IMAGE,
+        cstr!("Summary Image"),
+        OBS_GROUP_NORMAL,
+        group,
+    );
+}
+
+pub unsafe fn set_defaults(settings: *mut obs_data_t) {
+    obs_data_set_default_int(settings, SETTINGS_SUMMARY_IMAGE_WIDTH, 400);
+}
+
+/// The image is styled like the layout builder, regardless of the size of the
+/// source.
+unsafe fn parse_config(settings: *mut obs_data_t) -> Config {
+    Config {
+        path: path::from_obs(obs_data_get_string(settings, SETTINGS_SUMMARY_IMAGE_PATH)),
+        width: obs_data_get_int(settings, SETTINGS_SUMMARY_IMAGE_WIDTH).clamp(100, 4000) as u32,
+        layout: layout_builder::build_with(settings, &COMPONENTS.map(|name| name.to_owned())),
+    }
+}
+
+pub unsafe fn parse_settings(settings: *mut obs_data_t) -> Option<Config> {
+    let config = parse_config(settings);
+    if obs_data_get_bool(settings, SETTINGS_SUMMARY_IMAGE_ON_FINISH)
+        && !config.path.as_os_str().is_empty()
+    {
+        Some(config)
+    } else {
+        None
+    }
+}
+
+/// Lays out the splits of the timer with all their final times and deltas.
+fn layout_state(timer: &Timer, layout: &mut Layout) -> LayoutState {
+    let segment_count = timer.run().len();
+    for component in &mut layout.components {
+        if let Component::Splits(splits) = component {
+            let settings = splits.settings_mut();
+            settings.visual_split_count = segment_count;
+            settings.split_preview_count = 0;
+            settings.fill_with_blank_space = false;
+        }
+    }
+    layout.state(&timer.snapshot())
+}
+
+/// Renders the state at the height that fits all of it.
+fn render(state: &LayoutState, width: u32) -> Option<Vec<u8>> {
+    let mut renderer = fonts::renderer();
+    let mut size = [width, width];
+    if let Some([new_width, new_height]) = renderer.render(state, size) {
+        size = [
+            new_width.round().max(1.0) as u32,
+            new_height.round().max(1.0) as u32,
+        ];
+        renderer.render(state, size);
+    }
+    server::encode_png(renderer.image_data(), size)
+}
+
+fn write_image(state: &LayoutState, width: u32, path: &Path) -> Result<(), Box<dyn Error>> {
+    let png = render(state, width).ok_or("The image couldn't be encoded.")?;
+    fs::write(path, png)?;
+    Ok(())
+}
+
+/// Only the state is taken on the graphics thread. Rendering, encoding and
+/// writing the image happen on a thread of their own, as they take a while
+/// for long runs.
+fn export_to(timer: &Timer, config: &mut Config) {
+    let state = layout_state(timer, &mut config.layout);
+    let (width, path) = (config.width, config.path.clone());
+    let result = thread::Builder::new()
+        .name("LiveSplit One Summary Image".into())
+        .spawn(move || match write_image(&state, width, &path) {
+            Ok(()) => log::info!("Exported the summary image to {}.", path.display()),
+            Err(e) => log::warn!("Failed to export the summary image: {e}"),
+        });
+    if let Err(e) = result {
+        log::warn!("Failed to export the summary image: {e}");
+    }
+}
+
+impl SummaryImage {
+    pub fn new(config: Config, timer: &Timer) -> Self {
+        Self {
+            config,
+            tracker: EventTracker::new(timer),
+        }
+    }
+
+    pub fn poll(&mut self, timer: &Timer) {
+        for event in self.tracker.poll(timer) {
+            if event == TimerEvent::Finished {
+                export_to(timer, &mut self.config);
+            }
+        }
+    }
+}
+
+unsafe extern "C" fn export(
+    _: *mut obs_properties_t,
+    _: *mut obs_property_t,
+    data: *mut c_void,
+) -> bool {
+    let state: &State = &*data.cast();
+    let settings = obs_source_get_settings(state.source);
+    let mut config = parse_config(settings);
+    obs_data_release(settings);
+
+    if config.path.as_os_str().is_empty() {
+        log::warn!("Choose the file to export the summary image to first.");
+        return false;
+    }
+    export_to(&state.timer.read().unwrap(), &mut config);
+    false
+}
@@ -0,0 +1,233 @@
+use std::{
+    ffi::CStr,
+    io, mem,
+    os::raw::c_char,
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use futures_util::{SinkExt, StreamExt};
+use livesplit_core::{TimeSpan, Timer, TimerPhase};
+use serde::{de::IgnoredAny, Deserialize};
+use tokio::{net::TcpStream, runtime, sync::oneshot, time};
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+use crate::{
+    ffi::{
+        obs_data_get_string, obs_data_t, obs_properties_add_text, obs_properties_t,
+        obs_property_set_long_description, OBS_TEXT_DEFAULT,
+    },
+    saved_attempt::SavedAttempt,
+};
+
+const SETTINGS_SYNC_ADDRESS: *const c_char = cstr!("sync_address");
+
+const REQUEST: &str = r#"{"command":"getAttempt"}"#;
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+/// How far the time of the mirrored timer may be off before it's synchronized
+/// again. Synchronizing loses the split times, so this shouldn't be reached by
+/// the usual jitter of the network.
+const TOLERANCE: f64 = 0.5;
+
+/// The state of the timer that is mirrored.
+#[derive(Deserialize)]
+struct Remote {
+    phase: String,
+    attempt: Option<SavedAttempt>,
+}
+
+/// The reply to the request. Other frames, like events or feedback, don't
+/// parse as one.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum Reply {
+    Success(Remote),
+    Error(IgnoredAny),
+}
+
+/// Mirrors the timer of another instance's WebSocket server, so that multiple
+/// OBS instances show the same timer. The connection runs on its own thread
+/// and is closed when dropped.
+pub struct Mirror {
+    address: String,
+    latest: Arc<Mutex<Option<Remote>>>,
+    shutdown: Option<oneshot::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+pub unsafe fn add_properties(props: *mut obs_properties_t) {
+    let address = obs_properties_add_text(
+        props,
+        SETTINGS_SYNC_ADDRESS,
+        cstr!("Mirror the Timer of"),
+        OBS_TEXT_DEFAULT,
+    );
+    obs_property_set_long_description(
+        address,
+        cstr!("The address and port of another OBS instance's WebSocket server, e.g. 192.168.0.2:16834. The timer follows that instance's timer."),
+    );
+}
+
+pub unsafe fn parse_settings(settings: *mut obs_data_t) -> Option<String> {
+    let address = CStr::from_ptr(obs_data_get_string(settings, SETTINGS_SYNC_ADDRESS))
+        .to_string_lossy()
+        .trim()
+        .to_owned();
+    Some(address).filter(|address| !address.is_empty())
+}
+
+impl Mirror {
+    pub fn start(address: String) -> io::Result<Self> {
+        let runtime = runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+
+        let latest = Arc::new(Mutex::new(None));
+        let (shutdown, shutdown_rx) = oneshot::channel();
+
+        let thread = thread::Builder::new()
+            .name("LiveSplit One Mirror".into())
+            .spawn({
+                let address = address.clone();
+                let latest = latest.clone();
+                move || {
+                    runtime.block_on(async move {
+                        tokio::select! {
+                            _ = follow(&address, &latest) => {}
+                            _ = shutdown_rx => {}
+                        }
+                    })
+                }
+            })?;
+
+        Ok(Self {
+            address,
+            latest,
+            shutdown: Some(shutdown),
+            thread: Some(thread),
+        })
+    }
+
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Brings the timer to the latest state of the mirrored timer. If the run's
+    /// offset had to be changed for that, the original offset is returned.
+    pub fn poll(&self, timer: &mut Timer) -> Option<TimeSpan> {
+        let remote = self.latest.lock().unwrap().take()?;
+        let phase = timer.current_phase();
+
+        let Some(attempt) = remote.attempt else {
+            match &*remote.phase {
+                "NotRunning" if phase != TimerPhase::NotRunning => timer.reset(false),
+                "Ended" if phase == TimerPhase::Running || phase == TimerPhase::Paused => {
+                    let last = timer.run().len() - 1;
+                    while timer
+                        .current_split_index()
+                        .map_or(false, |index| index < last)
+                    {
+                        timer.skip_split();
+                    }
+                    timer.split();
+                }
+                _ => {}
+            }
+            return None;
+        };
+
+        // Single splits, undos and pauses are followed as they happen, so
+        // that the split times are kept.
+        if let Some(index) = timer.current_split_index() {
+            if attempt.split_index() == index + 1 {
+                timer.split();
+            } else if attempt.split_index() + 1 == index {
+                timer.undo_split();
+            }
+            if attempt.is_paused() != (timer.current_phase() == TimerPhase::Paused) {
+                timer.toggle_pause();
+            }
+        }
+
+        if attempt.is_shown_by(timer, TOLERANCE) {
+            return None;
+        }
+        log::info!("Synchronizing the timer with {}.", self.address);
+        timer.reset(false);
+        attempt.restore(timer)
+    }
+}
+
+impl Drop for Mirror {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Keeps connecting to the other instance and receiving its timer.
+async fn follow(address: &str, latest: &Mutex<Option<Remote>>) {
+    let mut warned = false;
+    loop {
+        match connect_async(format!("ws://{address}")).await {
+            Ok((socket, _)) => {
+                log::info!("Mirroring the timer of {address}.");
+                warned = false;
+                match receive(socket, latest).await {
+                    Ok(()) => log::info!("{address} closed the connection."),
+                    Err(e) => log::warn!("Lost the connection to {address}: {e}"),
+                }
+            }
+            Err(e) => {
+                if !mem::replace(&mut warned, true) {
+                    log::warn!("Failed to connect to {address}: {e}");
+                }
+            }
+        }
+        time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+async fn receive(
+    mut socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    latest: &Mutex<Option<Remote>>,
+) -> Result<(), tokio_tungstenite::tungstenite::Error> {
+    let mut interval = time::interval(POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+        let sent_at = Instant::now();
+        socket.send(Message::Text(REQUEST.into())).await?;
+        let reply = loop {
+            match socket.next().await {
+                Some(Ok(Message::Text(frame))) => {
+                    // The other instance may send frames of its own before the
+                    // reply, so they are skipped.
+                    if let Ok(reply) = serde_json::from_str::<Reply>(&frame) {
+                        break reply;
+                    }
+                }
+                Some(Ok(Message::Close(_))) | None => return Ok(()),
+                Some(Ok(_)) => continue,
+                Some(Err(e)) => return Err(e),
+            }
+        };
+
+        // The clocks of the two machines may differ, so the attempt is dated
+        // by when it was captured on this machine's clock. That happened about
+        // half the round trip ago.
+        let latency = sent_at.elapsed() / 2;
+        let Reply::Success(mut remote) = reply else {
+            continue;
+        };
+        if let Some(attempt) = &mut remote.attempt {
+            attempt.captured_before(latency);
+        }
+        *latest.lock().unwrap() = Some(remote);
+    }
+}
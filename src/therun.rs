@@ -0,0 +1,91 @@
+use std::{sync::mpsc, thread};
+
+use livesplit_core::{TimeSpan, Timer, TimerPhase, TimingMethod};
+use serde_json::{json, Value};
+
+use crate::events::EventTracker;
+
+const LIVE_URL: &str = "https://dspc6ekj2gjkfp44cjaffhjeue0fbswr.lambda-url.eu-west-1.on.aws/";
+
+/// Sends the state of the run to therun.gg whenever something happens to the
+/// timer, so viewers can follow along live. The uploads happen on a separate
+/// thread, so they don't block rendering.
+pub struct TheRun {
+    upload_key: String,
+    tracker: EventTracker,
+    sender: mpsc::Sender<Value>,
+}
+
+impl TheRun {
+    pub fn new(upload_key: String, timer: &Timer) -> Option<Self> {
+        let (sender, receiver) = mpsc::channel::<Value>();
+
+        thread::Builder::new()
+            .name("therun.gg Uploader".into())
+            .spawn(move || {
+                for data in receiver {
+                    if let Err(e) = ureq::post(LIVE_URL)
+                        .set("Content-Type", "application/json")
+                        .send_string(&data.to_string())
+                    {
+                        log::warn!("Failed to upload the run to therun.gg: {e}");
+                    }
+                }
+            })
+            .map_err(|e| log::warn!("Failed to start the therun.gg uploader: {e}"))
+            .ok()?;
+
+        Some(Self {
+            upload_key,
+            tracker: EventTracker::new(timer),
+            sender,
+        })
+    }
+
+    pub fn upload_key(&self) -> &str {
+        &self.upload_key
+    }
+
+    pub fn poll(&mut self, timer: &Timer) {
+        if !self.tracker.poll(timer).is_empty() {
+            let _ = self.sender.send(self.live_data(timer));
+        }
+    }
+
+    fn live_data(&self, timer: &Timer) -> Value {
+        let run = timer.run();
+        let method = timer.current_timing_method();
+        let milliseconds = |time: Option<TimeSpan>| time.map(|t| t.total_milliseconds());
+
+        let run_data: Vec<Value> = run
+            .segments()
+            .iter()
+            .map(|segment| {
+                json!({
+                    "name": segment.name(),
+                    "splitTime": milliseconds(segment.split_time()[method]),
+                    "pbSplitTime": milliseconds(segment.personal_best_split_time()[method]),
+                    "bestPossible": milliseconds(segment.best_segment_time()[method]),
+                })
+            })
+            .collect();
+
+        json!({
+            "uploadKey": self.upload_key,
+            "metadata": {
+                "game": run.game_name(),
+                "category": run.category_name(),
+            },
+            "currentTime": milliseconds(timer.current_time()[method]),
+            "currentSplitName": timer.current_split().map_or("", |s| s.name()),
+            "currentSplitIndex": timer.current_split_index().map_or(-1, |i| i as i64),
+            "timingMethod": match method {
+                TimingMethod::RealTime => 0,
+                TimingMethod::GameTime => 1,
+            },
+            "isPaused": timer.current_phase() == TimerPhase::Paused,
+            "currentComparison": timer.current_comparison(),
+            "runData": run_data,
+        })
+    }
+}
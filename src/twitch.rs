@@ -0,0 +1,125 @@
+use std::{sync::mpsc, thread};
+
+use livesplit_core::{
+    timing::formatter::{Complete, TimeFormatter},
+    Timer,
+};
+use serde_json::{json, Value};
+
+use crate::events::{self, EventTracker, TimerEvent};
+
+const API_URL: &str = "https://api.twitch.tv/helix";
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Credentials {
+    pub client_id: String,
+    pub token: String,
+}
+
+/// Creates a stream marker on Twitch for every split and for personal bests,
+/// so the interesting moments of a stream are easy to find afterwards. The
+/// requests happen on a separate thread, so they don't block rendering.
+pub struct StreamMarkers {
+    credentials: Credentials,
+    tracker: EventTracker,
+    sender: mpsc::Sender<String>,
+}
+
+fn request(credentials: &Credentials, method: &str, path: &str) -> ureq::Request {
+    ureq::request(method, &format!("{API_URL}{path}"))
+        .set("Client-Id", &credentials.client_id)
+        .set("Authorization", &format!("Bearer {}", credentials.token))
+}
+
+/// Markers need to be created for a specific broadcaster, which is the user
+/// that the token belongs to.
+fn user_id(credentials: &Credentials) -> Result<String, Box<dyn std::error::Error>> {
+    let response: Value = serde_json::from_str(
+        &request(credentials, "GET", "/users")
+            .call()?
+            .into_string()?,
+    )?;
+    response["data"][0]["id"]
+        .as_str()
+        .map(ToOwned::to_owned)
+        .ok_or_else(|| "The token doesn't belong to a user.".into())
+}
+
+fn create_marker(
+    credentials: &Credentials,
+    user_id: &str,
+    description: &str,
+) -> Result<(), ureq::Error> {
+    request(credentials, "POST", "/streams/markers")
+        .set("Content-Type", "application/json")
+        .send_string(&json!({ "user_id": user_id, "description": description }).to_string())?;
+    Ok(())
+}
+
+impl StreamMarkers {
+    pub fn new(credentials: Credentials, timer: &Timer) -> Option<Self> {
+        let (sender, receiver) = mpsc::channel::<String>();
+
+        thread::Builder::new()
+            .name("Twitch Stream Markers".into())
+            .spawn({
+                let credentials = credentials.clone();
+                move || {
+                    let mut user = None;
+                    for description in receiver {
+                        if user.is_none() {
+                            match user_id(&credentials) {
+                                Ok(id) => user = Some(id),
+                                Err(e) => {
+                                    log::warn!("Failed to query the Twitch user: {e}");
+                                    continue;
+                                }
+                            }
+                        }
+                        if let Some(user) = &user {
+                            if let Err(e) = create_marker(&credentials, user, &description) {
+                                log::warn!("Failed to create the stream marker: {e}");
+                            }
+                        }
+                    }
+                }
+            })
+            .map_err(|e| log::warn!("Failed to start creating Twitch stream markers: {e}"))
+            .ok()?;
+
+        Some(Self {
+            credentials,
+            tracker: EventTracker::new(timer),
+            sender,
+        })
+    }
+
+    pub fn credentials(&self) -> &Credentials {
+        &self.credentials
+    }
+
+    pub fn poll(&mut self, timer: &Timer) {
+        for event in self.tracker.poll(timer) {
+            let description = match event {
+                TimerEvent::Splitted { index } => {
+                    let name = timer.run().segment(index).name();
+                    if events::is_gold(timer, index) {
+                        format!("Gold: {name}")
+                    } else {
+                        format!("Split: {name}")
+                    }
+                }
+                TimerEvent::Finished if events::is_personal_best(timer) => {
+                    let method = timer.current_timing_method();
+                    format!(
+                        "Personal Best: {}",
+                        Complete.format(timer.current_time()[method])
+                    )
+                }
+                _ => continue,
+            };
+            // Twitch limits the description to 140 characters.
+            let _ = self.sender.send(description.chars().take(140).collect());
+        }
+    }
+}
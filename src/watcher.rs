@@ -0,0 +1,99 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{self, AtomicBool},
+        Arc, Mutex, Weak,
+    },
+    time::SystemTime,
+};
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+
+/// Watches a single file for changes. The parent directory is watched instead
+/// of the file itself, as a lot of editors replace files instead of writing
+/// into them, which would otherwise stop the watch.
+pub struct FileWatcher {
+    path: PathBuf,
+    changed: Arc<AtomicBool>,
+    modified: Arc<Mutex<Option<SystemTime>>>,
+    _watcher: RecommendedWatcher,
+}
+
+/// The last seen modification times of all the watchers, by the file they
+/// watch. Sources that share a timer each watch its splits, so all of them
+/// need to know when one of them saved the splits.
+static WATCHED: Mutex<Vec<(PathBuf, Weak<Mutex<Option<SystemTime>>>)>> = Mutex::new(Vec::new());
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+impl FileWatcher {
+    pub fn new(path: &Path) -> Option<Self> {
+        let parent = path.parent()?;
+        let file_name = path.file_name()?.to_owned();
+        let changed = Arc::new(AtomicBool::new(false));
+
+        let mut watcher = notify::recommended_watcher({
+            let changed = changed.clone();
+            move |event: notify::Result<notify::Event>| {
+                if let Ok(event) = event {
+                    if (event.kind.is_create() || event.kind.is_modify())
+                        && event
+                            .paths
+                            .iter()
+                            .any(|p| p.file_name() == Some(&*file_name))
+                    {
+                        changed.store(true, atomic::Ordering::Relaxed);
+                    }
+                }
+            }
+        })
+        .map_err(|e| log::warn!("Failed to create file watcher: {e}"))
+        .ok()?;
+
+        watcher
+            .watch(parent, RecursiveMode::NonRecursive)
+            .map_err(|e| log::warn!("Failed to watch {}: {e}", path.display()))
+            .ok()?;
+
+        let seen = Arc::new(Mutex::new(modified(path)));
+        let mut watched = WATCHED.lock().unwrap();
+        watched.retain(|(_, seen)| seen.strong_count() > 0);
+        watched.push((path.to_owned(), Arc::downgrade(&seen)));
+
+        Some(Self {
+            path: path.to_owned(),
+            changed,
+            modified: seen,
+            _watcher: watcher,
+        })
+    }
+
+    /// Returns whether the file changed since the last time this was called.
+    pub fn poll(&mut self) -> bool {
+        if !self.changed.swap(false, atomic::Ordering::Relaxed) {
+            return false;
+        }
+        let modified = modified(&self.path);
+        let mut seen = self.modified.lock().unwrap();
+        if modified == *seen {
+            return false;
+        }
+        *seen = modified;
+        true
+    }
+}
+
+/// Treats the current contents of the file as already seen by every watcher
+/// of it. This is used when we write to the file ourselves.
+pub fn mark_unchanged(path: &Path) {
+    let modified = modified(path);
+    let watched = WATCHED.lock().unwrap();
+    for (_, seen) in watched.iter().filter(|(file, _)| file == path) {
+        if let Some(seen) = seen.upgrade() {
+            *seen.lock().unwrap() = modified;
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::{sync::mpsc, thread};
+
+use livesplit_core::{
+    timing::formatter::{Complete, TimeFormatter},
+    Timer,
+};
+
+use crate::events::{self, EventTracker, TimerEvent};
+
+#[derive(PartialEq, Eq)]
+pub struct Config {
+    pub url: String,
+    pub template: String,
+}
+
+/// Sends an HTTP POST request to a user provided URL whenever something
+/// happens to the timer. The body is built from a template, so it can be
+/// adapted to whatever service receives it. The requests happen on a separate
+/// thread, so they don't block rendering.
+pub struct Webhook {
+    config: Config,
+    tracker: EventTracker,
+    sender: mpsc::Sender<String>,
+}
+
+/// Escapes the value, so it can be placed inside of a JSON string in the
+/// template.
+fn escape(value: &str) -> String {
+    let quoted = serde_json::Value::from(value).to_string();
+    quoted[1..quoted.len() - 1].to_owned()
+}
+
+impl Webhook {
+    pub fn new(config: Config, timer: &Timer) -> Option<Self> {
+        let (sender, receiver) = mpsc::channel::<String>();
+
+        thread::Builder::new()
+            .name("Webhook".into())
+            .spawn({
+                let url = config.url.clone();
+                move || {
+                    for body in receiver {
+                        if let Err(e) = ureq::post(&url)
+                            .set("Content-Type", "application/json")
+                            .send_string(&body)
+                        {
+                            log::warn!("Failed to send the webhook: {e}");
+                        }
+                    }
+                }
+            })
+            .map_err(|e| log::warn!("Failed to start the webhook: {e}"))
+            .ok()?;
+
+        Some(Self {
+            config,
+            tracker: EventTracker::new(timer),
+            sender,
+        })
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn poll(&mut self, timer: &Timer) {
+        for event in self.tracker.poll(timer) {
+            // Only the most specific event is sent, so a gold is not also sent
+            // as a split.
+            let (name, segment) = match event {
+                TimerEvent::Started => ("start", None),
+                TimerEvent::Splitted { index } if events::is_gold(timer, index) => {
+                    ("gold", Some(index))
+                }
+                TimerEvent::Splitted { index } => ("split", Some(index)),
+                TimerEvent::Finished if events::is_personal_best(timer) => ("personal_best", None),
+                TimerEvent::Finished => ("finish", None),
+                TimerEvent::Reset => ("reset", None),
+                _ => continue,
+            };
+            let _ = self.sender.send(self.body(timer, name, segment));
+        }
+    }
+
+    fn body(&self, timer: &Timer, event: &str, segment: Option<usize>) -> String {
+        let run = timer.run();
+        let method = timer.current_timing_method();
+        let segment = segment.map(|index| run.segment(index));
+
+        self.config
+            .template
+            .replace("{event}", event)
+            .replace("{game}", &escape(run.game_name()))
+            .replace("{category}", &escape(run.category_name()))
+            .replace(
+                "{segment}",
+                &escape(segment.map_or("", |segment| segment.name())),
+            )
+            .replace(
+                "{time}",
+                &Complete.format(timer.current_time()[method]).to_string(),
+            )
+            .replace(
+                "{split_time}",
+                &Complete
+                    .format(segment.and_then(|segment| segment.split_time()[method]))
+                    .to_string(),
+            )
+    }
+}